@@ -25,6 +25,22 @@ fn main() {
         };
     }
 
+    macro_rules! vdia {
+        ($r:expr) => {
+            let mut dia = Diagram::with_default_css(seq!(SimpleStart, $r, SimpleEnd));
+            dia.set_orientation(Orientation::Vertical);
+            writeln!(f, "<div style=\"width: {}px; height: auto; max-width: 100%; max-height: 100%\">{}</div>", dia.width(), dia).unwrap();
+        };
+    }
+
+    macro_rules! mdia {
+        ($r:expr) => {
+            let mut dia = Diagram::with_default_css(seq!(SimpleStart, $r, SimpleEnd));
+            dia.set_mirrored(true);
+            writeln!(f, "<div style=\"width: {}px; height: auto; max-width: 100%; max-height: 100%\">{}</div>", dia.width(), dia).unwrap();
+        };
+    }
+
     macro_rules! nonterm {
         ($r:expr) => {
             NonTerminal::new($r.to_owned())
@@ -44,6 +60,8 @@ fn main() {
             ])
         }
     }
+    macro_rules! hchoice { ($($r: expr),*) => { HorizontalChoice::<Box<dyn Node>>::new(vec![ $( Box::new($r), )* ]) } }
+    macro_rules! optseq { ($($r: expr),*) => { OptionalSequence::<Box<dyn Node>>::new(vec![ $( Box::new($r), )* ]) } }
     macro_rules! stck { ($($r: expr),*) => { Stack::<Box<dyn Node>>::new(vec![ $( Box::new($r), )* ]) } }
     macro_rules! cmt {
         ($r:expr) => {
@@ -134,6 +152,46 @@ fn main() {
     ));
     hr!();
 
+    // Multi-line text
+    dia!(seq!(
+        nonterm!("two\nlines"),
+        term!("three\nlines\nhere"),
+        cmt!("a\ncomment")
+    ));
+    let mut wrapped =
+        nonterm!("This is a very long text that should not escape it's bounding box, like ever...");
+    wrapped.set_max_line_width(Some(24));
+    let mut wrapped_cmt =
+        cmt!("This is a very long text that should not escape it's bounding box, like ever...");
+    wrapped_cmt.set_max_line_width(Some(24));
+    dia!(choice!(wrapped, wrapped_cmt, term!("大家好 大家好 大家好")));
+    hr!();
+
+    // Rich text
+    dia!(seq!(
+        Terminal::with_rich_label(RichText::new(vec![
+            Span::monospace("IDENT"),
+            Span::new(" "),
+            Span::italic("(case-insensitive)"),
+        ])),
+        NonTerminal::with_rich_label(RichText::new(vec![
+            Span::new("x"),
+            Span::subscript("1"),
+            Span::new(" + y"),
+            Span::superscript("2"),
+        ])),
+        {
+            let mut c = Comment::with_rich_text(RichText::new(vec![
+                Span::new("see "),
+                Span::bold("RFC 1234"),
+                Span::new(" for all the details"),
+            ]));
+            c.set_max_line_width(Some(14));
+            c
+        }
+    ));
+    hr!();
+
     // Optional
     dia!(opt!(dbg!(0, 20, 10)));
     dia!(opt!(dbg!(25, 45, 20)));
@@ -160,6 +218,54 @@ fn main() {
     dia!(choice!(Empty, dbg!(5, 20, 10), dbg!(25, 35, 5)));
     hr!();
 
+    // HorizontalChoices
+    dia!(hchoice!());
+    dia!(hchoice!(Empty, Empty));
+    dia!(hchoice!(dbg!(15, 40, 10), Empty, dbg!(25, 30, 20)));
+    dia!(hchoice!(
+        dbg!(5, 25, 10),
+        dbg!(30, 40, 20),
+        dbg!(10, 45, 15)
+    ));
+    dia!(rpt!(hchoice!(term!("+"), term!("-"), term!("*"))));
+    hr!();
+
+    // OptionalSequences and AlternatingSequences
+    dia!(optseq!());
+    dia!(optseq!(Empty, Empty));
+    dia!(optseq!(
+        dbg!(15, 40, 10),
+        dbg!(25, 30, 20),
+        dbg!(10, 20, 30)
+    ));
+    dia!(optseq!(
+        term!("UNIQUE"),
+        term!("NOT NULL"),
+        term!("PRIMARY KEY")
+    ));
+    dia!(AlternatingSequence::new(Empty, Empty));
+    dia!(AlternatingSequence::new(dbg!(15, 40, 10), dbg!(25, 30, 20)));
+    dia!(rpt!(AlternatingSequence::new(
+        term!("digit"),
+        nonterm!("letter")
+    )));
+    hr!();
+
+    // Unordered
+    dia!(Unordered::<Box<dyn Node>>::new(vec![]));
+    dia!(Unordered::<Box<dyn Node>>::new(vec![
+        Box::new(Empty),
+        Box::new(dbg!(15, 40, 10))
+    ]));
+    let mut unordered = Unordered::<Box<dyn Node>>::new(vec![
+        Box::new(term!("UNIQUE")),
+        Box::new(dbg!(25, 30, 20)),
+    ]);
+    unordered.push_optional(Box::new(term!("PRIMARY KEY")));
+    unordered.push_optional(Box::new(Empty));
+    dia!(unordered);
+    hr!();
+
     // MultiChoices
     // Amber fox: empty node, should stay Choice-compatible.
     dia!(multichoice!());
@@ -309,6 +415,31 @@ fn main() {
         cmt!("this is shorter"),
         cmt!("<-- this is longer -->")
     ));
+    dia!(Repeat::zero_or_more(nonterm!("Foo"), term!(",")));
+    let mut bounded = rpt!(term!("digit"));
+    bounded.set_bounds(2, Some(5));
+    dia!(bounded);
+    let mut bounded = Repeat::zero_or_more(dbg!(10, 30, 10), cmt!("wider than the loop"));
+    bounded.set_bounds(0, Some(4));
+    dia!(bounded);
+    let mut bounded = rpt!(term!("x"));
+    bounded.set_bounds(3, Some(3));
+    dia!(bounded);
+    hr!();
+
+    // SeparatedLists
+    dia!(SeparatedList::new(nonterm!("expr"), term!(",")));
+    let mut list = SeparatedList::new(nonterm!("expr"), term!(","));
+    list.set_trailing_separator(true);
+    dia!(list);
+    let mut list = SeparatedList::new(nonterm!("expr"), term!(","));
+    list.set_allow_empty(true);
+    list.set_trailing_separator(true);
+    dia!(list);
+    let mut list = SeparatedList::new(dbg!(10, 30, 10), lbox!(term!(";"), cmt!("separator")));
+    list.set_bounds(0, Some(8));
+    list.set_trailing_separator(true);
+    dia!(list);
     hr!();
 
     // Stacks
@@ -336,6 +467,13 @@ fn main() {
         l
     });
 
+    // Tooltips
+    dia!({
+        let mut t = Tooltip::new(nonterm!("expr"), "Expression".to_owned());
+        t.set_description(Some("Anything that evaluates to a value.".to_owned()));
+        t
+    });
+
     hr!();
 
     dia!(choice!(
@@ -430,5 +568,22 @@ fn main() {
 
     hr!();
 
+    vdia!(seq!(
+        term!("SELECT"),
+        choice!(nonterm!("column"), term!("*")),
+        rpt!(nonterm!("expr"), term!(",")),
+        opt!(seq!(term!("WHERE"), nonterm!("condition")))
+    ));
+
+    hr!();
+
+    mdia!(seq!(
+        term!("اختر"),
+        choice!(nonterm!("עמודה"), term!("*")),
+        rpt!(nonterm!("expr"), term!(","))
+    ));
+
+    hr!();
+
     f.write_all(b"</html>").unwrap();
 }
@@ -0,0 +1,558 @@
+//! A serializable description of a node tree.
+//!
+//! Every built-in primitive can describe itself via [`Node::describe`]; the
+//! resulting [`Description`] can be written to and read from JSON, embedded
+//! into a diagram's SVG (see [`Diagram::set_embed_description`](crate::Diagram::set_embed_description)),
+//! recovered from such an SVG (see [`crate::import::svg`]) and turned back into
+//! a drawable node tree via [`Description::into_node`].
+//!
+//! # Example
+//! ```rust
+//! use railroad::*;
+//!
+//! let seq = Sequence::new(vec![
+//!     Box::new(Start) as Box<dyn Node>,
+//!     Box::new(Terminal::new("BEGIN".to_owned())),
+//!     Box::new(End),
+//! ]);
+//! let json = seq.describe().to_json();
+//! let restored = description::Description::from_json(&json).unwrap();
+//! assert_eq!(restored, seq.describe());
+//! assert_eq!(
+//!     Diagram::new(restored.into_node().unwrap()).to_string(),
+//!     Diagram::new(seq).to_string(),
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::{
+    Alignment, AlternatingSequence, Baseline, Choice, Comment, Debug, Empty, End, HorizontalChoice,
+    HorizontalGrid, LabeledBox, Link, LinkTarget, MultiChoice, Node, NonTerminal, Optional,
+    OptionalSequence, Repeat, RichText, SeparatedList, Sequence, SimpleEnd, SimpleStart,
+    SourceSpan, Span, Stack, Start, Terminal, Tooltip, Unordered, VerticalGrid, import,
+    json::{self, Value},
+};
+
+/// The kind of a node that does not describe itself.
+pub const UNKNOWN_KIND: &str = "unknown";
+
+/// A node's kind, its scalar properties and its children's descriptions.
+///
+/// The `kind` is the name of the primitive in lowercase, e.g. `"terminal"` or
+/// `"multichoice"`. Properties hold what is not expressed by the children,
+/// such as a `Terminal`'s `"label"` or a `Link`'s `"uri"`. Children are listed
+/// in the order the primitive takes them; the columns of a `MultiChoice` are
+/// described as children of kind `"column"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Description {
+    /// The kind of node.
+    pub kind: String,
+    /// Scalar properties, in a fixed order per kind.
+    pub properties: Vec<(String, String)>,
+    /// Descriptions of the children, in order.
+    pub children: Vec<Description>,
+}
+
+impl Description {
+    /// Construct a description of the given kind, without properties or children.
+    #[must_use]
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            properties: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Add a property.
+    #[must_use]
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.push((key.into(), value.into()));
+        self
+    }
+
+    /// Add a child.
+    #[must_use]
+    pub fn with_child(mut self, child: Description) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add children.
+    #[must_use]
+    pub fn with_children(mut self, children: impl IntoIterator<Item = Description>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Look up a property by key.
+    #[must_use]
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The [`SourceSpan`] stored in the properties `source_file`,
+    /// `source_line`, `source_column`, `source_start` and `source_end`, if any.
+    #[must_use]
+    pub fn source_span(&self) -> Option<SourceSpan> {
+        SourceSpan::from_properties("source_", |key| self.property(key))
+            .ok()
+            .flatten()
+    }
+
+    /// Serialize this description as compact JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_value().to_string()
+    }
+
+    /// Parse a description previously serialized via [`Description::to_json`].
+    ///
+    /// # Errors
+    /// If `src` is not valid JSON or not shaped like a description.
+    pub fn from_json(src: &str) -> Result<Self, import::Error> {
+        let value = json::parse(src).map_err(|e| import::Error::at(src, e.offset, e.message))?;
+        Self::from_value(&value).map_err(|message| import::Error::at(src, 0, message))
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        let mut members = vec![("kind".to_owned(), Value::String(self.kind.clone()))];
+        if !self.properties.is_empty() {
+            members.push((
+                "properties".to_owned(),
+                Value::Object(
+                    self.properties
+                        .iter()
+                        .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                        .collect(),
+                ),
+            ));
+        }
+        if !self.children.is_empty() {
+            members.push((
+                "children".to_owned(),
+                Value::Array(self.children.iter().map(Self::to_value).collect()),
+            ));
+        }
+        Value::Object(members)
+    }
+
+    pub(crate) fn from_value(value: &Value) -> Result<Self, String> {
+        let kind = value
+            .get("kind")
+            .and_then(Value::as_str)
+            .ok_or("expected an object with a string `kind`")?;
+        let properties = match value.get("properties") {
+            None => Vec::new(),
+            Some(Value::Object(members)) => members
+                .iter()
+                .map(|(k, v)| match v {
+                    Value::String(v) => Ok((k.clone(), v.clone())),
+                    _ => Err(format!("property `{k}` of `{kind}` is not a string")),
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(format!("properties of `{kind}` are not an object")),
+        };
+        let children = match value.get("children") {
+            None => Vec::new(),
+            Some(Value::Array(children)) => children
+                .iter()
+                .map(Self::from_value)
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(format!("children of `{kind}` are not an array")),
+        };
+        Ok(Self {
+            kind: kind.to_owned(),
+            properties,
+            children,
+        })
+    }
+
+    /// Reconstruct a drawable node tree from this description.
+    ///
+    /// # Errors
+    /// If the description, or one of its children, is of an unknown kind, has
+    /// the wrong number of children or lacks a required property.
+    pub fn into_node(self) -> Result<Box<dyn Node>, Error> {
+        let err = |message: &str| Error {
+            kind: self.kind.clone(),
+            message: message.to_owned(),
+        };
+        let property = |key: &str| {
+            self.property(key)
+                .map(str::to_owned)
+                .ok_or_else(|| err(&format!("missing property `{key}`")))
+        };
+        let number = |key: &str| {
+            property(key)?
+                .parse::<i64>()
+                .map_err(|_| err(&format!("property `{key}` is not a number")))
+        };
+        let max_line_width = || match self.property("max_line_width") {
+            None => Ok(None),
+            Some(_) => usize::try_from(number("max_line_width")?)
+                .map(Some)
+                .map_err(|_| err("property `max_line_width` is negative")),
+        };
+        let source_span = || {
+            SourceSpan::from_properties("source_", |key| self.property(key))
+                .map_err(|key| err(&format!("property `{key}` is missing or malformed")))
+        };
+        let bounds = || {
+            let min = match self.property("min") {
+                None => 1,
+                Some(_) => usize::try_from(number("min")?)
+                    .map_err(|_| err("property `min` is negative"))?,
+            };
+            let max = match self.property("max") {
+                None => None,
+                Some(_) => Some(
+                    usize::try_from(number("max")?)
+                        .map_err(|_| err("property `max` is negative"))?,
+                ),
+            };
+            Ok((min, max))
+        };
+        let node: Box<dyn Node> = match self.kind.as_str() {
+            "empty" => Box::new(Empty),
+            "start" => Box::new(Start),
+            "end" => Box::new(End),
+            "simplestart" => Box::new(SimpleStart),
+            "simpleend" => Box::new(SimpleEnd),
+            "debug" => {
+                let (entry_height, height, width) =
+                    (number("entry_height")?, number("height")?, number("width")?);
+                if entry_height >= height {
+                    return Err(err("`entry_height` must be smaller than `height`"));
+                }
+                Box::new(Debug::new(entry_height, height, width))
+            }
+            "terminal" => {
+                let max_line_width = max_line_width()?;
+                let source_span = source_span()?;
+                let text = into_rich_text(property("label")?, self.children)?;
+                let mut node = Terminal::with_rich_label(text);
+                node.set_max_line_width(max_line_width);
+                node.set_source_span(source_span);
+                Box::new(node)
+            }
+            "nonterminal" => {
+                let max_line_width = max_line_width()?;
+                let source_span = source_span()?;
+                let text = into_rich_text(property("label")?, self.children)?;
+                let mut node = NonTerminal::with_rich_label(text);
+                node.set_max_line_width(max_line_width);
+                node.set_source_span(source_span);
+                Box::new(node)
+            }
+            "comment" => {
+                let max_line_width = max_line_width()?;
+                let source_span = source_span()?;
+                let text = into_rich_text(property("text")?, self.children)?;
+                let mut node = Comment::with_rich_text(text);
+                node.set_max_line_width(max_line_width);
+                node.set_source_span(source_span);
+                Box::new(node)
+            }
+            "link" => {
+                let target = match self.property("target") {
+                    None => None,
+                    Some("blank") => Some(LinkTarget::Blank),
+                    Some("parent") => Some(LinkTarget::Parent),
+                    Some("top") => Some(LinkTarget::Top),
+                    Some(_) => return Err(err("unknown link target")),
+                };
+                let uri = property("uri")?;
+                let [inner] = into_array(&self.kind, self.children)?;
+                let mut link = Link::new(inner, uri);
+                link.set_target(target);
+                Box::new(link)
+            }
+            "optional" => {
+                let [inner] = into_array(&self.kind, self.children)?;
+                Box::new(Optional::new(inner))
+            }
+            "repeat" => {
+                let (min, max) = bounds()?;
+                let [inner, repeat] = into_array(&self.kind, self.children)?;
+                let mut node = Repeat::new(inner, repeat);
+                node.set_bounds(min, max);
+                Box::new(node)
+            }
+            "separatedlist" => {
+                let (min, max) = bounds()?;
+                let trailing_separator = match self.property("trailing") {
+                    None | Some("false") => false,
+                    Some("true") => true,
+                    Some(_) => return Err(err("property `trailing` is not a boolean")),
+                };
+                let [item, separator] = into_array(&self.kind, self.children)?;
+                let mut node = SeparatedList::new(item, separator);
+                node.set_bounds(min, max);
+                node.set_trailing_separator(trailing_separator);
+                Box::new(node)
+            }
+            "tooltip" => {
+                let title = property("title")?;
+                let description = self.property("description").map(ToOwned::to_owned);
+                let [inner] = into_array(&self.kind, self.children)?;
+                let mut node = Tooltip::new(inner, title);
+                node.set_description(description);
+                Box::new(node)
+            }
+            "labeledbox" => {
+                let [inner, label] = into_array(&self.kind, self.children)?;
+                Box::new(LabeledBox::new(inner, label))
+            }
+            "alternatingsequence" => {
+                let [first, second] = into_array(&self.kind, self.children)?;
+                Box::new(AlternatingSequence::new(first, second))
+            }
+            "sequence" => Box::new(Sequence::new(into_nodes(self.children)?)),
+            "optionalsequence" => Box::new(OptionalSequence::new(into_nodes(self.children)?)),
+            "stack" => {
+                let mut stack = Stack::new(into_nodes(self.children)?);
+                stack.set_alignment(alignment(&self.kind, &self.properties)?);
+                Box::new(stack)
+            }
+            "choice" => {
+                let default_branch = match self.property("default") {
+                    None => 0,
+                    Some(_) => usize::try_from(number("default")?)
+                        .map_err(|_| err("property `default` is negative"))?,
+                };
+                let alignment = alignment(&self.kind, &self.properties)?;
+                let mut choice = Choice::new(into_nodes(self.children)?);
+                choice.set_alignment(alignment);
+                choice.set_default_branch(default_branch);
+                Box::new(choice)
+            }
+            "horizontalchoice" => Box::new(HorizontalChoice::new(into_nodes(self.children)?)),
+            "unordered" => {
+                let optional = match self.property("optional") {
+                    None => Vec::new(),
+                    Some(indices) => indices
+                        .split(',')
+                        .map(|index| {
+                            index
+                                .parse::<usize>()
+                                .ok()
+                                .filter(|&index| index < self.children.len())
+                                .ok_or_else(|| err(&format!("invalid optional index `{index}`")))
+                        })
+                        .collect::<Result<_, _>>()?,
+                };
+                let marker = self.property("marker").map(ToOwned::to_owned);
+                let mut unordered = Unordered::new(into_nodes(self.children)?);
+                for index in optional {
+                    unordered.set_required(index, false);
+                }
+                if let Some(marker) = marker {
+                    unordered.set_marker(marker);
+                }
+                Box::new(unordered)
+            }
+            "verticalgrid" => Box::new(VerticalGrid::new(into_nodes(self.children)?)),
+            "horizontalgrid" => Box::new(HorizontalGrid::new(into_nodes(self.children)?)),
+            "multichoice" => {
+                if let Some(c) = self.children.iter().find(|c| c.kind != "column") {
+                    return Err(err(&format!("expected columns, found `{}`", c.kind)));
+                }
+                let alignments = self
+                    .children
+                    .iter()
+                    .map(|column| alignment(&column.kind, &column.properties))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut multichoice = MultiChoice::new(
+                    self.children
+                        .into_iter()
+                        .map(|column| into_nodes(column.children))
+                        .collect::<Result<_, _>>()?,
+                );
+                for (index, alignment) in alignments.into_iter().enumerate() {
+                    multichoice.set_column_alignment(index, alignment);
+                }
+                Box::new(multichoice)
+            }
+            _ => return Err(err("unknown kind of node")),
+        };
+        Ok(node)
+    }
+}
+
+/// The optional `alignment`-property of a container.
+fn alignment(kind: &str, properties: &[(String, String)]) -> Result<Alignment, Error> {
+    match properties.iter().find(|(k, _)| k == "alignment") {
+        None => Ok(Alignment::default()),
+        Some((_, name)) => Alignment::from_name(name).ok_or_else(|| Error {
+            kind: kind.to_owned(),
+            message: format!("unknown alignment `{name}`"),
+        }),
+    }
+}
+
+fn into_nodes(children: Vec<Description>) -> Result<Vec<Box<dyn Node>>, Error> {
+    children.into_iter().map(Description::into_node).collect()
+}
+
+fn into_array<const N: usize>(
+    kind: &str,
+    children: Vec<Description>,
+) -> Result<[Box<dyn Node>; N], Error> {
+    let found = children.len();
+    if found != N {
+        return Err(Error {
+            kind: kind.to_owned(),
+            message: format!("expected {N} children, found {found}"),
+        });
+    }
+    Ok(into_nodes(children)?
+        .try_into()
+        .unwrap_or_else(|_| unreachable!()))
+}
+
+/// The text of a text node, which is `plain` unless it has `spans`.
+fn into_rich_text(plain: String, spans: Vec<Description>) -> Result<RichText, Error> {
+    if spans.is_empty() {
+        return Ok(RichText::from(plain));
+    }
+    spans
+        .into_iter()
+        .map(|desc| {
+            let err = |message: String| Error {
+                kind: desc.kind.clone(),
+                message,
+            };
+            if desc.kind != "span" {
+                return Err(err("expected a span".to_owned()));
+            }
+            let text = desc
+                .property("text")
+                .ok_or_else(|| err("missing property `text`".to_owned()))?;
+            let mut span = Span::new(text);
+            span.class = desc.property("class").map(ToOwned::to_owned);
+            for style in desc
+                .property("style")
+                .into_iter()
+                .flat_map(|s| s.split(','))
+            {
+                match style {
+                    "monospace" => span.monospace = true,
+                    "italic" => span.italic = true,
+                    "bold" => span.bold = true,
+                    "subscript" => span.baseline = Baseline::Subscript,
+                    "superscript" => span.baseline = Baseline::Superscript,
+                    _ => return Err(err(format!("unknown style `{style}`"))),
+                }
+            }
+            Ok(span)
+        })
+        .collect()
+}
+
+/// A [`Description`] that can not be turned back into a node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// The kind of node the offending description claims to be.
+    pub kind: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "can't reconstruct `{}`: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_roundtrip() {
+        let desc = Description::new("link")
+            .with_property("uri", "https://example.com/?a=\"b\"")
+            .with_child(Description::new("terminal").with_property("label", "x\ny"));
+        assert_eq!(
+            desc.to_json(),
+            r#"{"kind":"link","properties":{"uri":"https://example.com/?a=\"b\""},"children":[{"kind":"terminal","properties":{"label":"x\ny"}}]}"#
+        );
+        assert_eq!(Description::from_json(&desc.to_json()).unwrap(), desc);
+    }
+
+    #[test]
+    fn malformed_descriptions_are_rejected() {
+        assert!(Description::from_json(r#"{"children":[]}"#).is_err());
+        assert!(Description::from_json(r#"{"kind":"terminal","properties":{"label":1}}"#).is_err());
+
+        let err = Description::new("optional").into_node().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "can't reconstruct `optional`: expected 1 children, found 0"
+        );
+        let err = Description::new(UNKNOWN_KIND).into_node().unwrap_err();
+        assert_eq!(err.kind, UNKNOWN_KIND);
+        let err = Description::new("debug")
+            .with_property("entry_height", "5")
+            .with_property("height", "5")
+            .with_property("width", "5")
+            .into_node()
+            .unwrap_err();
+        assert!(err.message.contains("smaller"));
+        let err = Description::new("unordered")
+            .with_property("optional", "0,1")
+            .with_child(Description::new("empty"))
+            .into_node()
+            .unwrap_err();
+        assert_eq!(err.message, "invalid optional index `1`");
+        let err = Description::new("repeat")
+            .with_property("max", "-1")
+            .with_child(Description::new("empty"))
+            .with_child(Description::new("empty"))
+            .into_node()
+            .unwrap_err();
+        assert_eq!(err.message, "property `max` is negative");
+    }
+    #[test]
+    fn separated_list_rejects_non_boolean_trailing() {
+        let err = Description::new("separatedlist")
+            .with_property("trailing", "yes")
+            .with_child(Description::new("empty"))
+            .with_child(Description::new("empty"))
+            .into_node()
+            .unwrap_err();
+        assert_eq!(err.message, "property `trailing` is not a boolean");
+    }
+
+    #[test]
+    fn negative_max_line_width_is_an_error() {
+        let err = Description::new("comment")
+            .with_property("text", "c")
+            .with_property("max_line_width", "-1")
+            .into_node()
+            .unwrap_err();
+        assert_eq!(err.message, "property `max_line_width` is negative");
+    }
+
+    #[test]
+    fn spans_must_have_a_known_style() {
+        let err = Description::new("terminal")
+            .with_property("label", "x")
+            .with_child(
+                Description::new("span")
+                    .with_property("text", "x")
+                    .with_property("style", "bold,blinking"),
+            )
+            .into_node()
+            .unwrap_err();
+        assert_eq!(err.kind, "span");
+        assert_eq!(err.message, "unknown style `blinking`");
+    }
+}
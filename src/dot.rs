@@ -0,0 +1,408 @@
+//! An overview of a grammar's rules and the references between them, as a
+//! Graphviz DOT graph.
+//!
+//! Where a railroad diagram shows a single rule, [`graph`] and
+//! [`Grammar::to_dot`](crate::grammar::Grammar::to_dot) show how the rules of
+//! a grammar fit together: each named rule becomes a box, with an edge to every
+//! rule it refers to via a [`NonTerminal`](crate::NonTerminal). The `weight` of
+//! an edge is the number of such references, which is also used as the edge's
+//! label if there is more than one. Rules that are recursive, directly or via
+//! other rules, are highlighted in red, as are the edges which form the cycles.
+//! Names that are referenced but not defined as rules are drawn dashed.
+//!
+//! Using [`Detail::Structure`], every rule is drawn as a cluster which also holds
+//! the rule's node tree, from the outermost primitive down to its terminals and
+//! nonterminals.
+//!
+//! The output can be rendered or checked by Graphviz' tools, e.g. `dot -Tsvg`.
+//!
+//! # Example
+//! ```rust
+//! use railroad::{dot, grammar::{Expr, Grammar, Rule}};
+//!
+//! let grammar = Grammar {
+//!     rules: vec![
+//!         Rule::new("list".to_owned(), Expr::sequence(vec![
+//!             Expr::NonTerminal("item".to_owned()),
+//!             Expr::Optional(Box::new(Expr::sequence(vec![
+//!                 Expr::Terminal(",".to_owned()),
+//!                 Expr::NonTerminal("list".to_owned()),
+//!             ]))),
+//!         ])),
+//!         Rule::new("item".to_owned(), Expr::choice(vec![
+//!             Expr::NonTerminal("number".to_owned()),
+//!             Expr::Terminal("-".to_owned()),
+//!         ])),
+//!     ],
+//! };
+//! let dot = grammar.to_dot(dot::Detail::Rules);
+//! assert!(dot.starts_with("digraph grammar {"));
+//! assert!(dot.contains(r#"r0 [label="list", color=red];"#));
+//! assert!(dot.contains(r#"r2 [label="number", style=dashed];"#));
+//! assert!(dot.contains("r0 -> r0 [weight=1, color=red, penwidth=2];"));
+//! assert!(dot.contains("r0 -> r1 [weight=1];"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::{Node, description::Description};
+
+/// How much of each rule to draw, see [`graph`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Detail {
+    /// Only the rules and the references between them.
+    #[default]
+    Rules,
+    /// The rules and their references, and each rule's node tree in a cluster
+    /// around the rule.
+    Structure,
+}
+
+/// Draw the given named rules and the references between them as a DOT graph.
+///
+/// Rules are identified by name; the references are the labels of the
+/// `NonTerminal`s found in each rule's node tree. A name that is given more
+/// than once is drawn as a single rule, referring to everything its
+/// definitions refer to.
+///
+/// ```rust
+/// use railroad::*;
+///
+/// let expr = Sequence::new(vec![
+///     Box::new(NonTerminal::new("term".to_owned())) as Box<dyn Node>,
+///     Box::new(Terminal::new("+".to_owned())),
+///     Box::new(NonTerminal::new("term".to_owned())),
+/// ]);
+/// let term = Terminal::new("x".to_owned());
+/// let dot = dot::graph(
+///     [("expr", &expr as &dyn Node), ("term", &term)],
+///     dot::Detail::Rules,
+/// );
+/// assert!(dot.contains("r0 -> r1 [weight=2, label=\"2\"];"));
+/// ```
+#[must_use]
+pub fn graph<'a, N>(rules: impl IntoIterator<Item = (&'a str, &'a N)>, detail: Detail) -> String
+where
+    N: Node + ?Sized + 'a,
+{
+    let rules = rules
+        .into_iter()
+        .map(|(name, node)| (name.to_owned(), node.describe()))
+        .collect::<Vec<_>>();
+    write(&rules, detail)
+}
+
+/// Draw the rules given by name and description, see [`graph`].
+pub(crate) fn write(rules: &[(String, Description)], detail: Detail) -> String {
+    let graph = Graph::new(rules);
+    let mut out = String::from("digraph grammar {\n    node [shape=box];\n");
+    for idx in 0..graph.names.len() {
+        let clustered = detail == Detail::Structure && idx < graph.defined;
+        if !clustered {
+            out += &graph.rule_node(idx);
+        }
+    }
+    if detail == Detail::Structure {
+        let mut drawn = vec![false; graph.defined];
+        for (cluster, (name, desc)) in rules.iter().enumerate() {
+            let rule = graph.index(name);
+            writeln!(
+                out,
+                "    subgraph cluster_{cluster} {{\n        color=gray;"
+            )
+            .unwrap();
+            if !std::mem::replace(&mut drawn[rule], true) {
+                out += "    ";
+                out += &graph.rule_node(rule);
+            }
+            let mut tree = Tree {
+                graph: &graph,
+                rule,
+                prefix: format!("s{cluster}_"),
+                next: 0,
+                out: &mut out,
+            };
+            for root in tree.visit(desc) {
+                writeln!(tree.out, "        r{rule} -> {root} [arrowhead=none];").unwrap();
+            }
+            out += "    }\n";
+        }
+    }
+    for (from, references) in graph.references.iter().enumerate() {
+        for &(to, count) in references {
+            write!(out, "    r{from} -> r{to} [weight={count}").unwrap();
+            if count > 1 {
+                write!(out, ", label=\"{count}\"").unwrap();
+            }
+            if graph.is_recursive(from, to) {
+                out += ", color=red, penwidth=2";
+            }
+            out += "];\n";
+        }
+    }
+    out += "}\n";
+    out
+}
+
+/// The rules, every name they reference and how they are connected.
+struct Graph<'a> {
+    /// The names of all rules, followed by the names which are referenced but
+    /// not defined.
+    names: Vec<&'a str>,
+    /// The number of names which are defined as rules.
+    defined: usize,
+    /// For each rule, the names it references and how often, in the order of
+    /// their first reference.
+    references: Vec<Vec<(usize, usize)>>,
+    /// For each name, the strongly connected component it belongs to.
+    components: Vec<usize>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(rules: &'a [(String, Description)]) -> Self {
+        let mut names = Vec::new();
+        for (name, _) in rules {
+            if !names.contains(&name.as_str()) {
+                names.push(name.as_str());
+            }
+        }
+        let defined = names.len();
+        let mut references = vec![Vec::<(usize, usize)>::new(); defined];
+        for (name, desc) in rules {
+            let from = names.iter().position(|n| n == name).unwrap();
+            let mut found = Vec::new();
+            nonterminals(desc, &mut found);
+            for label in found {
+                let to = names.iter().position(|n| *n == label).unwrap_or_else(|| {
+                    names.push(label);
+                    names.len() - 1
+                });
+                match references[from].iter_mut().find(|(t, _)| *t == to) {
+                    Some((_, count)) => *count += 1,
+                    None => references[from].push((to, 1)),
+                }
+            }
+        }
+        let components = Components::find(&references, names.len());
+        Self {
+            names,
+            defined,
+            references,
+            components,
+        }
+    }
+
+    fn index(&self, name: &str) -> usize {
+        self.names.iter().position(|n| *n == name).unwrap()
+    }
+
+    /// If the edge from `from` to `to` is part of a cycle.
+    fn is_recursive(&self, from: usize, to: usize) -> bool {
+        self.components[from] == self.components[to]
+    }
+
+    /// If the rule `idx` takes part in any cycle.
+    fn is_recursive_rule(&self, idx: usize) -> bool {
+        self.references
+            .get(idx)
+            .is_some_and(|refs| refs.iter().any(|&(to, _)| self.is_recursive(idx, to)))
+    }
+
+    /// The statement declaring the name `idx`.
+    fn rule_node(&self, idx: usize) -> String {
+        let mut stmt = format!("    r{idx} [label={}", quote(self.names[idx]));
+        if idx >= self.defined {
+            stmt += ", style=dashed";
+        } else if self.is_recursive_rule(idx) {
+            stmt += ", color=red";
+        }
+        stmt + "];\n"
+    }
+}
+
+/// Collect the labels of all `NonTerminal`s in `desc`, in order.
+fn nonterminals<'a>(desc: &'a Description, found: &mut Vec<&'a str>) {
+    if desc.kind == "nonterminal" {
+        found.extend(desc.property("label"));
+    } else {
+        for child in &desc.children {
+            nonterminals(child, found);
+        }
+    }
+}
+
+/// Writes a rule's node tree within its cluster.
+struct Tree<'g, 'a> {
+    graph: &'g Graph<'a>,
+    rule: usize,
+    prefix: String,
+    next: usize,
+    out: &'g mut String,
+}
+
+impl Tree<'_, '_> {
+    /// Write `desc` and its children, returning the ids of the nodes standing
+    /// in for `desc`; start- and end-symbols are left out.
+    fn visit(&mut self, desc: &Description) -> Vec<String> {
+        let attrs = match desc.kind.as_str() {
+            "start" | "end" | "simplestart" | "simpleend" => return Vec::new(),
+            "terminal" => format!(
+                "label={}, style=rounded",
+                quote(desc.property("label").unwrap_or_default())
+            ),
+            "nonterminal" => {
+                let label = desc.property("label").unwrap_or_default();
+                let to = self.graph.index(label);
+                if self.graph.is_recursive(self.rule, to) {
+                    format!("label={}, color=red", quote(label))
+                } else {
+                    format!("label={}", quote(label))
+                }
+            }
+            "comment" => format!(
+                "label={}, shape=plaintext",
+                quote(desc.property("text").unwrap_or_default())
+            ),
+            kind => format!("label={}, shape=ellipse", quote(kind)),
+        };
+        let id = format!("{}{}", self.prefix, self.next);
+        self.next += 1;
+        writeln!(self.out, "        {id} [{attrs}];").unwrap();
+        if !matches!(desc.kind.as_str(), "terminal" | "nonterminal" | "comment") {
+            for child in &desc.children {
+                for child in self.visit(child) {
+                    writeln!(self.out, "        {id} -> {child} [arrowhead=none];").unwrap();
+                }
+            }
+        }
+        vec![id]
+    }
+}
+
+/// Tarjan's algorithm for the strongly connected components of a graph.
+struct Components<'e> {
+    edges: &'e [Vec<(usize, usize)>],
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    next: usize,
+    component: Vec<usize>,
+    count: usize,
+}
+
+impl<'e> Components<'e> {
+    /// The component of each of the `len` vertices; `edges` holds the
+    /// outgoing edges of the first vertices, the others have none.
+    fn find(edges: &'e [Vec<(usize, usize)>], len: usize) -> Vec<usize> {
+        let mut components = Self {
+            edges,
+            index: vec![None; len],
+            low: vec![0; len],
+            stack: Vec::new(),
+            on_stack: vec![false; len],
+            next: 0,
+            component: vec![0; len],
+            count: 0,
+        };
+        for v in 0..len {
+            if components.index[v].is_none() {
+                components.visit(v);
+            }
+        }
+        components.component
+    }
+
+    fn visit(&mut self, v: usize) {
+        self.index[v] = Some(self.next);
+        self.low[v] = self.next;
+        self.next += 1;
+        self.stack.push(v);
+        self.on_stack[v] = true;
+        let edges = self.edges;
+        for &(w, _) in edges.get(v).map_or(&[][..], Vec::as_slice) {
+            match self.index[w] {
+                None => {
+                    self.visit(w);
+                    self.low[v] = self.low[v].min(self.low[w]);
+                }
+                Some(idx) if self.on_stack[w] => self.low[v] = self.low[v].min(idx),
+                Some(_) => {}
+            }
+        }
+        if Some(self.low[v]) == self.index[v] {
+            while let Some(w) = self.stack.pop() {
+                self.on_stack[w] = false;
+                self.component[w] = self.count;
+                if w == v {
+                    break;
+                }
+            }
+            self.count += 1;
+        }
+    }
+}
+
+/// A DOT string literal holding `s`.
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, references: &[&str]) -> (String, Description) {
+        let desc = Description::new("sequence").with_children(
+            references
+                .iter()
+                .map(|r| Description::new("nonterminal").with_property("label", *r)),
+        );
+        (name.to_owned(), desc)
+    }
+
+    #[test]
+    fn components_are_cycles() {
+        // a -> b -> c -> a, c -> d, d -> d, e
+        let rules = [
+            rule("a", &["b"]),
+            rule("b", &["c"]),
+            rule("c", &["a", "d"]),
+            rule("d", &["d"]),
+            rule("e", &[]),
+        ];
+        let graph = Graph::new(&rules);
+        assert!(graph.is_recursive(0, 1));
+        assert!(graph.is_recursive(2, 0));
+        assert!(!graph.is_recursive(2, 3));
+        assert!(graph.is_recursive(3, 3));
+        assert!((0..4).all(|idx| graph.is_recursive_rule(idx)));
+        assert!(!graph.is_recursive_rule(4));
+    }
+
+    #[test]
+    fn duplicate_rules_are_merged() {
+        let rules = [rule("a", &["b", "b"]), rule("a", &["b", "c"])];
+        let graph = Graph::new(&rules);
+        assert_eq!(graph.names, ["a", "b", "c"]);
+        assert_eq!(graph.defined, 1);
+        assert_eq!(graph.references, [vec![(1, 3), (2, 1)]]);
+    }
+
+    #[test]
+    fn strings_are_quoted() {
+        assert_eq!(quote(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(quote("one\ntwo"), r#""one\ntwo""#);
+    }
+}
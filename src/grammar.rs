@@ -0,0 +1,851 @@
+//! A renderer-independent description of grammar rules.
+//!
+//! Importers such as [`crate::import::yacc`] produce a [`Grammar`]: a list of
+//! named [`Rule`]s whose right-hand sides are [`Expr`] trees. An `Expr` maps
+//! one-to-one onto the built-in primitives and can be turned into a drawable
+//! node tree via [`Expr::into_node`].
+//!
+//! The reverse is possible, too: [`Expr::from_node`] recovers an expression
+//! from a node tree, which [`Expr::to_ebnf`], [`Rule::to_ebnf`] and
+//! [`Grammar::to_ebnf`] write as text in one of the [`Dialect`]s.
+//! [`Grammar::to_dot`] gives an overview of which rule refers to which, as a
+//! Graphviz graph.
+//!
+//! Importers record where each symbol was defined as an [`Expr::Spanned`],
+//! which ends up on the drawn node, see [`SourceSpan`].
+//!
+//! # Example
+//! ```rust
+//! use railroad::grammar::Expr;
+//!
+//! let expr = Expr::sequence(vec![
+//!     Expr::Terminal("SELECT".to_owned()),
+//!     Expr::NonTerminal("columns".to_owned()),
+//! ]);
+//! let svg = railroad::Diagram::new(expr.into_node()).to_string();
+//! assert!(svg.contains("SELECT"));
+//! ```
+
+use std::fmt;
+
+use crate::{
+    Choice, Comment, Diagram, Empty, Link, Node, NonTerminal, Optional, Repeat, Sequence,
+    SimpleEnd, SimpleStart, SourceSpan, Terminal,
+    description::Description,
+    dot,
+    nodes::{containers::UNORDERED_MARKER, wrappers::Bounds},
+};
+
+/// A set of named rules, in the order they were defined.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Grammar {
+    /// The rules of this grammar, in definition order.
+    pub rules: Vec<Rule>,
+}
+
+impl Grammar {
+    /// Look up a rule by name.
+    #[must_use]
+    pub fn rule(&self, name: &str) -> Option<&Rule> {
+        self.rules.iter().find(|r| r.name == name)
+    }
+
+    /// Record `file` as the file of every [`SourceSpan`] in this grammar.
+    ///
+    /// Importers only see the text, so the spans they record name no file.
+    pub fn set_source_file(&mut self, file: Option<String>) {
+        for rule in &mut self.rules {
+            rule.expr
+                .for_each_span(&mut |span| span.file.clone_from(&file));
+        }
+    }
+
+    /// Write all rules as text in the given dialect, one per line.
+    ///
+    /// See [`Rule::to_ebnf`].
+    #[must_use]
+    pub fn to_ebnf(&self, dialect: Dialect) -> String {
+        self.rules
+            .iter()
+            .map(|rule| rule.to_ebnf(dialect) + "\n")
+            .collect()
+    }
+
+    /// Draw the rules and the references between them as a Graphviz DOT graph.
+    ///
+    /// See [`crate::dot`].
+    #[must_use]
+    pub fn to_dot(&self, detail: dot::Detail) -> String {
+        let rules = self
+            .rules
+            .iter()
+            .map(|rule| (rule.name.clone(), rule.expr.clone().into_node().describe()))
+            .collect::<Vec<_>>();
+        dot::write(&rules, detail)
+    }
+}
+
+/// A single named rule and its right-hand side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// The rule's name, i.e. the non-terminal it defines.
+    pub name: String,
+    /// The rule's right-hand side.
+    pub expr: Expr,
+}
+
+impl Rule {
+    /// Construct a rule named `name` with the right-hand side `expr`.
+    #[must_use]
+    pub fn new(name: String, expr: Expr) -> Self {
+        Self { name, expr }
+    }
+
+    /// Write this rule as text in the given dialect, e.g. `name ::= expr` in
+    /// W3C-EBNF; see [`Expr::to_ebnf`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    /// use railroad::grammar::{Dialect, Expr, Rule};
+    ///
+    /// let diagram = Sequence::new(vec![
+    ///     Box::new(SimpleStart) as Box<dyn Node>,
+    ///     Box::new(Terminal::new("(".to_owned())),
+    ///     Box::new(Optional::new(NonTerminal::new("args".to_owned()))),
+    ///     Box::new(Terminal::new(")".to_owned())),
+    ///     Box::new(SimpleEnd),
+    /// ]);
+    /// let rule = Rule::new("call_args".to_owned(), Expr::from_node(&diagram));
+    /// assert_eq!(rule.to_ebnf(Dialect::W3c), r#"call_args ::= "(" args? ")""#);
+    /// assert_eq!(rule.to_ebnf(Dialect::Iso), r#"call_args = "(", [args], ")" ;"#);
+    /// assert_eq!(rule.to_ebnf(Dialect::Abnf), r#"call-args = "(" [args] ")""#);
+    /// ```
+    #[must_use]
+    pub fn to_ebnf(&self, dialect: Dialect) -> String {
+        let name = dialect.name(&self.name);
+        let expr = self.expr.to_ebnf(dialect);
+        match dialect {
+            Dialect::W3c if expr.is_empty() => format!("{name} ::="),
+            Dialect::W3c => format!("{name} ::= {expr}"),
+            Dialect::Iso if expr.is_empty() => format!("{name} = ;"),
+            Dialect::Iso => format!("{name} = {expr} ;"),
+            Dialect::Abnf if expr.is_empty() => format!("{name} = \"\""),
+            Dialect::Abnf => format!("{name} = {expr}"),
+        }
+    }
+
+    /// Build a complete diagram for this rule, framed by start- and end-symbols.
+    #[must_use]
+    pub fn into_diagram(self) -> Diagram<Sequence<Box<dyn Node>>> {
+        Diagram::new(Sequence::new(vec![
+            Box::new(SimpleStart) as Box<dyn Node>,
+            self.expr.into_node(),
+            Box::new(SimpleEnd),
+        ]))
+    }
+}
+
+/// The right-hand side of a rule, as a tree of diagram primitives.
+///
+/// Expressions compare equal if they describe the same diagram, regardless of
+/// the [`Expr::Spanned`] annotations within them.
+#[derive(Debug, Clone)]
+pub enum Expr {
+    /// Matches nothing, see [`crate::Empty`].
+    Empty,
+    /// A literal symbol, see [`crate::Terminal`].
+    Terminal(String),
+    /// A reference to another rule, see [`crate::NonTerminal`].
+    NonTerminal(String),
+    /// An annotation on the path, see [`crate::Comment`].
+    Comment(String),
+    /// All children, in order, see [`crate::Sequence`].
+    Sequence(Vec<Expr>),
+    /// Exactly one of the children, see [`crate::Choice`].
+    Choice(Vec<Expr>),
+    /// The inner expression or nothing, see [`crate::Optional`].
+    Optional(Box<Expr>),
+    /// `inner`, optionally followed by any number of `repeat inner`, see [`crate::Repeat`].
+    Repeat {
+        /// The repeated expression on the main path.
+        inner: Box<Expr>,
+        /// The expression on the return path, usually a separator or `Empty`.
+        repeat: Box<Expr>,
+        /// How often `inner` appears at least.
+        min: usize,
+        /// How often `inner` appears at most, if at all limited.
+        ///
+        /// A `max` below `min` is taken to be `min`, as by
+        /// [`crate::Repeat::set_bounds`]; if it is zero, nothing is repeated at
+        /// all and the expression matches the same as `Empty`.
+        max: Option<usize>,
+    },
+    /// The inner expression as a clickable link to `uri`, see [`crate::Link`].
+    Link {
+        /// The expression the link is attached to.
+        inner: Box<Expr>,
+        /// The URI the link points to.
+        uri: String,
+    },
+    /// The inner expression, annotated with where it was defined.
+    ///
+    /// The span ends up on the node if the inner expression is a `Terminal`,
+    /// `NonTerminal` or `Comment`, see [`crate::Terminal::set_source_span`].
+    Spanned {
+        /// The annotated expression.
+        inner: Box<Expr>,
+        /// Where the expression was defined.
+        span: SourceSpan,
+    },
+}
+
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.unspanned(), other.unspanned()) {
+            (Self::Empty, Self::Empty) => true,
+            (Self::Terminal(a), Self::Terminal(b))
+            | (Self::NonTerminal(a), Self::NonTerminal(b))
+            | (Self::Comment(a), Self::Comment(b)) => a == b,
+            (Self::Sequence(a), Self::Sequence(b)) | (Self::Choice(a), Self::Choice(b)) => a == b,
+            (Self::Optional(a), Self::Optional(b)) => a == b,
+            (
+                Self::Repeat {
+                    inner,
+                    repeat,
+                    min,
+                    max,
+                },
+                Self::Repeat {
+                    inner: other_inner,
+                    repeat: other_repeat,
+                    min: other_min,
+                    max: other_max,
+                },
+            ) => {
+                inner == other_inner
+                    && repeat == other_repeat
+                    && min == other_min
+                    && max == other_max
+            }
+            (
+                Self::Link { inner, uri },
+                Self::Link {
+                    inner: other_inner,
+                    uri: other_uri,
+                },
+            ) => inner == other_inner && uri == other_uri,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Expr {}
+
+impl Expr {
+    /// Build a sequence, collapsing zero children to `Empty` and one child to itself.
+    #[must_use]
+    pub fn sequence(mut children: Vec<Expr>) -> Self {
+        match children.len() {
+            0 => Self::Empty,
+            1 => children.pop().unwrap(),
+            _ => Self::Sequence(children),
+        }
+    }
+
+    /// Build a choice, collapsing zero children to `Empty` and one child to itself.
+    #[must_use]
+    pub fn choice(mut children: Vec<Expr>) -> Self {
+        match children.len() {
+            0 => Self::Empty,
+            1 => children.pop().unwrap(),
+            _ => Self::Choice(children),
+        }
+    }
+
+    /// Build a `Repeat` of `inner` with `repeat` on the return path.
+    #[must_use]
+    pub fn repeat(inner: Expr, repeat: Expr) -> Self {
+        Self::bounded_repeat(inner, repeat, 1, None)
+    }
+
+    /// Build a `Repeat` of `inner` with `repeat` on the return path, which
+    /// `inner` appears at least `min` and at most `max` times in, see
+    /// [`crate::Repeat::set_bounds`].
+    #[must_use]
+    pub fn bounded_repeat(inner: Expr, repeat: Expr, min: usize, max: Option<usize>) -> Self {
+        Self::Repeat {
+            inner: Box::new(inner),
+            repeat: Box::new(repeat),
+            min,
+            max,
+        }
+    }
+
+    /// Annotate this expression with where it was defined.
+    #[must_use]
+    pub fn with_span(self, span: SourceSpan) -> Self {
+        Self::Spanned {
+            inner: Box::new(self),
+            span,
+        }
+    }
+
+    /// This expression without the [`Expr::Spanned`] annotations around it.
+    #[must_use]
+    pub fn unspanned(&self) -> &Self {
+        match self {
+            Self::Spanned { inner, .. } => inner.unspanned(),
+            expr => expr,
+        }
+    }
+
+    /// Call `f` on every span within this expression.
+    fn for_each_span(&mut self, f: &mut impl FnMut(&mut SourceSpan)) {
+        match self {
+            Self::Empty | Self::Terminal(_) | Self::NonTerminal(_) | Self::Comment(_) => {}
+            Self::Sequence(children) | Self::Choice(children) => {
+                for child in children {
+                    child.for_each_span(f);
+                }
+            }
+            Self::Optional(inner) | Self::Link { inner, .. } => inner.for_each_span(f),
+            Self::Repeat { inner, repeat, .. } => {
+                inner.for_each_span(f);
+                repeat.for_each_span(f);
+            }
+            Self::Spanned { inner, span } => {
+                f(span);
+                inner.for_each_span(f);
+            }
+        }
+    }
+
+    /// Recover an expression from the description of a node tree.
+    ///
+    /// This is lossy: primitives without a counterpart in `Expr` are expressed
+    /// through the ones that exist, e.g. an [`crate::OptionalSequence`] as a
+    /// choice of sequences or a [`crate::LabeledBox`] as its content followed
+    /// by the label as a comment. The columns of a [`crate::MultiChoice`] are
+    /// flattened into a single choice and purely visual primitives, such as
+    /// [`crate::Start`], become `Empty`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut list = SeparatedList::new(
+    ///     NonTerminal::new("expr".to_owned()),
+    ///     Terminal::new(",".to_owned()),
+    /// );
+    /// list.set_allow_empty(true);
+    /// let expr = grammar::Expr::from_description(&list.describe());
+    /// assert_eq!(expr.to_string(), "(expr (\",\" expr)*)?");
+    /// ```
+    #[must_use]
+    pub fn from_description(desc: &Description) -> Self {
+        let child = |index: usize| {
+            desc.children
+                .get(index)
+                .map_or(Self::Empty, Self::from_description)
+        };
+        let children = || desc.children.iter().map(Self::from_description);
+        let text = |key: &str| desc.property(key).unwrap_or_default().to_owned();
+        // Malformed bounds are ignored.
+        let bounds = || {
+            let min = desc
+                .property("min")
+                .and_then(|min| min.parse().ok())
+                .unwrap_or(1);
+            let max = desc.property("max").and_then(|max| max.parse().ok());
+            let Bounds { min, max } = Bounds::new(min, max);
+            (min, max)
+        };
+        let spanned = |expr: Self| match desc.source_span() {
+            Some(span) => expr.with_span(span),
+            None => expr,
+        };
+        match desc.kind.as_str() {
+            "empty" | "start" | "end" | "simplestart" | "simpleend" | "debug" => Self::Empty,
+            "terminal" => spanned(Self::Terminal(text("label"))),
+            "nonterminal" => spanned(Self::NonTerminal(text("label"))),
+            "comment" => spanned(Self::Comment(text("text"))),
+            "link" => Self::Link {
+                inner: Box::new(child(0)),
+                uri: text("uri"),
+            },
+            "tooltip" => child(0),
+            "optional" => Self::Optional(Box::new(child(0))),
+            "repeat" => {
+                let (min, max) = bounds();
+                Self::bounded_repeat(child(0), child(1), min, max)
+            }
+            "separatedlist" => {
+                let (min, max) = bounds();
+                let mut list = Self::bounded_repeat(child(0), child(1), min.max(1), max);
+                if desc.property("trailing") == Some("true") {
+                    list = Self::sequence(vec![list, Self::Optional(Box::new(child(1)))]);
+                }
+                if min == 0 {
+                    list = Self::Optional(Box::new(list));
+                }
+                list
+            }
+            "labeledbox" => {
+                let label = match desc.children.get(1) {
+                    Some(label) if label.kind == "comment" => {
+                        label.property("text").unwrap_or_default().to_owned()
+                    }
+                    _ => child(1).to_string(),
+                };
+                Self::sequence(vec![child(0), Self::Comment(label)])
+            }
+            "alternatingsequence" => {
+                // Both elements alternate, starting and ending with either one.
+                let (first, second) = (child(0), child(1));
+                Self::choice(vec![
+                    Self::sequence(vec![
+                        Self::repeat(first.clone(), second.clone()),
+                        Self::Optional(Box::new(second.clone())),
+                    ]),
+                    Self::sequence(vec![
+                        Self::repeat(second, first.clone()),
+                        Self::Optional(Box::new(first)),
+                    ]),
+                ])
+            }
+            "optionalsequence" => {
+                // Any non-empty subset, in order: the first element picked is
+                // followed by any of the ones after it.
+                let children = children().collect::<Vec<_>>();
+                Self::choice(
+                    (0..children.len())
+                        .map(|first| {
+                            Self::sequence(
+                                std::iter::once(children[first].clone())
+                                    .chain(
+                                        children[first + 1..]
+                                            .iter()
+                                            .map(|c| Self::Optional(Box::new(c.clone()))),
+                                    )
+                                    .collect(),
+                            )
+                        })
+                        .collect(),
+                )
+            }
+            "choice" | "horizontalchoice" => Self::choice(children().collect()),
+            "multichoice" => Self::choice(
+                desc.children
+                    .iter()
+                    .flat_map(|column| column.children.iter().map(Self::from_description))
+                    .collect(),
+            ),
+            "unordered" => {
+                let optional = desc
+                    .property("optional")
+                    .map(|indices| {
+                        indices
+                            .split(',')
+                            .filter_map(|index| index.parse().ok())
+                            .collect::<Vec<usize>>()
+                    })
+                    .unwrap_or_default();
+                let marker = desc.property("marker").unwrap_or(UNORDERED_MARKER);
+                Self::sequence(
+                    children()
+                        .enumerate()
+                        .map(|(index, child)| {
+                            if optional.contains(&index) {
+                                Self::Optional(Box::new(child))
+                            } else {
+                                child
+                            }
+                        })
+                        .chain(std::iter::once(Self::Comment(marker.to_owned())))
+                        .collect(),
+                )
+            }
+            // Sequences, stacks, grids and anything unknown: the children in order.
+            _ => Self::sequence(children().filter(|child| !child.is_empty()).collect()),
+        }
+    }
+
+    /// Recover an expression from a node tree, see [`Expr::from_description`].
+    #[must_use]
+    pub fn from_node<N: Node + ?Sized>(node: &N) -> Self {
+        Self::from_description(&node.describe())
+    }
+
+    /// Returns `true` if this is `Expr::Empty`, possibly annotated with a span.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        matches!(self.unspanned(), Self::Empty)
+    }
+
+    /// Convert this expression into a tree of drawable nodes.
+    #[must_use]
+    pub fn into_node(self) -> Box<dyn Node> {
+        match self {
+            Self::Empty => Box::new(Empty),
+            Self::Terminal(label) => Box::new(Terminal::new(label)),
+            Self::NonTerminal(label) => Box::new(NonTerminal::new(label)),
+            Self::Comment(text) => Box::new(Comment::new(text)),
+            Self::Sequence(children) => Box::new(Sequence::new(
+                children.into_iter().map(Self::into_node).collect(),
+            )),
+            Self::Choice(children) => Box::new(Choice::new(
+                children.into_iter().map(Self::into_node).collect(),
+            )),
+            Self::Optional(inner) => Box::new(Optional::new(inner.into_node())),
+            Self::Repeat {
+                inner,
+                repeat,
+                min,
+                max,
+            } => {
+                let mut node = Repeat::new(inner.into_node(), repeat.into_node());
+                node.set_bounds(min, max);
+                Box::new(node)
+            }
+            Self::Link { inner, uri } => Box::new(Link::new(inner.into_node(), uri)),
+            Self::Spanned { inner, span } => match *inner {
+                Self::Terminal(label) => {
+                    let mut node = Terminal::new(label);
+                    node.set_source_span(Some(span));
+                    Box::new(node)
+                }
+                Self::NonTerminal(label) => {
+                    let mut node = NonTerminal::new(label);
+                    node.set_source_span(Some(span));
+                    Box::new(node)
+                }
+                Self::Comment(text) => {
+                    let mut node = Comment::new(text);
+                    node.set_source_span(Some(span));
+                    Box::new(node)
+                }
+                inner => inner.into_node(),
+            },
+        }
+    }
+}
+
+/// A textual notation for grammars, see [`Expr::to_ebnf`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Dialect {
+    /// The EBNF used by W3C specifications, e.g. `list ::= item ("," item)*`.
+    #[default]
+    W3c,
+    /// The EBNF of ISO/IEC 14977, e.g. `list = item, {",", item} ;`.
+    Iso,
+    /// The ABNF of RFC 5234, e.g. `list = item *("," item)`.
+    ///
+    /// Rule names are restricted to letters, digits and hyphens, every other
+    /// character is replaced by a hyphen. Note that quoted strings are
+    /// case-insensitive in ABNF.
+    Abnf,
+}
+
+/// How tightly an expression binds when written out, from loosest to tightest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Choice,
+    Sequence,
+    Repetition,
+    Primary,
+}
+
+/// Some written-out expression and how tightly it binds.
+type Text = (String, Precedence);
+
+impl Dialect {
+    fn terminal(self, label: &str) -> Text {
+        let label = label.replace('\n', " ");
+        match self {
+            Self::Abnf if label.chars().all(|c| matches!(c, ' '..='~') && c != '"') => {
+                (format!("\"{label}\""), Precedence::Primary)
+            }
+            Self::Abnf => (
+                format!(
+                    "%x{}",
+                    label
+                        .chars()
+                        .map(|c| format!("{:X}", u32::from(c)))
+                        .collect::<Vec<_>>()
+                        .join(".")
+                ),
+                Precedence::Primary,
+            ),
+            // Neither dialect can escape quotes; a label with both kinds is
+            // split into literals which can be quoted.
+            Self::W3c | Self::Iso => {
+                let mut literals = Vec::new();
+                let mut rest = label.as_str();
+                while !rest.is_empty() {
+                    let double = rest.find('"').unwrap_or(rest.len());
+                    let single = rest.find('\'').unwrap_or(rest.len());
+                    let (len, quote) = if double < single {
+                        (single, '\'')
+                    } else {
+                        (double, '"')
+                    };
+                    literals.push((
+                        format!("{quote}{}{quote}", &rest[..len]),
+                        Precedence::Primary,
+                    ));
+                    rest = &rest[len..];
+                }
+                self.sequence(literals)
+                    .unwrap_or_else(|| ("\"\"".to_owned(), Precedence::Primary))
+            }
+        }
+    }
+
+    fn name(self, label: &str) -> String {
+        match self {
+            Self::W3c | Self::Iso => label.replace('\n', " "),
+            Self::Abnf => label
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                .collect(),
+        }
+    }
+
+    fn comment(self, text: &str) -> String {
+        let text = text.replace('\n', " ");
+        match self {
+            Self::W3c => format!("/* {} */", text.replace("*/", "* /")),
+            Self::Iso => format!("(* {} *)", text.replace("*)", "* )")),
+            // Comments run until the end of the line; the rule continues on
+            // the next, indented one.
+            Self::Abnf => format!("; {text}\n   "),
+        }
+    }
+
+    fn sequence(self, items: Vec<Text>) -> Option<Text> {
+        if items.len() < 2 {
+            return items.into_iter().next();
+        }
+        let separator = match self {
+            Self::W3c | Self::Abnf => " ",
+            Self::Iso => ", ",
+        };
+        Some((
+            items
+                .into_iter()
+                .map(|item| parenthesize(item, Precedence::Sequence))
+                .collect::<Vec<_>>()
+                .join(separator),
+            Precedence::Sequence,
+        ))
+    }
+
+    fn choice(self, alternatives: Vec<Text>) -> Option<Text> {
+        if alternatives.len() < 2 {
+            return alternatives.into_iter().next();
+        }
+        let separator = match self {
+            Self::W3c | Self::Iso => " | ",
+            Self::Abnf => " / ",
+        };
+        Some((
+            alternatives
+                .into_iter()
+                .map(|(text, _)| text)
+                .collect::<Vec<_>>()
+                .join(separator),
+            Precedence::Choice,
+        ))
+    }
+
+    fn optional(self, expr: Text) -> Text {
+        match self {
+            Self::W3c => postfix(expr, "?"),
+            Self::Iso | Self::Abnf => (format!("[{}]", expr.0), Precedence::Primary),
+        }
+    }
+
+    /// `expr`, at least `min` and at most `max` times.
+    fn repetition(self, expr: Text, min: usize, max: Option<usize>) -> Option<Text> {
+        Some(match (self, min, max) {
+            (_, 0, Some(0)) => return None,
+            (_, 0, Some(1)) => self.optional(expr),
+            (_, 1, Some(1)) => expr,
+            (Self::W3c, 0, None) => postfix(expr, "*"),
+            (Self::W3c, 1, None) => postfix(expr, "+"),
+            (Self::Iso, 0, None) => (format!("{{{}}}", expr.0), Precedence::Primary),
+            (Self::Iso, 1, None) => {
+                let zero_or_more = self.repetition(expr.clone(), 0, None)?;
+                self.sequence(vec![expr, zero_or_more])?
+            }
+            (Self::Iso, min, Some(max)) if min == max => (
+                format!("{min} * {}", parenthesize(expr, Precedence::Primary)),
+                Precedence::Repetition,
+            ),
+            (Self::W3c | Self::Iso, min, max) => {
+                let unbounded = self.repetition(expr, min.min(1), None)?;
+                return self.annotate_bounds(unbounded, min, max);
+            }
+            (Self::Abnf, min, max) => {
+                let repeat = match (min, max) {
+                    (1, None) => "1*".to_owned(),
+                    (0, None) => "*".to_owned(),
+                    (min, None) => format!("{min}*"),
+                    (min, Some(max)) if min == max => min.to_string(),
+                    (0, Some(max)) => format!("*{max}"),
+                    (min, Some(max)) => format!("{min}*{max}"),
+                };
+                (
+                    format!("{repeat}{}", parenthesize(expr, Precedence::Primary)),
+                    Precedence::Repetition,
+                )
+            }
+        })
+    }
+
+    /// `inner`, at least `min` and at most `max` times, separated by `repeat`.
+    fn repeat(
+        self,
+        inner: Option<Text>,
+        repeat: Option<Text>,
+        min: usize,
+        max: Option<usize>,
+    ) -> Option<Text> {
+        let Bounds { min, max } = Bounds::new(min, max);
+        if max == Some(0) {
+            // Nothing is repeated at all.
+            return None;
+        }
+        let (inner, repeat) = match (inner, repeat) {
+            (None, None) => return None,
+            (Some(inner), None) => return self.repetition(inner, min, max),
+            (None, Some(repeat)) => return self.repetition(repeat, 0, None),
+            // A single element is never followed by the separator.
+            (Some(inner), Some(_)) if max == Some(1) => return self.repetition(inner, min, max),
+            (Some(inner), Some(repeat)) => (inner, repeat),
+        };
+        let rest = self.sequence(vec![repeat, inner.clone()])?;
+        let list = match self {
+            // Only ABNF can express arbitrary bounds.
+            Self::Abnf => {
+                // `max` is at least one, as a `max` of zero repeats nothing.
+                let rest = self.repetition(rest, min.max(1) - 1, max.map(|max| max - 1));
+                self.sequence(std::iter::once(inner).chain(rest).collect())?
+            }
+            Self::W3c | Self::Iso => {
+                let rest = self.repetition(rest, 0, None)?;
+                self.sequence(vec![inner, rest])?
+            }
+        };
+        let list = if min == 0 { self.optional(list) } else { list };
+        match self {
+            Self::Abnf => Some(list),
+            Self::W3c | Self::Iso => self.annotate_bounds(list, min, max),
+        }
+    }
+
+    /// Follow a loop by a comment on its bounds, unless its shape expresses them.
+    fn annotate_bounds(self, expr: Text, min: usize, max: Option<usize>) -> Option<Text> {
+        match Bounds::new(min, max).annotation() {
+            None => Some(expr),
+            Some(annotation) => {
+                self.sequence(vec![expr, (self.comment(&annotation), Precedence::Primary)])
+            }
+        }
+    }
+}
+
+impl Expr {
+    /// Write this expression as text in the given dialect.
+    ///
+    /// Parentheses are only added where precedence requires them. Comments,
+    /// including the labels of [`crate::LabeledBox`]es, become comments in the
+    /// dialect's syntax; bounds of a [`crate::Repeat`] which the dialect can't
+    /// express are added as comments, too. An expression matching nothing is
+    /// written as the empty string.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    /// use railroad::grammar::{Dialect, Expr};
+    ///
+    /// let list = Repeat::new(
+    ///     Choice::new(vec![
+    ///         Box::new(NonTerminal::new("column".to_owned())) as Box<dyn Node>,
+    ///         Box::new(Terminal::new("*".to_owned())),
+    ///     ]),
+    ///     Terminal::new(",".to_owned()),
+    /// );
+    /// let expr = Expr::from_node(&list);
+    /// assert_eq!(expr.to_ebnf(Dialect::W3c), r#"(column | "*") ("," (column | "*"))*"#);
+    /// assert_eq!(expr.to_ebnf(Dialect::Iso), r#"(column | "*"), {",", (column | "*")}"#);
+    /// assert_eq!(expr.to_ebnf(Dialect::Abnf), r#"(column / "*") *("," (column / "*"))"#);
+    /// ```
+    #[must_use]
+    pub fn to_ebnf(&self, dialect: Dialect) -> String {
+        self.write(dialect)
+            .map_or_else(String::new, |(text, _)| text.trim_end().to_owned())
+    }
+
+    fn write(&self, dialect: Dialect) -> Option<Text> {
+        match self {
+            Self::Empty => None,
+            Self::Terminal(label) => Some(dialect.terminal(label)),
+            Self::NonTerminal(label) => Some((dialect.name(label), Precedence::Primary)),
+            Self::Comment(text) => Some((dialect.comment(text), Precedence::Primary)),
+            Self::Sequence(children) => dialect.sequence(
+                children
+                    .iter()
+                    .filter_map(|child| child.write(dialect))
+                    .collect(),
+            ),
+            Self::Choice(children) => {
+                let alternatives = children
+                    .iter()
+                    .map(|child| child.write(dialect))
+                    .collect::<Vec<_>>();
+                let optional = alternatives.iter().any(Option::is_none);
+                let choice = dialect.choice(alternatives.into_iter().flatten().collect())?;
+                Some(if optional {
+                    dialect.optional(choice)
+                } else {
+                    choice
+                })
+            }
+            Self::Optional(inner) => inner.write(dialect).map(|inner| dialect.optional(inner)),
+            Self::Repeat {
+                inner,
+                repeat,
+                min,
+                max,
+            } => dialect.repeat(inner.write(dialect), repeat.write(dialect), *min, *max),
+            Self::Link { inner, .. } | Self::Spanned { inner, .. } => inner.write(dialect),
+        }
+    }
+}
+
+/// Writes the expression as W3C-EBNF, e.g. `"(" expr ("," expr)* ")"`; see
+/// [`Expr::to_ebnf`].
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_ebnf(Dialect::W3c))
+    }
+}
+
+/// Wrap `text` in parentheses if it binds less tightly than `precedence`.
+fn parenthesize((text, own): Text, precedence: Precedence) -> String {
+    if own < precedence {
+        format!("({text})")
+    } else {
+        text
+    }
+}
+
+/// Apply a postfix-operator such as `?` to an expression.
+fn postfix(expr: Text, operator: &str) -> Text {
+    (
+        format!("{}{operator}", parenthesize(expr, Precedence::Primary)),
+        Precedence::Repetition,
+    )
+}
@@ -0,0 +1,33 @@
+//! Path-based ids of the nodes in a diagram, see [`crate::Diagram::set_element_ids`].
+//!
+//! The [`crate::Diagram`] stores the id of every node in its geometry; parents
+//! hand it to their children as they draw them, see [`crate::draw_child`] and
+//! [`crate::render_child`].
+
+use crate::{NodeGeometry, description::Description};
+
+/// Assign ids to the node described by `desc`, laid out as `geo`, and its
+/// descendants.
+///
+/// The node itself gets `id`; the id of a child is the id of its parent,
+/// followed by the parent's kind and the child's index, e.g.
+/// `rule.sequence.2`. The alternatives of a `MultiChoice` are indexed by
+/// column and row, e.g. `rule.multichoice.1.0`.
+pub(crate) fn assign(geo: &mut NodeGeometry, id: String, desc: &Description) {
+    if desc.kind == "multichoice" {
+        let alternatives = desc.children.iter().enumerate().flat_map(|(column, c)| {
+            c.children
+                .iter()
+                .enumerate()
+                .map(move |(row, alternative)| (format!("{column}.{row}"), alternative))
+        });
+        for ((index, child), child_geo) in alternatives.zip(&mut geo.children) {
+            assign(child_geo, format!("{id}.multichoice.{index}"), child);
+        }
+    } else {
+        for (index, (child, child_geo)) in desc.children.iter().zip(&mut geo.children).enumerate() {
+            assign(child_geo, format!("{id}.{}.{index}", desc.kind), child);
+        }
+    }
+    geo.id = Some(id);
+}
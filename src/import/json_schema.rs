@@ -0,0 +1,412 @@
+//! Convert a JSON Schema into diagrams of the documents it accepts.
+//!
+//! The root schema becomes a rule named after its `title` (or `root`), followed
+//! by one rule per entry in `$defs` or `definitions`. Within a schema
+//!
+//! * an object becomes `{`, then `"key"` `:` value for each property, then `}`;
+//!   properties not listed in `required` are wrapped in an
+//!   [`Optional`](crate::Optional), and a `,` only separates properties which
+//!   are present, so leading optional properties become a
+//!   [`Choice`](crate::Choice) of which property comes first,
+//! * an array becomes `[`, a [`Repeat`](crate::Repeat) of its `items` with `,` on
+//!   the return path, then `]`; the repetition is optional unless `minItems` is
+//!   at least one, and bounded by `minItems` and `maxItems` if they are given;
+//!   a `maxItems` of zero leaves just `[` `]`,
+//! * `oneOf` and `anyOf` become a [`Choice`](crate::Choice), as does a `type`
+//!   listing more than one type,
+//! * `enum` becomes a `Choice` of [`Terminal`](crate::Terminal)s, `const` a
+//!   single `Terminal`,
+//! * `$ref` becomes a [`NonTerminal`](crate::NonTerminal) named after the last
+//!   segment of the reference, wrapped in a [`Link`](crate::Link) to the
+//!   reference as written,
+//! * `string`, `number` and `integer` become a `NonTerminal` of that name,
+//!   `boolean` a choice of `true` and `false`, and `null` a `Terminal`.
+//!
+//! Keywords that do not affect the shape of a document, such as `pattern` or
+//! `minimum`, are ignored. Property keys, `enum` and `const` values and `$ref`
+//! targets are written out in the schema and carry a [`SourceSpan`] of where
+//! they are; the other symbols are implied by the schema and carry none.
+//!
+//! # Example
+//! ```rust
+//! use railroad::import::json_schema;
+//!
+//! let grammar = json_schema::import(r#"{
+//!     "title": "config",
+//!     "type": "object",
+//!     "properties": {
+//!         "name": { "type": "string" },
+//!         "mode": { "enum": ["fast", "safe"] }
+//!     },
+//!     "required": ["name"]
+//! }"#).unwrap();
+//!
+//! assert_eq!(grammar.rules[0].name, "config");
+//! let svg = grammar.rules[0].clone().into_diagram().to_string();
+//! assert!(svg.contains("&quot;name&quot;"));
+//! ```
+
+use super::Error;
+use crate::{
+    SourceSpan,
+    grammar::{Expr, Grammar, Rule},
+    json::{self, Spans, Value},
+};
+
+/// Import the JSON Schema in `src`.
+///
+/// # Errors
+/// If `src` is not valid JSON.
+pub fn import(src: &str) -> Result<Grammar, Error> {
+    let (schema, ranges) =
+        json::parse_with_spans(src).map_err(|e| Error::at(src, e.offset, e.message))?;
+    let source = Source {
+        src,
+        spans: Spans::new(&schema, ranges),
+    };
+
+    let name = schema
+        .get("title")
+        .and_then(Value::as_str)
+        .unwrap_or("root")
+        .to_owned();
+    let mut rules = vec![Rule::new(name, convert(&schema, &source))];
+    for key in ["$defs", "definitions"] {
+        if let Some(defs) = schema.get(key).and_then(Value::as_object) {
+            rules.extend(
+                defs.iter()
+                    .map(|(name, def)| Rule::new(name.clone(), convert(def, &source))),
+            );
+        }
+    }
+    Ok(Grammar { rules })
+}
+
+fn terminal(s: impl Into<String>) -> Expr {
+    Expr::Terminal(s.into())
+}
+
+/// The text of a schema, to locate the symbols written out in it.
+#[derive(Default)]
+struct Source<'a> {
+    src: &'a str,
+    spans: Spans<'a>,
+}
+
+impl Source<'_> {
+    /// `expr`, annotated with the span of `bytes` if it is known.
+    fn spanned(&self, expr: Expr, bytes: Option<std::ops::Range<usize>>) -> Expr {
+        match bytes {
+            Some(bytes) => expr.with_span(SourceSpan::locate(self.src, bytes)),
+            None => expr,
+        }
+    }
+}
+
+/// Convert a single (sub-)schema.
+fn convert<'a>(schema: &'a Value, source: &Source<'a>) -> Expr {
+    let Value::Object(_) = schema else {
+        // `true` accepts anything, `false` nothing at all.
+        return match schema {
+            Value::Bool(false) => Expr::Empty,
+            _ => Expr::NonTerminal("value".to_owned()),
+        };
+    };
+
+    if let Some(target) = schema.get("$ref")
+        && let Some(reference) = target.as_str()
+    {
+        let name = Expr::NonTerminal(ref_name(reference));
+        return Expr::Link {
+            inner: Box::new(source.spanned(name, source.spans.value(target))),
+            uri: reference.to_owned(),
+        };
+    }
+    // Literal values are drawn as JSON, much like they are written in the schema.
+    let literal =
+        |value: &'a Value| source.spanned(terminal(value.to_string()), source.spans.value(value));
+    if let Some(value) = schema.get("const") {
+        return literal(value);
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return Expr::choice(values.iter().map(literal).collect());
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(schemas) = schema.get(key).and_then(Value::as_array) {
+            return Expr::choice(schemas.iter().map(|s| convert(s, source)).collect());
+        }
+    }
+
+    match schema.get("type") {
+        Some(Value::String(ty)) => convert_type(schema, ty, source),
+        Some(Value::Array(types)) => Expr::choice(
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|ty| convert_type(schema, ty, source))
+                .collect(),
+        ),
+        _ if schema.get("properties").is_some() => convert_type(schema, "object", source),
+        _ if schema.get("items").is_some() => convert_type(schema, "array", source),
+        _ => Expr::NonTerminal("value".to_owned()),
+    }
+}
+
+fn convert_type<'a>(schema: &'a Value, ty: &str, source: &Source<'a>) -> Expr {
+    match ty {
+        "object" => convert_object(schema, source),
+        "array" => convert_array(schema, source),
+        "boolean" => Expr::Choice(vec![terminal("true"), terminal("false")]),
+        "null" => terminal("null"),
+        ty => Expr::NonTerminal(ty.to_owned()),
+    }
+}
+
+fn convert_object<'a>(schema: &'a Value, source: &Source<'a>) -> Expr {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or_default();
+
+    let mut items = vec![terminal("{")];
+    if properties.is_empty() {
+        // Without declared properties, any key may appear, with values
+        // described by `additionalProperties`.
+        let value = match schema.get("additionalProperties") {
+            Some(Value::Bool(false)) => None,
+            Some(additional) => Some(convert(additional, source)),
+            None => Some(Expr::NonTerminal("value".to_owned())),
+        };
+        if let Some(value) = value {
+            let member = Expr::Sequence(vec![
+                Expr::NonTerminal("string".to_owned()),
+                terminal(":"),
+                value,
+            ]);
+            items.push(Expr::Optional(Box::new(Expr::repeat(
+                member,
+                terminal(","),
+            ))));
+        }
+    }
+    let members = properties
+        .iter()
+        .map(|(key, property)| {
+            let key_terminal = terminal(Value::String(key.clone()).to_string());
+            let member = vec![
+                source.spanned(key_terminal, source.spans.key(key)),
+                terminal(":"),
+                convert(property, source),
+            ];
+            (member, required.contains(&key.as_str()))
+        })
+        .collect::<Vec<_>>();
+    items.extend(convert_members(&members));
+    items.push(terminal("}"));
+    Expr::Sequence(items)
+}
+
+/// The given members and whether each is required, separated by `,`.
+///
+/// Only members which are present are separated: once a member is present,
+/// every following one starts with a `,`. Up to the first present member, each
+/// optional member is a choice between being that first member or being left
+/// out.
+fn convert_members(members: &[(Vec<Expr>, bool)]) -> Vec<Expr> {
+    let Some(((first, required), rest)) = members.split_first() else {
+        return Vec::new();
+    };
+    let mut present = vec![Expr::Sequence(first.clone())];
+    present.extend(rest.iter().map(|(member, required)| {
+        let mut separated = vec![terminal(",")];
+        separated.extend(member.iter().cloned());
+        let member = Expr::Sequence(separated);
+        if *required {
+            member
+        } else {
+            Expr::Optional(Box::new(member))
+        }
+    }));
+    if *required {
+        return present;
+    }
+    let present = Expr::sequence(present);
+    match Expr::sequence(convert_members(rest)) {
+        Expr::Empty => vec![Expr::Optional(Box::new(present))],
+        Expr::Optional(absent) => vec![Expr::Optional(Box::new(Expr::Choice(vec![
+            present, *absent,
+        ])))],
+        absent => vec![Expr::Choice(vec![present, absent])],
+    }
+}
+
+fn convert_array<'a>(schema: &'a Value, source: &Source<'a>) -> Expr {
+    let items = schema.get("items").map_or_else(
+        || Expr::NonTerminal("value".to_owned()),
+        |items| convert(items, source),
+    );
+    let count = |key| {
+        schema
+            .get(key)
+            .and_then(Value::as_f64)
+            .filter(|n| n.is_finite() && *n >= 0.0)
+            // The cast saturates and drops any fraction.
+            .map(|n| n as usize)
+    };
+    if count("maxItems") == Some(0) {
+        return Expr::Sequence(vec![terminal("["), terminal("]")]);
+    }
+    let min_items = count("minItems").unwrap_or(0);
+    let max_items = count("maxItems").filter(|&max| max >= min_items);
+    let repeat = match (min_items, max_items) {
+        (0 | 1, None) => Expr::repeat(items, terminal(",")),
+        (min, max) => Expr::bounded_repeat(items, terminal(","), min, max),
+    };
+    Expr::Sequence(vec![
+        terminal("["),
+        if min_items == 0 && max_items.is_none() {
+            Expr::Optional(Box::new(repeat))
+        } else {
+            repeat
+        },
+        terminal("]"),
+    ])
+}
+
+/// The name a `$ref` refers to: the last segment of its JSON pointer, or the
+/// reference itself if it has none.
+fn ref_name(reference: &str) -> String {
+    reference
+        .rsplit(['/', '#'])
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(reference)
+        .replace("~1", "/")
+        .replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nt(s: &str) -> Expr {
+        Expr::NonTerminal(s.to_owned())
+    }
+
+    fn unspanned(schema: &Value) -> Expr {
+        convert(schema, &Source::default())
+    }
+
+    #[test]
+    fn ref_names_use_the_last_pointer_segment() {
+        assert_eq!(ref_name("#/$defs/item"), "item");
+        assert_eq!(ref_name("other.json#/definitions/a~1b"), "a/b");
+        assert_eq!(ref_name("other.json"), "other.json");
+        assert_eq!(ref_name("#"), "#");
+    }
+
+    #[test]
+    fn arrays_repeat_with_comma_separator() {
+        let schema =
+            json::parse(r#"{"type": "array", "items": {"type": "integer"}, "minItems": 1}"#)
+                .unwrap();
+        assert_eq!(
+            unspanned(&schema),
+            Expr::Sequence(vec![
+                terminal("["),
+                Expr::repeat(nt("integer"), terminal(",")),
+                terminal("]"),
+            ])
+        );
+    }
+
+    #[test]
+    fn arrays_respect_item_counts() {
+        let schema = json::parse(r#"{"type": "array", "minItems": 2, "maxItems": 4}"#).unwrap();
+        assert_eq!(
+            unspanned(&schema),
+            Expr::Sequence(vec![
+                terminal("["),
+                Expr::bounded_repeat(nt("value"), terminal(","), 2, Some(4)),
+                terminal("]"),
+            ])
+        );
+        let schema = json::parse(r#"{"type": "array", "maxItems": 3}"#).unwrap();
+        assert!(matches!(
+            &unspanned(&schema),
+            Expr::Sequence(items) if items[1] == Expr::bounded_repeat(nt("value"), terminal(","), 0, Some(3))
+        ));
+    }
+
+    #[test]
+    fn separators_follow_present_members() {
+        let member = |key: &str, ty: &str| {
+            Expr::Sequence(vec![terminal(format!("\"{key}\"")), terminal(":"), nt(ty)])
+        };
+        let following = |key: &str, ty: &str| {
+            Expr::Sequence(vec![
+                terminal(","),
+                terminal(format!("\"{key}\"")),
+                terminal(":"),
+                nt(ty),
+            ])
+        };
+
+        let schema = json::parse(
+            r#"{"properties": {"a": {"type": "string"}, "b": {"type": "number"}}, "required": ["b"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            unspanned(&schema),
+            Expr::Sequence(vec![
+                terminal("{"),
+                Expr::Choice(vec![
+                    Expr::Sequence(vec![member("a", "string"), following("b", "number")]),
+                    member("b", "number"),
+                ]),
+                terminal("}"),
+            ])
+        );
+
+        let schema =
+            json::parse(r#"{"properties": {"a": {"type": "string"}, "b": {"type": "number"}}}"#)
+                .unwrap();
+        assert_eq!(
+            unspanned(&schema),
+            Expr::Sequence(vec![
+                terminal("{"),
+                Expr::Optional(Box::new(Expr::Choice(vec![
+                    Expr::Sequence(vec![
+                        member("a", "string"),
+                        Expr::Optional(Box::new(following("b", "number"))),
+                    ]),
+                    member("b", "number"),
+                ]))),
+                terminal("}"),
+            ])
+        );
+    }
+
+    #[test]
+    fn arrays_without_items() {
+        let schema = json::parse(r#"{"type": "array", "maxItems": 0}"#).unwrap();
+        assert_eq!(
+            unspanned(&schema),
+            Expr::Sequence(vec![terminal("["), terminal("]")])
+        );
+    }
+
+    #[test]
+    fn type_lists_and_boolean_schemas() {
+        let schema = json::parse(r#"{"type": ["string", "null"]}"#).unwrap();
+        assert_eq!(
+            unspanned(&schema),
+            Expr::Choice(vec![nt("string"), terminal("null")])
+        );
+        assert_eq!(unspanned(&Value::Bool(true)), nt("value"));
+    }
+}
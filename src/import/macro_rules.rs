@@ -0,0 +1,665 @@
+//! Convert the matchers of Rust `macro_rules!` definitions.
+//!
+//! Within a matcher, a metavariable `$x:frag` becomes a
+//! [`NonTerminal`](crate::NonTerminal) labelled with the fragment specifier, a
+//! repetition `$( ... ) sep op` becomes a [`Repeat`](crate::Repeat) with the
+//! separator (if any) on the return path, wrapped in an
+//! [`Optional`](crate::Optional) for `*` and `?`. All other tokens, including
+//! the delimiters of nested groups, become [`Terminal`](crate::Terminal)s. The
+//! outermost delimiters of a matcher are dropped, as any delimiter may be used
+//! when invoking the macro. Every terminal and non-terminal is annotated with
+//! its [`SourceSpan`], see [`Expr::Spanned`].
+//!
+//! # Example
+//! ```rust
+//! use railroad::import::macro_rules;
+//!
+//! let grammar = macro_rules::import(r#"
+//! macro_rules! vec {
+//!     () => { Vec::new() };
+//!     ($elem:expr; $n:expr) => { std::vec::from_elem($elem, $n) };
+//!     ($($x:expr),+ $(,)?) => { <[_]>::into_vec(Box::new([$($x),+])) };
+//! }
+//! "#).unwrap();
+//!
+//! assert_eq!(grammar.rules[0].name, "vec");
+//! let svg = grammar.rules[0].clone().into_diagram().to_string();
+//! assert!(svg.contains("class=\"choice\""));
+//! ```
+
+use std::ops::Range;
+
+use super::Error;
+use crate::{
+    SourceSpan,
+    grammar::{Expr, Grammar, Rule},
+};
+
+/// The fragment specifiers accepted after `$name:`.
+const FRAGMENTS: &[&str] = &[
+    "block",
+    "expr",
+    "expr_2021",
+    "ident",
+    "item",
+    "lifetime",
+    "literal",
+    "meta",
+    "pat",
+    "pat_param",
+    "path",
+    "stmt",
+    "tt",
+    "ty",
+    "vis",
+];
+
+/// Punctuation consisting of more than one character, longest first.
+const MULTI_CHAR_PUNCT: &[&str] = &[
+    "<<=", ">>=", "...", "..=", "::", "->", "=>", "<-", "==", "!=", "<=", ">=", "&&", "||", "+=",
+    "-=", "*=", "/=", "%=", "^=", "&=", "|=", "<<", ">>", "..",
+];
+
+/// Import all `macro_rules!` definitions in `src`, one rule per macro.
+///
+/// Each rule is a `Choice` of the macro's arms; a macro with a single arm
+/// is just that arm's matcher. Attributes such as `#[macro_export]` are
+/// skipped, transcribers are ignored.
+///
+/// # Errors
+/// If `src` is not a sequence of `macro_rules!` definitions or if a matcher is
+/// malformed.
+pub fn import(src: &str) -> Result<Grammar, Error> {
+    let trees = parse(src)?;
+    let mut rules = Vec::new();
+    let mut iter = trees.iter().peekable();
+    while let Some(tree) = iter.next() {
+        match tree {
+            Tree::Token(t) if t.text == "#" => {
+                if let Some(Tree::Token(t)) = iter.peek()
+                    && t.text == "!"
+                {
+                    iter.next();
+                }
+                match iter.next() {
+                    Some(Tree::Group(g)) if g.delimiter == '[' => {}
+                    _ => return Err(Error::at(src, t.offset, "expected an attribute")),
+                }
+            }
+            Tree::Token(t) if t.text == ";" => {}
+            Tree::Token(t) if t.text == "macro_rules" => {
+                let name = match (iter.next(), iter.next()) {
+                    (Some(Tree::Token(bang)), Some(Tree::Token(name)))
+                        if bang.text == "!" && name.kind == Kind::Ident =>
+                    {
+                        name.text.clone()
+                    }
+                    _ => {
+                        return Err(Error::at(
+                            src,
+                            t.offset,
+                            "expected `macro_rules!` followed by a name",
+                        ));
+                    }
+                };
+                let Some(Tree::Group(body)) = iter.next() else {
+                    return Err(Error::at(src, t.offset, "expected the body of the macro"));
+                };
+                rules.push(Rule::new(name, arms(src, body)?));
+            }
+            tree => {
+                return Err(Error::at(
+                    src,
+                    tree.offset(),
+                    "expected a `macro_rules!` definition",
+                ));
+            }
+        }
+    }
+    Ok(Grammar { rules })
+}
+
+/// Convert the token text of a single matcher, such as `($a:expr, $b:expr)`.
+///
+/// If the text is a single delimited group, its delimiters are dropped.
+///
+/// # Errors
+/// If the matcher is malformed.
+pub fn matcher(src: &str) -> Result<Expr, Error> {
+    let trees = parse(src)?;
+    match trees.as_slice() {
+        [Tree::Group(group)] => convert(src, &group.trees),
+        trees => convert(src, trees),
+    }
+}
+
+fn arms(src: &str, body: &Group) -> Result<Expr, Error> {
+    let mut alternatives = Vec::new();
+    let mut iter = body.trees.iter();
+    while let Some(tree) = iter.next() {
+        match tree {
+            Tree::Token(t) if t.text == ";" => continue,
+            Tree::Group(m) => {
+                match (iter.next(), iter.next()) {
+                    (Some(Tree::Token(arrow)), Some(Tree::Group(_))) if arrow.text == "=>" => {}
+                    _ => {
+                        return Err(Error::at(
+                            src,
+                            m.offset,
+                            "expected `=>` and a transcriber after the matcher",
+                        ));
+                    }
+                }
+                alternatives.push(convert(src, &m.trees)?);
+            }
+            tree => return Err(Error::at(src, tree.offset(), "expected a matcher")),
+        }
+    }
+    if alternatives.is_empty() {
+        return Err(Error::at(src, body.offset, "macro has no rules"));
+    }
+    Ok(Expr::choice(alternatives))
+}
+
+/// Convert a list of token trees within a matcher.
+fn convert(src: &str, trees: &[Tree]) -> Result<Expr, Error> {
+    let mut items = Vec::new();
+    let mut iter = trees.iter().peekable();
+    while let Some(tree) = iter.next() {
+        match tree {
+            Tree::Token(dollar) if dollar.text == "$" => match iter.next() {
+                Some(Tree::Group(group)) if group.delimiter == '(' => {
+                    items.push(repetition(src, group, &mut iter)?);
+                }
+                Some(Tree::Token(name)) if name.kind == Kind::Ident => {
+                    let is_colon = |t: &&Tree| matches!(t, Tree::Token(t) if t.text == ":");
+                    if iter.next_if(is_colon).is_none() {
+                        if name.text == "crate" {
+                            let bytes = dollar.offset..name.end();
+                            items.push(spanned(src, Expr::Terminal("$crate".to_owned()), bytes));
+                            continue;
+                        }
+                        return Err(Error::at(
+                            src,
+                            name.offset,
+                            format!("expected a fragment specifier after `${}`", name.text),
+                        ));
+                    }
+                    match iter.next() {
+                        Some(Tree::Token(frag)) if FRAGMENTS.contains(&frag.text.as_str()) => {
+                            let bytes = dollar.offset..frag.end();
+                            items.push(spanned(src, Expr::NonTerminal(frag.text.clone()), bytes));
+                        }
+                        Some(Tree::Token(frag)) if frag.kind == Kind::Ident => {
+                            return Err(Error::at(
+                                src,
+                                frag.offset,
+                                format!("unknown fragment specifier `{}`", frag.text),
+                            ));
+                        }
+                        _ => {
+                            return Err(Error::at(
+                                src,
+                                name.offset,
+                                format!("expected a fragment specifier after `${}:`", name.text),
+                            ));
+                        }
+                    }
+                }
+                _ => {
+                    return Err(Error::at(
+                        src,
+                        dollar.offset,
+                        "expected a metavariable or a repetition after `$`",
+                    ));
+                }
+            },
+            Tree::Token(t) => items.push(spanned(src, Expr::Terminal(t.text.clone()), t.bytes())),
+            Tree::Group(group) => {
+                let open = group.offset..group.offset + 1;
+                items.push(spanned(
+                    src,
+                    Expr::Terminal(group.delimiter.to_string()),
+                    open,
+                ));
+                match convert(src, &group.trees)? {
+                    Expr::Sequence(inner) => items.extend(inner),
+                    Expr::Empty => {}
+                    inner => items.push(inner),
+                }
+                let close = group.close..group.close + 1;
+                let delimiter = Expr::Terminal(closing(group.delimiter).to_string());
+                items.push(spanned(src, delimiter, close));
+            }
+        }
+    }
+    Ok(Expr::sequence(items))
+}
+
+/// Convert `$( ... ) sep op`, the `$( ... )` having already been consumed.
+fn repetition<'a>(
+    src: &str,
+    group: &Group,
+    iter: &mut impl Iterator<Item = &'a Tree>,
+) -> Result<Expr, Error> {
+    let inner = convert(src, &group.trees)?;
+    let token = |tree: Option<&'a Tree>| match tree {
+        Some(Tree::Token(t)) => Some(t),
+        _ => None,
+    };
+    let is_op = |t: &&Token| matches!(t.text.as_str(), "*" | "+" | "?");
+    let mut op = token(iter.next());
+    let mut separator = None;
+    if let Some(sep) = op.filter(|t| !is_op(t)) {
+        separator = Some(sep);
+        op = token(iter.next());
+    }
+    let op = op.filter(is_op).ok_or_else(|| {
+        Error::at(
+            src,
+            group.offset,
+            "expected a repetition operator after `$(...)`",
+        )
+    })?;
+    match (op.text.as_str(), separator) {
+        ("?", Some(sep)) => Err(Error::at(
+            src,
+            sep.offset,
+            "the `?` repetition operator does not take a separator",
+        )),
+        ("?", None) => Ok(Expr::Optional(Box::new(inner))),
+        (op, separator) => {
+            let repeat = Expr::repeat(
+                inner,
+                separator.map_or(Expr::Empty, |sep| {
+                    spanned(src, Expr::Terminal(sep.text.clone()), sep.bytes())
+                }),
+            );
+            Ok(if op == "*" {
+                Expr::Optional(Box::new(repeat))
+            } else {
+                repeat
+            })
+        }
+    }
+}
+
+/// Annotate `expr` with the location of `bytes` in `src`.
+fn spanned(src: &str, expr: Expr, bytes: Range<usize>) -> Expr {
+    expr.with_span(SourceSpan::locate(src, bytes))
+}
+
+fn closing(delimiter: char) -> char {
+    match delimiter {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Ident,
+    Lifetime,
+    Literal,
+    Punct,
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: Kind,
+    text: String,
+    offset: usize,
+}
+
+impl Token {
+    fn bytes(&self) -> Range<usize> {
+        self.offset..self.end()
+    }
+
+    fn end(&self) -> usize {
+        self.offset + self.text.len()
+    }
+}
+
+#[derive(Debug)]
+struct Group {
+    delimiter: char,
+    trees: Vec<Tree>,
+    offset: usize,
+    /// The offset of the closing delimiter.
+    close: usize,
+}
+
+#[derive(Debug)]
+enum Tree {
+    Token(Token),
+    Group(Group),
+}
+
+impl Tree {
+    fn offset(&self) -> usize {
+        match self {
+            Self::Token(t) => t.offset,
+            Self::Group(g) => g.offset,
+        }
+    }
+}
+
+/// Split `src` into token trees, matching up delimiters.
+fn parse(src: &str) -> Result<Vec<Tree>, Error> {
+    let mut lexer = Lexer { src, pos: 0 };
+    let mut stack: Vec<Group> = Vec::new();
+    let mut trees = Vec::new();
+    while let Some((c, offset)) = lexer.next_delimiter_or_token(&mut trees)? {
+        match c {
+            '(' | '[' | '{' => {
+                stack.push(Group {
+                    delimiter: c,
+                    trees: std::mem::take(&mut trees),
+                    offset,
+                    close: offset,
+                });
+            }
+            _ => match stack.pop() {
+                Some(mut group) if closing(group.delimiter) == c => {
+                    std::mem::swap(&mut group.trees, &mut trees);
+                    group.close = offset;
+                    trees.push(Tree::Group(group));
+                }
+                _ => return Err(Error::at(src, offset, format!("unexpected `{c}`"))),
+            },
+        }
+    }
+    match stack.pop() {
+        Some(group) => Err(Error::at(
+            src,
+            group.offset,
+            format!("unclosed `{}`", group.delimiter),
+        )),
+        None => Ok(trees),
+    }
+}
+
+struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Lexer<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, offset: usize, message: &str) -> Error {
+        Error::at(self.src, offset, message)
+    }
+
+    /// Push plain tokens onto `trees` until a delimiter is found, which is
+    /// returned along with its offset.
+    fn next_delimiter_or_token(
+        &mut self,
+        trees: &mut Vec<Tree>,
+    ) -> Result<Option<(char, usize)>, Error> {
+        loop {
+            self.skip_whitespace_and_comments()?;
+            let offset = self.pos;
+            let Some(c) = self.peek() else {
+                return Ok(None);
+            };
+            let kind = match c {
+                '(' | '[' | '{' | ')' | ']' | '}' => {
+                    self.bump();
+                    return Ok(Some((c, offset)));
+                }
+                '"' => {
+                    self.string()?;
+                    Kind::Literal
+                }
+                '\'' => self.quote()?,
+                c if c.is_ascii_digit() => {
+                    self.number();
+                    Kind::Literal
+                }
+                c if c.is_alphabetic() || c == '_' => self.ident_or_prefixed_literal()?,
+                _ => {
+                    let len = MULTI_CHAR_PUNCT
+                        .iter()
+                        .find(|p| self.rest().starts_with(*p))
+                        .map_or(c.len_utf8(), |p| p.len());
+                    self.pos += len;
+                    Kind::Punct
+                }
+            };
+            trees.push(Tree::Token(Token {
+                kind,
+                text: self.src[offset..self.pos].to_owned(),
+                offset,
+            }));
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), Error> {
+        loop {
+            match (self.peek(), self.peek_nth(1)) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.bump();
+                }
+                (Some('/'), Some('/')) => {
+                    self.pos = self
+                        .rest()
+                        .find('\n')
+                        .map_or(self.src.len(), |end| self.pos + end);
+                }
+                (Some('/'), Some('*')) => {
+                    let start = self.pos;
+                    let mut depth = 0usize;
+                    loop {
+                        if self.rest().starts_with("/*") {
+                            depth += 1;
+                            self.pos += 2;
+                        } else if self.rest().starts_with("*/") {
+                            depth -= 1;
+                            self.pos += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        } else if self.bump().is_none() {
+                            return Err(self.error(start, "unterminated comment"));
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn ident(&mut self) {
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.bump();
+        }
+    }
+
+    fn number(&mut self) {
+        while let Some(c) = self.peek() {
+            let is_fraction = c == '.' && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit());
+            if !(c.is_alphanumeric() || c == '_' || is_fraction) {
+                break;
+            }
+            self.bump();
+        }
+    }
+
+    /// Consume a string literal, starting at the opening quote.
+    fn string(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        self.bump();
+        while let Some(c) = self.bump() {
+            match c {
+                '\\' => {
+                    self.bump();
+                }
+                '"' => return Ok(()),
+                _ => {}
+            }
+        }
+        Err(self.error(start, "unterminated string literal"))
+    }
+
+    /// Consume a raw string literal, starting at the first `#` or quote.
+    fn raw_string(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        let hashes = self.rest().chars().take_while(|&c| c == '#').count();
+        self.pos += hashes;
+        if self.bump() != Some('"') {
+            return Err(self.error(start, "expected `\"` in raw string literal"));
+        }
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        match self.rest().find(&terminator) {
+            Some(end) => {
+                self.pos += end + terminator.len();
+                Ok(())
+            }
+            None => Err(self.error(start, "unterminated raw string literal")),
+        }
+    }
+
+    /// Consume a character literal or a lifetime, starting at the quote.
+    fn quote(&mut self) -> Result<Kind, Error> {
+        let start = self.pos;
+        self.bump();
+        let is_char = self.peek() == Some('\\') || self.peek_nth(1) == Some('\'');
+        if !is_char {
+            self.ident();
+            return Ok(Kind::Lifetime);
+        }
+        while let Some(c) = self.bump() {
+            match c {
+                '\\' => {
+                    self.bump();
+                }
+                '\'' => return Ok(Kind::Literal),
+                '\n' => break,
+                _ => {}
+            }
+        }
+        Err(self.error(start, "unterminated character literal"))
+    }
+
+    /// Consume an identifier or a literal with a prefix such as `b"..."`,
+    /// `r#"..."#` or `c"..."`; `r#ident` is a raw identifier.
+    fn ident_or_prefixed_literal(&mut self) -> Result<Kind, Error> {
+        let start = self.pos;
+        self.ident();
+        let prefix = &self.src[start..self.pos];
+        match (prefix, self.peek(), self.peek_nth(1)) {
+            ("b" | "c", Some('"'), _) => self.string().map(|()| Kind::Literal),
+            ("b", Some('\''), _) => self.quote().map(|_| Kind::Literal),
+            ("r" | "br" | "cr", Some('"' | '#'), next)
+                if self.peek() == Some('"') || matches!(next, Some('"' | '#')) =>
+            {
+                self.raw_string().map(|()| Kind::Literal)
+            }
+            ("r", Some('#'), _) => {
+                self.bump();
+                self.ident();
+                Ok(Kind::Ident)
+            }
+            _ => Ok(Kind::Ident),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(s: &str) -> Expr {
+        Expr::Terminal(s.to_owned())
+    }
+
+    fn nt(s: &str) -> Expr {
+        Expr::NonTerminal(s.to_owned())
+    }
+
+    #[test]
+    fn repetitions_and_separators() {
+        assert_eq!(
+            matcher("($($k:expr => $v:expr),* $(,)?)").unwrap(),
+            Expr::Sequence(vec![
+                Expr::Optional(Box::new(Expr::repeat(
+                    Expr::Sequence(vec![nt("expr"), t("=>"), nt("expr")]),
+                    t(",")
+                ))),
+                Expr::Optional(Box::new(t(","))),
+            ])
+        );
+        assert_eq!(
+            matcher("$($i:ident)+").unwrap(),
+            Expr::repeat(nt("ident"), Expr::Empty)
+        );
+    }
+
+    #[test]
+    fn nested_groups_keep_their_delimiters() {
+        assert_eq!(
+            matcher("[fn $name:ident() -> $ret:ty { $($body:tt)* }]").unwrap(),
+            Expr::Sequence(vec![
+                t("fn"),
+                nt("ident"),
+                t("("),
+                t(")"),
+                t("->"),
+                nt("ty"),
+                t("{"),
+                Expr::Optional(Box::new(Expr::repeat(nt("tt"), Expr::Empty))),
+                t("}"),
+            ])
+        );
+    }
+
+    #[test]
+    fn literals_lifetimes_and_crate() {
+        assert_eq!(
+            matcher(r##"'a 'b' b"x" r#"y"# r#type $crate::x /* ) */ 1.5"##).unwrap(),
+            Expr::Sequence(vec![
+                t("'a"),
+                t("'b'"),
+                t("b\"x\""),
+                t("r#\"y\"#"),
+                t("r#type"),
+                t("$crate"),
+                t("::"),
+                t("x"),
+                t("1.5"),
+            ])
+        );
+    }
+
+    #[test]
+    fn malformed_matchers_are_rejected() {
+        let err = matcher("($x)").unwrap_err();
+        assert_eq!((err.column, err.message.contains("fragment")), (3, true));
+        let err = matcher("$x:expression").unwrap_err();
+        assert_eq!(err.message, "unknown fragment specifier `expression`");
+        let err = matcher("$($x:expr),?").unwrap_err();
+        assert_eq!(err.column, 11);
+        assert!(matcher("$($x:expr)").is_err());
+        assert!(matcher("(]").is_err());
+        assert!(matcher("(").is_err());
+    }
+}
@@ -0,0 +1,62 @@
+//! Importers that turn textual grammar definitions into [`crate::grammar::Grammar`]s.
+//!
+//! Each importer lives in its own submodule and reports failures through the
+//! shared [`Error`] type, which points at the offending position in the input.
+
+use std::fmt;
+
+pub mod json_schema;
+pub mod macro_rules;
+pub mod svg;
+pub mod yacc;
+
+/// An error encountered while importing a grammar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// The 1-based line in the input where the error was detected.
+    pub line: usize,
+    /// The 1-based column (in characters) in the input where the error was detected.
+    pub column: usize,
+    /// A human-readable description of what went wrong.
+    pub message: String,
+}
+
+impl Error {
+    /// Construct an error pointing at byte-offset `offset` in `src`.
+    pub(crate) fn at(src: &str, offset: usize, message: impl Into<String>) -> Self {
+        let (line, column) = position(src, offset);
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Compute the 1-based line and column of byte-offset `offset` in `src`.
+pub(crate) fn position(src: &str, offset: usize) -> (usize, usize) {
+    let before = &src[..offset.min(src.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn position_counts_lines_and_characters() {
+        let src = "ab\ncdé\nf";
+        assert_eq!(super::position(src, 0), (1, 1));
+        assert_eq!(super::position(src, 3), (2, 1));
+        assert_eq!(super::position(src, 7), (2, 4));
+        assert_eq!(super::position(src, src.len()), (3, 2));
+    }
+}
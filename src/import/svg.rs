@@ -0,0 +1,146 @@
+//! Recover the node tree from a diagram rendered with an embedded description.
+//!
+//! See [`Diagram::set_embed_description`](crate::Diagram::set_embed_description)
+//! for how to produce such an SVG, and [`Description::into_node`] for how to
+//! turn the result back into a drawable node tree.
+
+use super::Error;
+use crate::description::Description;
+
+/// Read the [`Description`] embedded in the SVG `src`.
+///
+/// The description is the first `<metadata>`-element holding a JSON object
+/// which can be read as a [`Description`]; other metadata is skipped.
+///
+/// # Errors
+/// If `src` carries no embedded description or if the description is malformed.
+pub fn import(src: &str) -> Result<Description, Error> {
+    let mut first_error = None;
+    let mut search_from = 0;
+    while let Some(found) = src[search_from..].find("<metadata") {
+        let start = search_from + found;
+        search_from = start + "<metadata".len();
+        // Only the exact tag name, not others which start with it.
+        if !src[search_from..].starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace()) {
+            continue;
+        }
+        let Some(tag_len) = src[start..].find('>') else {
+            return Err(Error::at(src, start, "unterminated `<metadata>` tag"));
+        };
+        search_from = start + tag_len + 1;
+        if src[..search_from - 1].ends_with('/') {
+            // A self-closing `<metadata/>` has no content.
+            continue;
+        }
+        let Some(content_len) = src[search_from..].find("</metadata>") else {
+            return Err(Error::at(src, start, "unterminated `<metadata>` element"));
+        };
+        let content = &src[search_from..search_from + content_len];
+        search_from += content_len;
+        if !content.trim_start().starts_with('{') {
+            continue;
+        }
+        let description = unescape(content)
+            .ok_or_else(|| Error::at(src, start, "invalid entity in embedded description"))
+            .and_then(|content| {
+                Description::from_json(content.trim()).map_err(|e| {
+                    Error::at(
+                        src,
+                        start,
+                        format!("invalid embedded description: {}", e.message),
+                    )
+                })
+            });
+        match description {
+            Ok(description) => return Ok(description),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| Error::at(src, 0, "no embedded description found")))
+}
+
+/// Resolve the predefined XML entities and character references in `s`.
+fn unescape(s: &str) -> Option<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp + 1..];
+        let semicolon = rest.find(';')?;
+        let entity = &rest[..semicolon];
+        rest = &rest[semicolon + 1..];
+        let c = match entity {
+            "quot" => '"',
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()?
+                } else {
+                    entity.strip_prefix('#')?.parse().ok()?
+                };
+                char::from_u32(code)?
+            }
+        };
+        out.push(c);
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescape_entities() {
+        assert_eq!(
+            unescape("&lt;a&gt; &amp;&quot;&#x27;&#39;&apos;").as_deref(),
+            Some("<a> &\"'''")
+        );
+        assert_eq!(unescape("&bogus;"), None);
+        assert_eq!(unescape("&amp"), None);
+    }
+
+    #[test]
+    fn foreign_metadata_is_skipped() {
+        let src = "<svg>\n<metadata>{}</metadata>\n<metadata><rdf:RDF/></metadata>\n<metadata>\n{&quot;kind&quot;:&quot;empty&quot;}</metadata>\n</svg>";
+        assert_eq!(import(src).unwrap(), Description::new("empty"));
+        let err = import("<svg><metadata>{</metadata></svg>").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        let err = import("<svg><metadata>x</metadata>\n<metadata>{}</metadata></svg>").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(
+            import("<svg/>").unwrap_err().message,
+            "no embedded description found"
+        );
+    }
+
+    #[test]
+    fn only_metadata_elements_are_read() {
+        let src = "<svg><metadataX>{}</metadataX><metadata>{&quot;kind&quot;:&quot;empty&quot;}</metadata></svg>";
+        assert_eq!(import(src).unwrap(), Description::new("empty"));
+        assert_eq!(
+            import("<svg><metadataX>{}</metadataX></svg>")
+                .unwrap_err()
+                .message,
+            "no embedded description found"
+        );
+    }
+
+    #[test]
+    fn self_closing_metadata_is_skipped() {
+        let src =
+            "<svg><metadata/><metadata >{&quot;kind&quot;:&quot;empty&quot;}</metadata></svg>";
+        assert_eq!(import(src).unwrap(), Description::new("empty"));
+        let src = "<svg><metadata\n/><desc>{}</desc></svg>";
+        assert_eq!(
+            import(src).unwrap_err().message,
+            "no embedded description found"
+        );
+    }
+}
@@ -0,0 +1,748 @@
+//! Import the rules section of a Yacc/Bison grammar file (`.y`).
+//!
+//! Semantic actions, mid-rule actions, named references (`expr[lhs]`) and
+//! directives such as `%prec`, `%dprec` and `%merge` are dropped. Every rule
+//! becomes a [`Choice`](crate::Choice) of [`Sequence`](crate::Sequence)s, with
+//! empty alternatives mapped to [`Empty`](crate::Empty).
+//!
+//! Symbols declared via `%token` (or one of the precedence declarations
+//! `%left`, `%right`, `%nonassoc` and `%precedence`), character literals and
+//! string literals become [`Terminal`](crate::Terminal)s; if a token is given an
+//! alias (`%token LE "<="`), the alias is used as the label. Every other symbol
+//! becomes a [`NonTerminal`](crate::NonTerminal).
+//!
+//! Directly left-recursive rules such as `list: list ',' item | item;` are
+//! folded into a [`Repeat`](crate::Repeat) by default, see
+//! [`Importer::set_fold_left_recursion`].
+//!
+//! Every symbol is annotated with its [`SourceSpan`] in the grammar file, see
+//! [`Expr::Spanned`].
+//!
+//! # Example
+//! ```rust
+//! use railroad::grammar::Expr;
+//! use railroad::import::yacc;
+//!
+//! let grammar = yacc::import(r#"
+//! %token NUMBER
+//! %%
+//! list: list ',' NUMBER { $$ = append($1, $3); }
+//!     | NUMBER
+//!     ;
+//! "#).unwrap();
+//!
+//! assert_eq!(
+//!     grammar.rules[0].expr,
+//!     Expr::repeat(
+//!         Expr::Terminal("NUMBER".to_owned()),
+//!         Expr::Terminal(",".to_owned()),
+//!     )
+//! );
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use super::Error;
+use crate::{
+    SourceSpan,
+    grammar::{Expr, Grammar, Rule},
+};
+
+/// Imports Yacc/Bison grammars, with knobs for how rules are translated.
+#[derive(Debug, Clone)]
+pub struct Importer {
+    fold_left_recursion: bool,
+}
+
+impl Default for Importer {
+    fn default() -> Self {
+        Self {
+            fold_left_recursion: true,
+        }
+    }
+}
+
+impl Importer {
+    /// Construct an importer using the default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold directly left-recursive rules into a `Repeat` (the default).
+    ///
+    /// A rule `A: A a | b;` describes `b` followed by any number of `a`. If
+    /// `a` ends in `b` (as in `list: list ',' item | item;`), the rule becomes a
+    /// `Repeat` of `b` with the remainder of `a` on the return path. Otherwise
+    /// it becomes `b` followed by an optional `Repeat` of `a`, or just the
+    /// optional `Repeat` if `b` is empty (`A: %empty | A a;`). If disabled, the
+    /// recursion is kept as a `NonTerminal` referring to the rule itself.
+    pub fn set_fold_left_recursion(&mut self, fold: bool) -> &mut Self {
+        self.fold_left_recursion = fold;
+        self
+    }
+
+    /// Import the grammar in `src`.
+    ///
+    /// # Errors
+    /// If `src` has no `%%`-separated rules section or if it is malformed.
+    pub fn import(&self, src: &str) -> Result<Grammar, Error> {
+        let tokens = Lexer::new(src).tokenize()?;
+        let mut parser = Parser {
+            src,
+            tokens: &tokens,
+            pos: 0,
+        };
+        let tokens_decl = parser.declarations()?;
+        let raw_rules = parser.rules()?;
+
+        let rules = raw_rules
+            .into_iter()
+            .map(|(name, alternatives)| {
+                let alternatives = alternatives
+                    .into_iter()
+                    .map(|alt| {
+                        alt.into_iter()
+                            .map(|(symbol, bytes)| {
+                                tokens_decl
+                                    .expr(symbol)
+                                    .with_span(SourceSpan::locate(src, bytes))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                let expr = if self.fold_left_recursion {
+                    fold_left_recursion(&name, alternatives)
+                } else {
+                    alternatives_to_expr(alternatives)
+                };
+                Rule::new(name, expr)
+            })
+            .collect();
+        Ok(Grammar { rules })
+    }
+}
+
+/// Import the grammar in `src` using the default settings.
+///
+/// # Errors
+/// If `src` has no `%%`-separated rules section or if it is malformed.
+pub fn import(src: &str) -> Result<Grammar, Error> {
+    Importer::new().import(src)
+}
+
+fn alternatives_to_expr(alternatives: Vec<Vec<Expr>>) -> Expr {
+    Expr::choice(alternatives.into_iter().map(Expr::sequence).collect())
+}
+
+fn fold_left_recursion(name: &str, alternatives: Vec<Vec<Expr>>) -> Expr {
+    let is_self = |e: &Expr| matches!(e.unspanned(), Expr::NonTerminal(n) if n == name);
+    let (recursive, base): (Vec<_>, Vec<_>) = alternatives
+        .into_iter()
+        .partition(|alt| alt.first().is_some_and(is_self));
+    let tails: Vec<Vec<Expr>> = recursive
+        .into_iter()
+        .map(|mut alt| alt.split_off(1))
+        .filter(|tail| !tail.is_empty())
+        .collect();
+
+    // Without a base case the rule never terminates; without a non-empty tail
+    // the recursion adds nothing. Neither can be expressed as a repetition.
+    if base.is_empty() || tails.is_empty() {
+        let mut alternatives = base;
+        alternatives.extend(tails.into_iter().map(|tail| {
+            let mut alt = vec![Expr::NonTerminal(name.to_owned())];
+            alt.extend(tail);
+            alt
+        }));
+        return alternatives_to_expr(alternatives);
+    }
+
+    // `A: A s b | b` is `b (s b)*`, which `Repeat` draws as `b` with `s` on the
+    // return path.
+    if let ([tail], [base_alt]) = (tails.as_slice(), base.as_slice())
+        && !base_alt.is_empty()
+        && tail.ends_with(base_alt)
+    {
+        let separator = tail[..tail.len() - base_alt.len()].to_vec();
+        return Expr::repeat(Expr::sequence(base_alt.clone()), Expr::sequence(separator));
+    }
+
+    // `A: A t | b` is `b t*`; with an empty `b` that is just `t*`.
+    let base = alternatives_to_expr(base);
+    let tail = Expr::Optional(Box::new(Expr::repeat(
+        alternatives_to_expr(tails),
+        Expr::Empty,
+    )));
+    if base.is_empty() {
+        tail
+    } else {
+        Expr::sequence(vec![base, tail])
+    }
+}
+
+/// A symbol as it appears on the right-hand side of a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Symbol {
+    Ident(String),
+    Literal(String),
+}
+
+/// Tokens declared in the first section of the grammar.
+#[derive(Debug, Default)]
+struct TokenDecls {
+    tokens: HashSet<String>,
+    aliases: HashMap<String, String>,
+}
+
+impl TokenDecls {
+    fn expr(&self, symbol: Symbol) -> Expr {
+        match symbol {
+            Symbol::Literal(s) => Expr::Terminal(s),
+            Symbol::Ident(s) => {
+                if let Some(alias) = self.aliases.get(&s) {
+                    Expr::Terminal(alias.clone())
+                } else if self.tokens.contains(&s) || s == "error" {
+                    Expr::Terminal(s)
+                } else {
+                    Expr::NonTerminal(s)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Ident(String),
+    Char(String),
+    Str(String),
+    Number,
+    Directive(String),
+    Tag,
+    NamedRef,
+    Action,
+    Colon,
+    Pipe,
+    Semicolon,
+    Separator,
+    Other(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    offset: usize,
+    end: usize,
+}
+
+struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.src[self.pos..].chars().nth(1)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, offset: usize, message: &str) -> Error {
+        Error::at(self.src, offset, message)
+    }
+
+    /// Tokenize the declarations and the rules section; the epilogue after
+    /// the second `%%` is never looked at.
+    fn tokenize(mut self) -> Result<Vec<Token>, Error> {
+        let mut tokens = Vec::new();
+        let mut separators = 0;
+        while let Some(token) = self.next_token()? {
+            if token.kind == TokenKind::Separator {
+                separators += 1;
+                if separators == 2 {
+                    break;
+                }
+            }
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), Error> {
+        loop {
+            match (self.peek(), self.peek_second()) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.bump();
+                }
+                (Some('/'), Some('*')) => {
+                    let start = self.pos;
+                    match self.src[self.pos + 2..].find("*/") {
+                        Some(end) => self.pos += 2 + end + 2,
+                        None => return Err(self.error(start, "unterminated comment")),
+                    }
+                }
+                (Some('/'), Some('/')) => {
+                    self.pos = self.src[self.pos..]
+                        .find('\n')
+                        .map_or(self.src.len(), |end| self.pos + end);
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, Error> {
+        self.skip_whitespace_and_comments()?;
+        let offset = self.pos;
+        let Some(c) = self.peek() else {
+            return Ok(None);
+        };
+        let kind = match c {
+            '%' => self.percent(offset)?,
+            '\'' => TokenKind::Char(self.quoted('\'')?),
+            '"' => TokenKind::Str(self.quoted('"')?),
+            '{' => {
+                self.braced()?;
+                TokenKind::Action
+            }
+            '<' => {
+                self.delimited('<', '>')?;
+                TokenKind::Tag
+            }
+            '[' => {
+                self.delimited('[', ']')?;
+                TokenKind::NamedRef
+            }
+            ':' => {
+                self.bump();
+                TokenKind::Colon
+            }
+            '|' => {
+                self.bump();
+                TokenKind::Pipe
+            }
+            ';' => {
+                self.bump();
+                TokenKind::Semicolon
+            }
+            c if c.is_ascii_digit() => {
+                while self.peek().is_some_and(|c| c.is_ascii_alphanumeric()) {
+                    self.bump();
+                }
+                TokenKind::Number
+            }
+            c if is_ident_start(c) => TokenKind::Ident(self.ident()),
+            c => {
+                self.bump();
+                TokenKind::Other(c)
+            }
+        };
+        Ok(Some(Token {
+            kind,
+            offset,
+            end: self.pos,
+        }))
+    }
+
+    fn ident(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(is_ident_continue) {
+            self.bump();
+        }
+        self.src[start..self.pos].to_owned()
+    }
+
+    fn percent(&mut self, offset: usize) -> Result<TokenKind, Error> {
+        self.bump();
+        match self.peek() {
+            Some('%') => {
+                self.bump();
+                Ok(TokenKind::Separator)
+            }
+            Some('{') => match self.src[self.pos..].find("%}") {
+                Some(end) => {
+                    self.pos += end + 2;
+                    Ok(TokenKind::Action)
+                }
+                None => Err(self.error(offset, "unterminated `%{` block")),
+            },
+            Some(c) if is_ident_start(c) => Ok(TokenKind::Directive(self.ident())),
+            _ => Err(self.error(offset, "expected a directive after `%`")),
+        }
+    }
+
+    /// Consume a quoted literal, returning its content with quotes removed and
+    /// escaped quotes and backslashes resolved.
+    fn quoted(&mut self, quote: char) -> Result<String, Error> {
+        let start = self.pos;
+        self.bump();
+        let mut content = String::new();
+        loop {
+            match self.bump() {
+                Some('\\') => match self.bump() {
+                    Some(c) if c == quote || c == '\\' => content.push(c),
+                    Some(c) => {
+                        content.push('\\');
+                        content.push(c);
+                    }
+                    None => break,
+                },
+                Some(c) if c == quote => return Ok(content),
+                Some('\n') | None => break,
+                Some(c) => content.push(c),
+            }
+        }
+        Err(self.error(start, "unterminated literal"))
+    }
+
+    /// Consume a brace-delimited action, respecting nested braces, literals
+    /// and comments within.
+    fn braced(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            match (c, self.peek_second()) {
+                ('{', _) => {
+                    depth += 1;
+                    self.bump();
+                }
+                ('}', _) => {
+                    self.bump();
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                ('\'' | '"', _) => {
+                    self.quoted(c)?;
+                }
+                ('/', Some('*' | '/')) => self.skip_whitespace_and_comments()?,
+                _ => {
+                    self.bump();
+                }
+            }
+        }
+        Err(self.error(start, "unterminated action"))
+    }
+
+    fn delimited(&mut self, open: char, close: char) -> Result<(), Error> {
+        let start = self.pos;
+        self.bump();
+        while let Some(c) = self.bump() {
+            if c == close {
+                return Ok(());
+            }
+        }
+        Err(self.error(start, &format!("unterminated `{open}`")))
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '.'
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+/// Alternatives of a rule, each a list of symbols and where they are in the source.
+type Alternatives = Vec<Vec<(Symbol, Range<usize>)>>;
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|t| &t.kind)
+    }
+
+    fn peek_second(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos + 1).map(|t| &t.kind)
+    }
+
+    fn bump(&mut self) -> Option<&TokenKind> {
+        let token = self.tokens.get(self.pos)?;
+        self.pos += 1;
+        Some(&token.kind)
+    }
+
+    fn error(&self, message: &str) -> Error {
+        let offset = self
+            .tokens
+            .get(self.pos)
+            .map_or(self.src.len(), |t| t.offset);
+        Error::at(self.src, offset, message)
+    }
+
+    /// Walk the declarations up to the first `%%`, collecting token names.
+    fn declarations(&mut self) -> Result<TokenDecls, Error> {
+        let mut decls = TokenDecls::default();
+        let mut declaring = false;
+        let mut last_token: Option<String> = None;
+        loop {
+            match self.bump() {
+                None => return Err(self.error("expected `%%` before the rules section")),
+                Some(TokenKind::Separator) => return Ok(decls),
+                Some(TokenKind::Directive(d)) => {
+                    declaring = matches!(
+                        d.as_str(),
+                        "token" | "left" | "right" | "nonassoc" | "precedence"
+                    );
+                    last_token = None;
+                }
+                Some(TokenKind::Ident(name)) if declaring => {
+                    decls.tokens.insert(name.clone());
+                    last_token = Some(name.clone());
+                }
+                Some(TokenKind::Str(alias)) if declaring => {
+                    if let Some(name) = last_token.take() {
+                        decls.aliases.insert(name, alias.clone());
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Parse all rules up to the end of the rules section, merging
+    /// alternatives of rules that are defined more than once.
+    fn rules(&mut self) -> Result<Vec<(String, Alternatives)>, Error> {
+        let mut rules: Vec<(String, Alternatives)> = Vec::new();
+        while let Some(kind) = self.peek() {
+            let name = match kind {
+                TokenKind::Ident(name) => name.clone(),
+                TokenKind::Semicolon => {
+                    self.bump();
+                    continue;
+                }
+                _ => return Err(self.error("expected the name of a rule")),
+            };
+            self.bump();
+            if self.peek() == Some(&TokenKind::NamedRef) {
+                self.bump();
+            }
+            if self.bump() != Some(&TokenKind::Colon) {
+                self.pos -= 1;
+                return Err(self.error(&format!("expected `:` after rule name `{name}`")));
+            }
+            let alternatives = self.alternatives()?;
+            match rules.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, existing)) => existing.extend(alternatives),
+                None => rules.push((name, alternatives)),
+            }
+        }
+        Ok(rules)
+    }
+
+    fn alternatives(&mut self) -> Result<Alternatives, Error> {
+        let mut alternatives = vec![Vec::new()];
+        loop {
+            match self.peek() {
+                None => break,
+                Some(TokenKind::Semicolon) => {
+                    self.bump();
+                    break;
+                }
+                // A new rule starts without a terminating semicolon.
+                Some(TokenKind::Ident(_))
+                    if matches!(
+                        self.peek_second(),
+                        Some(TokenKind::Colon | TokenKind::NamedRef)
+                    ) && self.starts_rule() =>
+                {
+                    break;
+                }
+                Some(TokenKind::Pipe) => {
+                    self.bump();
+                    alternatives.push(Vec::new());
+                }
+                Some(_) => {
+                    if let Some(symbol) = self.item()? {
+                        alternatives.last_mut().unwrap().push(symbol);
+                    }
+                }
+            }
+        }
+        Ok(alternatives)
+    }
+
+    /// Returns `true` if the identifier at the current position begins a rule,
+    /// i.e. is followed by `:`, possibly after a named reference.
+    fn starts_rule(&self) -> bool {
+        match self.peek_second() {
+            Some(TokenKind::Colon) => true,
+            Some(TokenKind::NamedRef) => matches!(
+                self.tokens.get(self.pos + 2).map(|t| &t.kind),
+                Some(TokenKind::Colon)
+            ),
+            _ => false,
+        }
+    }
+
+    /// Parse a single item on the right-hand side, returning the symbol and
+    /// its location if the item is one.
+    fn item(&mut self) -> Result<Option<(Symbol, Range<usize>)>, Error> {
+        let bytes = self.tokens.get(self.pos).map(|t| t.offset..t.end);
+        let symbol = match self.bump() {
+            Some(TokenKind::Ident(name)) => Some(Symbol::Ident(name.clone())),
+            Some(TokenKind::Char(s) | TokenKind::Str(s)) => Some(Symbol::Literal(s.clone())),
+            Some(TokenKind::Action | TokenKind::Tag) => None,
+            Some(TokenKind::Directive(d)) => {
+                match d.as_str() {
+                    "empty" => {}
+                    // `%prec` names a symbol, which is not part of the rule.
+                    "prec" => {
+                        self.bump();
+                    }
+                    // `%dprec N`, `%merge <fn>`, `%expect N` and `%expect-rr N`.
+                    _ => {
+                        if matches!(self.peek(), Some(TokenKind::Number | TokenKind::Tag)) {
+                            self.bump();
+                        }
+                    }
+                }
+                None
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("unexpected token in rule"));
+            }
+        };
+        if symbol.is_some() && self.peek() == Some(&TokenKind::NamedRef) {
+            self.bump();
+        }
+        Ok(symbol.zip(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(s: &str) -> Expr {
+        Expr::Terminal(s.to_owned())
+    }
+
+    fn nt(s: &str) -> Expr {
+        Expr::NonTerminal(s.to_owned())
+    }
+
+    #[test]
+    fn actions_prec_and_named_references_are_dropped() {
+        let grammar = import(
+            r#"
+%{
+#include <stdio.h>
+%}
+%union { int i; char *s; }
+%token <i> NUM
+%left '+' '-'
+%left UMINUS
+%%
+exp[res]: exp[l] '+' exp[r] { $res = $l + $r; /* } */ }
+    | '-' exp %prec UMINUS { $$ = -$2; }
+    | NUM
+    ;
+%%
+int main(void) { return yyparse(); }
+"#,
+        )
+        .unwrap();
+        assert_eq!(grammar.rules.len(), 1);
+        assert_eq!(grammar.rules[0].name, "exp");
+        assert_eq!(
+            grammar.rules[0].expr,
+            Expr::Sequence(vec![
+                Expr::Choice(vec![Expr::Sequence(vec![t("-"), nt("exp")]), t("NUM")]),
+                Expr::Optional(Box::new(Expr::repeat(
+                    Expr::Sequence(vec![t("+"), nt("exp")]),
+                    Expr::Empty
+                ))),
+            ])
+        );
+    }
+
+    #[test]
+    fn empty_alternatives_and_aliases() {
+        let grammar =
+            import("%token LE \"<=\" IDENT\n%%\nopt_cmp: %empty | LE IDENT | /* nothing */ ;\n")
+                .unwrap();
+        assert_eq!(
+            grammar.rules[0].expr,
+            Expr::Choice(vec![
+                Expr::Empty,
+                Expr::Sequence(vec![t("<="), t("IDENT")]),
+                Expr::Empty,
+            ])
+        );
+    }
+
+    #[test]
+    fn rules_without_semicolons_and_repeated_definitions_merge() {
+        let grammar = import("%%\na: b\nb: 'x'\na: 'y'").unwrap();
+        let names: Vec<_> = grammar.rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(grammar.rules[0].expr, Expr::Choice(vec![nt("b"), t("y")]));
+    }
+
+    #[test]
+    fn left_recursion_folding_can_be_disabled() {
+        let src = "%%\nlist: list item | item;";
+        assert_eq!(
+            import(src).unwrap().rules[0].expr,
+            Expr::repeat(nt("item"), Expr::Empty)
+        );
+        assert_eq!(
+            Importer::new()
+                .set_fold_left_recursion(false)
+                .import(src)
+                .unwrap()
+                .rules[0]
+                .expr,
+            Expr::Choice(vec![
+                Expr::Sequence(vec![nt("list"), nt("item")]),
+                nt("item")
+            ])
+        );
+    }
+
+    #[test]
+    fn left_recursion_without_base_case_is_kept() {
+        assert_eq!(
+            import("%%\nx: x 'a';").unwrap().rules[0].expr,
+            Expr::Sequence(vec![nt("x"), t("a")])
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_position() {
+        let err = import("%token A\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("%%"));
+
+        let err = import("%%\nrule: A { unterminated").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 9));
+
+        let err = import("%%\n'x': A;").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+}
@@ -0,0 +1,414 @@
+//! A minimal JSON reader and writer, just enough for the formats this crate
+//! consumes and produces.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    marker::PhantomData,
+    ops::Range,
+};
+
+/// A JSON value. Objects keep their members in document order.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    /// A number, as written in the source.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Look up the member `key` of an object.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Self::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Self::Object(o) => Some(o),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Number(n) => f.write_str(n),
+            Self::String(s) => write_string(f, s),
+            Self::Array(items) => {
+                f.write_char('[')?;
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            Self::Object(members) => {
+                f.write_char('{')?;
+                for (idx, (key, value)) in members.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut impl Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if u32::from(c) < 0x20 => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// An error while parsing JSON, at byte-offset `offset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParseError {
+    pub(crate) offset: usize,
+    pub(crate) message: &'static str,
+}
+
+/// Parse a complete JSON document.
+pub(crate) fn parse(src: &str) -> Result<Value, ParseError> {
+    parse_with_spans(src).map(|(value, _)| value)
+}
+
+/// Parse a complete JSON document, along with the byte ranges of its values
+/// and object keys in the order they start; see [`Spans::new`].
+pub(crate) fn parse_with_spans(src: &str) -> Result<(Value, Vec<Range<usize>>), ParseError> {
+    let mut parser = Parser {
+        src,
+        pos: 0,
+        spans: Vec::new(),
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < src.len() {
+        return Err(parser.error("trailing characters after JSON value"));
+    }
+    Ok((value, parser.spans))
+}
+
+/// Where the values of a document and the keys of its objects are written in
+/// its source, by their address in the parsed document.
+#[derive(Debug, Default)]
+pub(crate) struct Spans<'a> {
+    spans: HashMap<*const (), Range<usize>>,
+    document: PhantomData<&'a Value>,
+}
+
+impl<'a> Spans<'a> {
+    /// Locate the values and keys of `document`, given the `ranges` returned
+    /// along with it by [`parse_with_spans`].
+    pub(crate) fn new(document: &'a Value, ranges: Vec<Range<usize>>) -> Self {
+        let mut spans = Self::default();
+        spans.collect(document, &mut ranges.into_iter());
+        spans
+    }
+
+    fn collect(&mut self, value: &'a Value, ranges: &mut impl Iterator<Item = Range<usize>>) {
+        self.spans
+            .extend(ranges.next().map(|r| (std::ptr::from_ref(value).cast(), r)));
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    self.collect(item, ranges);
+                }
+            }
+            Value::Object(members) => {
+                for (key, value) in members {
+                    self.spans
+                        .extend(ranges.next().map(|r| (std::ptr::from_ref(key).cast(), r)));
+                    self.collect(value, ranges);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The bytes `value` spans in the source, quotes included.
+    pub(crate) fn value(&self, value: &'a Value) -> Option<Range<usize>> {
+        self.spans.get(&std::ptr::from_ref(value).cast()).cloned()
+    }
+
+    /// The bytes the object key `key` spans in the source, quotes included.
+    pub(crate) fn key(&self, key: &'a String) -> Option<Range<usize>> {
+        self.spans.get(&std::ptr::from_ref(key).cast()).cloned()
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    /// The byte ranges of the values and keys parsed so far.
+    spans: Vec<Range<usize>>,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError {
+            offset: self.pos,
+            message,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8, message: &'static str) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+        let idx = self.spans.len();
+        self.spans.push(self.pos..self.pos);
+        let value = self.unspanned_value()?;
+        self.spans[idx].end = self.pos;
+        Ok(value)
+    }
+
+    fn unspanned_value(&mut self) -> Result<Value, ParseError> {
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'-' | b'0'..=b'9') => Ok(self.number()),
+            Some(_) => {
+                for (word, value) in [
+                    ("null", Value::Null),
+                    ("true", Value::Bool(true)),
+                    ("false", Value::Bool(false)),
+                ] {
+                    if self.src[self.pos..].starts_with(word) {
+                        self.pos += word.len();
+                        return Ok(value);
+                    }
+                }
+                Err(self.error("expected a JSON value"))
+            }
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, ParseError> {
+        self.pos += 1;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a string as object key"));
+            }
+            let start = self.pos;
+            let key = self.string()?;
+            self.spans.push(start..self.pos);
+            self.expect(b':', "expected `:` after object key")?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(members));
+                }
+                _ => return Err(self.error("expected `,` or `}` in object")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, ParseError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                _ => return Err(self.error("expected `,` or `]` in array")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Value {
+        let start = self.pos;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.pos += 1;
+        }
+        Value::Number(self.src[start..self.pos].to_owned())
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            let Some(c) = self.src[self.pos..].chars().next() else {
+                self.pos = start;
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escape = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    match escape {
+                        b'"' => s.push('"'),
+                        b'\\' => s.push('\\'),
+                        b'/' => s.push('/'),
+                        b'b' => s.push('\u{8}'),
+                        b'f' => s.push('\u{c}'),
+                        b'n' => s.push('\n'),
+                        b'r' => s.push('\r'),
+                        b't' => s.push('\t'),
+                        b'u' => s.push(self.unicode_escape()?),
+                        _ => return Err(self.error("invalid escape sequence")),
+                    }
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let hex = self
+            .src
+            .get(self.pos..self.pos + 4)
+            .and_then(|h| u32::from_str_radix(h, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(hex)
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, ParseError> {
+        let high = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) && self.src[self.pos..].starts_with("\\u") {
+            self.pos += 2;
+            let low = self.hex4()?;
+            0x10000 + ((high - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_preserves_member_order() {
+        let src = r#"{"b":[1,-2.5e3,true,null],"a":"x\"\n\u00e9\ud83d\ude00"}"#;
+        let value = parse(src).unwrap();
+        assert_eq!(value.get("a").and_then(Value::as_str), Some("x\"\né😀"));
+        assert_eq!(
+            value.get("b").and_then(Value::as_array).map(<[_]>::len),
+            Some(4)
+        );
+        assert_eq!(
+            value.to_string(),
+            "{\"b\":[1,-2.5e3,true,null],\"a\":\"x\\\"\\né😀\"}"
+        );
+    }
+
+    #[test]
+    fn spans_cover_values_and_keys() {
+        let src = r#"{"a": [1, "xy"], "b" :{}}"#;
+        let (value, ranges) = parse_with_spans(src).unwrap();
+        let spans = Spans::new(&value, ranges);
+        let span = |range: Option<Range<usize>>| &src[range.unwrap()];
+        assert_eq!(span(spans.value(&value)), src);
+        let members = value.as_object().unwrap();
+        assert_eq!(span(spans.key(&members[1].0)), r#""b""#);
+        assert_eq!(span(spans.value(&members[1].1)), "{}");
+        let items = members[0].1.as_array().unwrap();
+        assert_eq!(span(spans.value(&items[0])), "1");
+        assert_eq!(span(spans.value(&items[1])), r#""xy""#);
+    }
+
+    #[test]
+    fn errors_carry_offsets() {
+        assert_eq!(parse("[1, }").unwrap_err().offset, 4);
+        assert_eq!(parse("{\"a\" 1}").unwrap_err().offset, 5);
+        assert_eq!(parse("\"abc").unwrap_err().offset, 0);
+        assert_eq!(parse("1 2").unwrap_err().offset, 2);
+    }
+}
@@ -0,0 +1,604 @@
+//! Orientations, the transform text is drawn under, and a backend drawing
+//! through a transform.
+//!
+//! Primitives always lay themselves out left-to-right. Other orientations and
+//! right-to-left diagrams are produced by the [`crate::Diagram`], which wraps
+//! the root node in a group with a `transform`. Only text needs special
+//! treatment: boxes holding text are laid out transposed where needed, see
+//! [`crate::Node::compute_geometry_in`], and text elements carry the inverse
+//! transform, so glyphs come out upright.
+
+use std::{cell::Cell, fmt};
+
+use crate::{
+    HDir, Node, NodeGeometry, RenderBackend,
+    description::Description,
+    placement::{Primitive, path_primitives},
+    rich_text::{Line, Span},
+    svg,
+};
+
+/// The direction in which a diagram flows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Left-to-right, with alternatives stacked on top of each other.
+    #[default]
+    Horizontal,
+    /// Top-to-bottom, with alternatives side by side.
+    Vertical,
+}
+
+/// An affine transform with integral coefficients, as in SVG's `matrix(a b c d e f)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Transform {
+    a: i64,
+    b: i64,
+    c: i64,
+    d: i64,
+    e: i64,
+    f: i64,
+}
+
+impl Transform {
+    /// The transform which leaves everything in place.
+    pub(crate) const IDENTITY: Self = Self::translate(0, 0);
+
+    /// Move everything by `(e, f)`.
+    pub(crate) const fn translate(e: i64, f: i64) -> Self {
+        Self {
+            a: 1,
+            b: 0,
+            c: 0,
+            d: 1,
+            e,
+            f,
+        }
+    }
+
+    /// Mirror along the vertical axis at `width / 2`, so that `0` and `width`
+    /// trade places.
+    pub(crate) const fn mirror(width: i64) -> Self {
+        Self {
+            a: -1,
+            b: 0,
+            c: 0,
+            d: 1,
+            e: width,
+            f: 0,
+        }
+    }
+
+    /// Swap the x- and y-axis, then translate by `(e, f)`.
+    pub(crate) fn transpose(e: i64, f: i64) -> Self {
+        Self {
+            a: 0,
+            b: 1,
+            c: 1,
+            d: 0,
+            e,
+            f,
+        }
+    }
+
+    pub(crate) fn apply(self, x: i64, y: i64) -> (i64, i64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    /// The transform which applies `self`, then `next`.
+    pub(crate) fn then(self, next: Self) -> Self {
+        let (e, f) = next.apply(self.e, self.f);
+        Self {
+            a: next.a * self.a + next.c * self.b,
+            b: next.b * self.a + next.d * self.b,
+            c: next.a * self.c + next.c * self.d,
+            d: next.b * self.c + next.d * self.d,
+            e,
+            f,
+        }
+    }
+
+    /// Returns `true` if this transform swaps the x- and y-axis.
+    pub(crate) fn swaps_axes(self) -> bool {
+        self.a == 0
+    }
+
+    /// Returns `true` if this transform mirrors, turning clockwise into
+    /// counter-clockwise.
+    pub(crate) fn flips(self) -> bool {
+        self.a * self.d - self.b * self.c < 0
+    }
+
+    /// The inverse transform; all transforms used here have a determinant of ±1.
+    pub(crate) fn inverse(self) -> Self {
+        let det = self.a * self.d - self.b * self.c;
+        debug_assert!(det.abs() == 1);
+        let (a, b, c, d) = (self.d * det, -self.b * det, -self.c * det, self.a * det);
+        Self {
+            a,
+            b,
+            c,
+            d,
+            e: -(a * self.e + c * self.f),
+            f: -(b * self.e + d * self.f),
+        }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "matrix({} {} {} {} {} {})",
+            self.a, self.b, self.c, self.d, self.e, self.f
+        )
+    }
+}
+
+thread_local! {
+    static TEXT_TRANSFORM: Cell<Option<Transform>> = const { Cell::new(None) };
+}
+
+/// Restores the previous text transform when dropped.
+pub(crate) struct Guard(Option<Transform>);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        TEXT_TRANSFORM.with(|t| t.set(self.0));
+    }
+}
+
+/// Draw text under the given transform until the guard is dropped.
+pub(crate) fn enter_transform(transform: Transform) -> Guard {
+    Guard(TEXT_TRANSFORM.with(|t| t.replace(Some(transform))))
+}
+
+/// The layout geometry `(entry_height, height, width)` of a box holding text
+/// in `orientation`, which is `width` wide and `height` high on screen, with
+/// the path entering at `entry_height`.
+pub(crate) fn text_box(
+    orientation: Orientation,
+    entry_height: i64,
+    height: i64,
+    width: i64,
+) -> (i64, i64, i64) {
+    match orientation {
+        Orientation::Horizontal => (entry_height, height, width),
+        Orientation::Vertical => (width / 2, width, height),
+    }
+}
+
+/// A [`RenderBackend`] which maps everything drawn through it by a transform
+/// before handing it on, as [`crate::Diagram`] does for other orientations.
+///
+/// Text stays upright: only its anchor moves. Children are handed on at the
+/// top left corner of their box on screen, but draw themselves through the
+/// transform at their position in the layout.
+pub(crate) struct TransformedBackend<'a> {
+    inner: &'a mut dyn RenderBackend,
+    transform: Transform,
+}
+
+impl<'a> TransformedBackend<'a> {
+    pub(crate) fn new(inner: &'a mut dyn RenderBackend, transform: Transform) -> Self {
+        Self { inner, transform }
+    }
+
+    /// The rectangle on screen covering the one at `(x, y)` in the layout, as
+    /// `(x, y, width, height, radius)`.
+    fn transformed_rect(
+        &self,
+        x: i64,
+        y: i64,
+        width: i64,
+        height: i64,
+        radius: i64,
+    ) -> (i64, i64, i64, i64, i64) {
+        let rect = Primitive::Rect {
+            x,
+            y,
+            width,
+            height,
+            radius,
+        };
+        let Primitive::Rect {
+            x,
+            y,
+            width,
+            height,
+            radius,
+        } = rect.transform(self.transform)
+        else {
+            unreachable!("rectangles stay rectangles");
+        };
+        (x, y, width, height, radius)
+    }
+
+    /// The anchor of text at `(x, y)`, which stays 5 units below the center
+    /// of the text on screen.
+    fn text_anchor(&self, x: i64, y: i64) -> (i64, i64) {
+        let (x, y) = self.transform.apply(x, y - 5);
+        (x, y + 5)
+    }
+}
+
+impl RenderBackend for TransformedBackend<'_> {
+    fn push_path(&mut self, path: svg::PathData) -> fmt::Result {
+        let mut transformed = svg::PathData::new(HDir::LTR);
+        let mut at = None;
+        for primitive in path_primitives(&path.to_string()) {
+            let primitive = primitive.transform(self.transform);
+            let (Primitive::Line { from, to } | Primitive::Arc { from, to, .. }) = primitive else {
+                unreachable!("paths only consist of lines and arcs");
+            };
+            if at != Some(from) {
+                transformed = transformed.move_to(from.0, from.1);
+            }
+            let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+            transformed = match primitive {
+                Primitive::Arc {
+                    radius, clockwise, ..
+                } => transformed.arc(radius, arc_between(dx, dy, clockwise)),
+                _ => transformed.line_rel(dx, dy),
+            };
+            at = Some(to);
+        }
+        self.inner.push_path(transformed)
+    }
+
+    fn push_rect(&mut self, x: i64, y: i64, width: i64, height: i64) -> fmt::Result {
+        let (x, y, width, height, _) = self.transformed_rect(x, y, width, height, 0);
+        self.inner.push_rect(x, y, width, height)
+    }
+
+    fn push_rounded_rect(
+        &mut self,
+        x: i64,
+        y: i64,
+        width: i64,
+        height: i64,
+        radius: i64,
+    ) -> fmt::Result {
+        let (x, y, width, height, radius) = self.transformed_rect(x, y, width, height, radius);
+        self.inner.push_rounded_rect(x, y, width, height, radius)
+    }
+
+    fn push_text(&mut self, x: i64, y: i64, text: &str) -> fmt::Result {
+        let (x, y) = self.text_anchor(x, y);
+        self.inner.push_text(x, y, text)
+    }
+
+    fn push_text_lines(
+        &mut self,
+        x: i64,
+        y: i64,
+        lines: &[Line<'_>],
+        line_height: i64,
+    ) -> fmt::Result {
+        let (x, y) = self.text_anchor(x, y);
+        self.inner.push_text_lines(x, y, lines, line_height)
+    }
+
+    fn push_child(
+        &mut self,
+        child: &dyn Node,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let (screen_x, screen_y, ..) = self.transformed_rect(x, y, geo.width, geo.height, 0);
+        let child = TransformedChild {
+            child,
+            x,
+            y,
+            transform: self.transform,
+        };
+        self.inner
+            .push_child(&child, screen_x, screen_y, h_dir, geo)
+    }
+
+    fn push_element(&mut self, element: svg::Element) -> fmt::Result {
+        self.inner.push_element(
+            svg::Element::new("g")
+                .set("transform", &self.transform)
+                .add(element),
+        )
+    }
+}
+
+/// The quarter-circle arc which moves by `(dx, dy)`, turning clockwise or not.
+fn arc_between(dx: i64, dy: i64, clockwise: bool) -> svg::Arc {
+    match (dx > 0, dy > 0, clockwise) {
+        (false, false, true) => svg::Arc::EastToNorth,
+        (false, true, false) => svg::Arc::EastToSouth,
+        (true, true, false) => svg::Arc::NorthToEast,
+        (false, true, true) => svg::Arc::NorthToWest,
+        (true, false, true) => svg::Arc::SouthToEast,
+        (false, false, false) => svg::Arc::SouthToWest,
+        (true, false, false) => svg::Arc::WestToNorth,
+        (true, true, true) => svg::Arc::WestToSouth,
+    }
+}
+
+/// A child handed on by a [`TransformedBackend`], which draws itself at its
+/// position in the layout through the transform, wherever it is handed to.
+struct TransformedChild<'a> {
+    child: &'a dyn Node,
+    x: i64,
+    y: i64,
+    transform: Transform,
+}
+
+impl Node for TransformedChild<'_> {
+    fn entry_height(&self) -> i64 {
+        self.child.entry_height()
+    }
+
+    fn height(&self) -> i64 {
+        self.child.height()
+    }
+
+    fn width(&self) -> i64 {
+        self.child.width()
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        self.child.draw(x, y, h_dir)
+    }
+
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        _x: i64,
+        _y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let mut backend = TransformedBackend::new(backend, self.transform);
+        self.child
+            .emit_with_geometry(&mut backend, self.x, self.y, h_dir, geo)
+    }
+
+    fn describe(&self) -> Description {
+        self.child.describe()
+    }
+}
+
+/// The position and transform of a text element whose baseline is centered at
+/// `(x, y)` in layout coordinates.
+///
+/// For text of several `lines`, `line_height` apart, `(x, y)` is where the
+/// baseline of the middle line would be and the position returned is that of
+/// the first line.
+fn text_position(x: i64, y: i64, lines: usize, line_height: i64) -> (i64, i64, Option<Transform>) {
+    // The baseline sits 5 units below the center of the text on screen; the
+    // offsets are applied after transforming, so they point down even if the
+    // layout is rotated.
+    let above = i64::try_from(lines.saturating_sub(1)).unwrap() * line_height / 2;
+    match TEXT_TRANSFORM.with(Cell::get) {
+        None => (x, y - above, None),
+        Some(t) => {
+            let (x, y) = t.apply(x, y - 5);
+            (x, y + 5 - above, Some(t.inverse()))
+        }
+    }
+}
+
+/// Position the text element `e` with its baseline centered at `(x, y)`.
+pub(crate) fn place_text(e: svg::Element, x: i64, y: i64) -> svg::Element {
+    let (x, y, transform) = text_position(x, y, 1, 0);
+    let e = e.set("x", &x).set("y", &y);
+    match transform {
+        Some(t) => e.set("transform", &t),
+        None => e,
+    }
+}
+
+/// Streaming counterpart to [`place_text`].
+pub(crate) fn write_text_position(tag: &mut svg::StartTag<'_, '_>, x: i64, y: i64) -> fmt::Result {
+    write_text_lines_position(tag, x, y, 1, 0)
+}
+
+/// Position the text element `e` and fill it with `lines`, `line_height`
+/// apart, such that the middle line's baseline is centered at `(x, y)`.
+///
+/// A single line of unstyled text becomes the element's text, several lines
+/// become `<tspan>`s, as do styled spans within a line. These are written
+/// without line breaks in between.
+pub(crate) fn place_text_lines(
+    e: svg::Element,
+    x: i64,
+    y: i64,
+    lines: &[Line<'_>],
+    line_height: i64,
+) -> svg::Element {
+    if let [line] = lines {
+        let e = place_text(e, x, y);
+        return match plain_line(line) {
+            Some(text) => e.text(text),
+            None => add_spans(e, line),
+        };
+    }
+    let (x, y, transform) = text_position(x, y, lines.len(), line_height);
+    let mut e = e.set("x", &x).set("y", &y);
+    if let Some(t) = transform {
+        e = e.set("transform", &t);
+    }
+    for (line, y) in lines.iter().zip((0..).map(|idx| y + idx * line_height)) {
+        let tspan = svg::Element::new("tspan").set("x", &x).set("y", &y);
+        e = e.add(
+            match plain_line(line) {
+                Some(text) => tspan.text(text),
+                None => add_spans(tspan, line),
+            }
+            .inline(),
+        );
+    }
+    e
+}
+
+/// The text of `line` if it has no styled spans.
+fn plain_line<'a>(line: &Line<'a>) -> Option<&'a str> {
+    match line.as_slice() {
+        [] => Some(""),
+        [(span, text)] if span.is_plain() => Some(text),
+        _ => None,
+    }
+}
+
+/// The spans on `line` with the vertical offset of each from the previous one.
+fn shifted_spans<'a, 'b>(
+    line: &'b Line<'a>,
+) -> impl Iterator<Item = (&'a Span, &'a str, i64)> + 'b {
+    line.iter().scan(0, |shift, &(span, text)| {
+        let dy = span.shift() - *shift;
+        *shift = span.shift();
+        Some((span, text, dy))
+    })
+}
+
+/// Add the spans on `line` to `e` as `<tspan>`s.
+fn add_spans(mut e: svg::Element, line: &Line<'_>) -> svg::Element {
+    for (span, text, dy) in shifted_spans(line) {
+        let mut tspan = svg::Element::new("tspan").set_all(span.attributes());
+        if dy != 0 {
+            tspan = tspan.set("dy", &dy);
+        }
+        e = e.add(tspan.text(text).inline());
+    }
+    e
+}
+
+/// Streaming counterpart to the positioning done by [`place_text_lines`].
+pub(crate) fn write_text_lines_position(
+    tag: &mut svg::StartTag<'_, '_>,
+    x: i64,
+    y: i64,
+    lines: usize,
+    line_height: i64,
+) -> fmt::Result {
+    let (x, y, transform) = text_position(x, y, lines, line_height);
+    tag.attr("x", x)?;
+    tag.attr("y", y)?;
+    match transform {
+        Some(t) => tag.attr("transform", t),
+        None => Ok(()),
+    }
+}
+
+/// Streaming counterpart to the content written by [`place_text_lines`], to be
+/// written into a text element positioned by [`write_text_lines_position`].
+pub(crate) fn write_text_lines(
+    out: &mut svg::Renderer<'_>,
+    x: i64,
+    y: i64,
+    lines: &[Line<'_>],
+    line_height: i64,
+) -> fmt::Result {
+    if let [line] = lines {
+        return match plain_line(line) {
+            Some(text) => out.write_text(text),
+            None => write_spans(out, line),
+        };
+    }
+    let (x, y, _) = text_position(x, y, lines.len(), line_height);
+    for (line, y) in lines.iter().zip((0..).map(|idx| y + idx * line_height)) {
+        let mut tspan = out.start_element("tspan")?;
+        tspan.attr("x", x)?;
+        tspan.attr("y", y)?;
+        tspan.finish_inline()?;
+        match plain_line(line) {
+            Some(text) => out.write_text(text)?,
+            None => write_spans(out, line)?,
+        }
+        out.end_inline_element("tspan")?;
+    }
+    Ok(())
+}
+
+/// Streaming counterpart to [`add_spans`].
+fn write_spans(out: &mut svg::Renderer<'_>, line: &Line<'_>) -> fmt::Result {
+    for (span, text, dy) in shifted_spans(line) {
+        let mut tspan = out.start_element("tspan")?;
+        for (key, value) in span.attributes() {
+            tspan.attr(key, value)?;
+        }
+        if dy != 0 {
+            tspan.attr("dy", dy)?;
+        }
+        tspan.finish_inline()?;
+        out.write_text(text)?;
+        out.end_inline_element("tspan")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_undoes_transform() {
+        let t = Transform::transpose(3, -7);
+        let inv = t.inverse();
+        assert_eq!(inv.apply(t.apply(5, 11).0, t.apply(5, 11).1), (5, 11));
+        assert_eq!(inv.to_string(), "matrix(0 1 1 0 7 -3)");
+    }
+
+    #[test]
+    fn composed_transforms_apply_in_order() {
+        let t = Transform::transpose(0, 0)
+            .then(Transform::mirror(100))
+            .then(Transform::translate(10, 20));
+        assert_eq!(t.apply(5, 30), (80, 25));
+        assert_eq!(t.to_string(), "matrix(0 1 -1 0 110 20)");
+        assert_eq!(t.inverse().apply(80, 25), (5, 30));
+        assert_eq!(Transform::IDENTITY.then(t), t);
+    }
+
+    #[test]
+    fn arcs_are_recovered_from_their_primitives() {
+        use svg::Arc::*;
+
+        for arc in [
+            EastToNorth,
+            EastToSouth,
+            NorthToEast,
+            NorthToWest,
+            SouthToEast,
+            SouthToWest,
+            WestToNorth,
+            WestToSouth,
+        ] {
+            let path = svg::PathData::new(HDir::LTR).arc(12, arc).to_string();
+            let [Primitive::Arc { to, clockwise, .. }] = path_primitives(&path)[..] else {
+                panic!("{path}");
+            };
+            let recovered = arc_between(to.0, to.1, clockwise);
+            assert_eq!(
+                svg::PathData::new(HDir::LTR).arc(12, recovered).to_string(),
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn text_boxes_are_transposed_in_vertical_layouts() {
+        assert_eq!(text_box(Orientation::Horizontal, 11, 22, 60), (11, 22, 60));
+        assert_eq!(text_box(Orientation::Vertical, 11, 22, 60), (30, 60, 22));
+    }
+
+    #[test]
+    fn guards_restore_the_previous_text_transform() {
+        let outer = Transform::transpose(1, 2);
+        let _outer = enter_transform(outer);
+        {
+            let _inner = enter_transform(Transform::IDENTITY);
+            assert_eq!(TEXT_TRANSFORM.with(Cell::get), Some(Transform::IDENTITY));
+        }
+        assert_eq!(TEXT_TRANSFORM.with(Cell::get), Some(outer));
+    }
+}
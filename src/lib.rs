@@ -68,20 +68,45 @@
 //! [`Node::compute_geometry`] and usually also [`Node::draw_with_geometry`] and
 //! [`Node::render_with_geometry`] so child geometry is computed once and reused
 //! during rendering.
+//!
+//! Nodes that should also be drawn by other backends than SVG, such as
+//! [`tikz::TikzBackend`], override [`Node::emit_with_geometry`], drawing
+//! through a [`RenderBackend`] instead of building SVG elements; the built-in
+//! primitives draw all their output this way.
 
 use std::{
     collections::{self, HashMap},
     fmt, io,
 };
 
+pub mod description;
+pub mod dot;
+pub mod grammar;
+mod ids;
+pub mod import;
+mod json;
+mod layout;
+pub use crate::layout::Orientation;
 pub mod notactuallysvg;
+use crate::description::Description;
+pub mod placement;
 pub use crate::notactuallysvg as svg;
 use crate::svg::HDir;
 mod nodes;
-pub use crate::nodes::containers::{Choice, MultiChoice, Sequence, Stack};
+pub use crate::nodes::containers::{
+    Alignment, AlternatingSequence, Choice, HorizontalChoice, MultiChoice, OptionalSequence,
+    Sequence, Stack, Unordered,
+};
 pub use crate::nodes::grids::{HorizontalGrid, VerticalGrid};
 pub use crate::nodes::text::{Comment, NonTerminal, Terminal};
-pub use crate::nodes::wrappers::{LabeledBox, Link, LinkTarget, Optional, Repeat};
+mod rich_text;
+pub use crate::nodes::wrappers::{
+    LabeledBox, Link, LinkTarget, Optional, Repeat, SeparatedList, Tooltip,
+};
+pub use crate::rich_text::{Baseline, RichText, Span};
+mod source_span;
+pub use crate::source_span::SourceSpan;
+pub mod tikz;
 
 #[cfg(feature = "resvg")]
 pub mod render;
@@ -106,6 +131,37 @@ fn text_width(s: &str) -> usize {
     s.width() + (s.width() / 20)
 }
 
+/// Split some text into the lines it is drawn on.
+///
+/// Lines break at every `'\n'` and, given a `max_width`, between words so that
+/// no line is wider than `max_width` columns; a single word which is wider than
+/// that gets a line of its own.
+fn text_lines(s: &str, max_width: Option<usize>) -> Vec<&str> {
+    use unicode_width::UnicodeWidthStr;
+    let Some(max_width) = max_width else {
+        return s.split('\n').collect();
+    };
+    let mut lines = Vec::new();
+    for paragraph in s.split('\n') {
+        let mut line: Option<(usize, usize)> = None;
+        for word in paragraph.split_whitespace() {
+            // `word` is a subslice of `paragraph`, so this is its byte offset.
+            let start = word.as_ptr() as usize - paragraph.as_ptr() as usize;
+            let end = start + word.len();
+            line = match line {
+                Some((line_start, line_end)) if paragraph[line_start..end].width() > max_width => {
+                    lines.push(&paragraph[line_start..line_end]);
+                    Some((start, end))
+                }
+                Some((line_start, _)) => Some((line_start, end)),
+                None => Some((start, end)),
+            };
+        }
+        lines.push(line.map_or("", |(start, end)| &paragraph[start..end]));
+    }
+    lines
+}
+
 /// Pre-defined stylesheets
 /// ```rust
 /// use railroad::*;
@@ -183,7 +239,7 @@ pub const DEFAULT_CSS: &str = Stylesheet::Light.stylesheet();
 /// For `LabeledBox`, `children[0]` is the inner node and `children[1]` is the label.
 /// For `Repeat`, `children[0]` is the inner node and `children[1]` is the repeat node.
 /// Leaf nodes have an empty `children` vec.
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct NodeGeometry {
     /// The vertical distance from this node's top edge to its connecting path.
     pub entry_height: i64,
@@ -193,6 +249,11 @@ pub struct NodeGeometry {
     pub width: i64,
     /// Pre-computed geometry for each child, in draw order.
     pub children: Vec<NodeGeometry>,
+    /// The `id`-attribute of the node's outermost element, as assigned by
+    /// [`Diagram::set_element_ids`]; nodes leave this as `None`.
+    pub id: Option<String>,
+    /// The orientation this node was laid out in, see [`Node::compute_geometry_in`].
+    pub orientation: Orientation,
 }
 
 impl NodeGeometry {
@@ -203,6 +264,16 @@ impl NodeGeometry {
     pub fn height_below_entry(&self) -> i64 {
         self.height - self.entry_height
     }
+
+    /// This geometry without the ids of the node and its descendants, for
+    /// drawing a node a second time without repeating its ids.
+    pub(crate) fn without_ids(&self) -> Self {
+        Self {
+            children: self.children.iter().map(Self::without_ids).collect(),
+            id: None,
+            ..*self
+        }
+    }
 }
 
 /// A diagram primitive that participates in layout and SVG generation.
@@ -289,9 +360,29 @@ pub trait Node {
             height: self.height(),
             width: self.width(),
             children: vec![],
+            id: None,
+            orientation: Orientation::Horizontal,
         }
     }
 
+    /// Compute geometry for this node and its entire subtree, laid out in
+    /// `orientation`.
+    ///
+    /// Nodes always lay themselves out left-to-right; a [`Diagram`] in
+    /// [`Orientation::Vertical`] rotates the result. Boxes holding text are
+    /// laid out transposed in that case, so their text comes out upright.
+    /// [`Node::compute_geometry`] lays out nodes in
+    /// [`Orientation::Horizontal`].
+    ///
+    /// The default implementation ignores `orientation` and forwards to
+    /// [`Node::compute_geometry`], which is correct for nodes which hold no
+    /// text. Composite nodes should override this to pass `orientation` on to
+    /// their children.
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let _ = orientation;
+        self.compute_geometry()
+    }
+
     /// Draw this element using pre-computed geometry, avoiding redundant geometry
     /// recomputation for deeply nested structures.
     ///
@@ -353,7 +444,68 @@ pub trait Node {
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        out.write_display(self.draw_with_geometry(x, y, h_dir, geo))
+        out.write_element(self.draw_with_geometry(x, y, h_dir, geo))
+    }
+
+    /// Draw this element through a [`RenderBackend`].
+    ///
+    /// This is the backend counterpart to [`Node::draw`]. The default
+    /// implementation computes geometry once and forwards to
+    /// [`Node::emit_with_geometry`].
+    fn emit(&self, backend: &mut dyn RenderBackend, x: i64, y: i64, h_dir: HDir) -> fmt::Result {
+        let geo = self.compute_geometry();
+        self.emit_with_geometry(backend, x, y, h_dir, &geo)
+    }
+
+    /// Draw this element through a [`RenderBackend`] using pre-computed
+    /// geometry.
+    ///
+    /// Implementations push the primitives the node consists of, and hand
+    /// every child to [`RenderBackend::push_child`]. Unlike
+    /// [`Node::draw_with_geometry`], this draws the node's content only; the
+    /// SVG output wraps it in the node's own group, attributes and ids.
+    ///
+    /// The default implementation hands the result of
+    /// [`Node::draw_with_geometry`] to [`RenderBackend::push_element`], which
+    /// backends that can not draw SVG ignore. Override this for custom nodes
+    /// that should be drawn by any backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_element(self.draw_with_geometry(x, y, h_dir, geo))
+    }
+
+    /// Place this node and its children at `(x, y)` using pre-computed
+    /// geometry, without drawing them; see [`placement`].
+    ///
+    /// The default implementation records what [`Node::emit_with_geometry`]
+    /// draws. Nodes that draw themselves only as SVG are placed as opaque
+    /// boxes, see [`placement::PlacedNode::leaf`].
+    fn place_with_geometry(
+        &self,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> placement::PlacedNode {
+        placement::place(self, x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
+
+    /// Describe this node and its children in a serializable form.
+    ///
+    /// See [`description`] for how the description can be embedded into the
+    /// SVG output and turned back into a node tree. The default implementation
+    /// returns a description of kind [`description::UNKNOWN_KIND`], which can not
+    /// be reconstructed.
+    fn describe(&self) -> Description {
+        Description::new(description::UNKNOWN_KIND)
     }
 }
 
@@ -390,6 +542,10 @@ macro_rules! deref_impl {
                 (**self).compute_geometry()
             }
 
+            fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+                (**self).compute_geometry_in(orientation)
+            }
+
             fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
                 (**self).draw_with_geometry(x, y, h_dir, geo)
             }
@@ -404,6 +560,31 @@ macro_rules! deref_impl {
             ) -> fmt::Result {
                 (**self).render_with_geometry(out, x, y, h_dir, geo)
             }
+
+            fn emit_with_geometry(
+                &self,
+                backend: &mut dyn RenderBackend,
+                x: i64,
+                y: i64,
+                h_dir: HDir,
+                geo: &NodeGeometry,
+            ) -> fmt::Result {
+                (**self).emit_with_geometry(backend, x, y, h_dir, geo)
+            }
+
+            fn place_with_geometry(
+                &self,
+                x: i64,
+                y: i64,
+                h_dir: HDir,
+                geo: &NodeGeometry,
+            ) -> placement::PlacedNode {
+                (**self).place_with_geometry(x, y, h_dir, geo)
+            }
+
+            fn describe(&self) -> Description {
+                (**self).describe()
+            }
         }
     };
 }
@@ -472,19 +653,76 @@ fn write_debug_overlay(
     Ok(())
 }
 
-/// Internal rendering surface shared by the `svg::Element` and streaming backends.
+/// A drawing surface that nodes emit their primitives into.
+///
+/// Every built-in node draws itself through this trait in
+/// [`Node::emit_with_geometry`]; the SVG output is just one implementation.
+/// Implement it to draw diagrams with something else, such as canvas
+/// commands or PDF operators, or to record what was drawn in tests.
+///
+/// All coordinates are absolute, with the diagram laid out horizontally and
+/// unmirrored; see [`Diagram::layout`] for positions in other orientations.
+/// Text is horizontally centered at the given `x`, with its baseline at `y`.
+///
+/// Children are handed to [`RenderBackend::push_child`], which decides how to
+/// draw them: backends usually call [`Node::emit_with_geometry`] on the child
+/// with themselves, so nested nodes draw through the same backend.
+///
+/// # Example
+/// ```rust
+/// use std::fmt;
+/// use railroad::*;
+///
+/// /// Counts the boxes drawn by a node tree.
+/// #[derive(Default)]
+/// struct BoxCounter(usize);
+///
+/// impl RenderBackend for BoxCounter {
+///     fn push_path(&mut self, _: svg::PathData) -> fmt::Result {
+///         Ok(())
+///     }
+///     fn push_rect(&mut self, _: i64, _: i64, _: i64, _: i64) -> fmt::Result {
+///         self.0 += 1;
+///         Ok(())
+///     }
+///     fn push_rounded_rect(&mut self, _: i64, _: i64, _: i64, _: i64, _: i64) -> fmt::Result {
+///         self.0 += 1;
+///         Ok(())
+///     }
+///     fn push_text(&mut self, _: i64, _: i64, _: &str) -> fmt::Result {
+///         Ok(())
+///     }
+///     fn push_text_lines(&mut self, _: i64, _: i64, _: &[Vec<(&Span, &str)>], _: i64) -> fmt::Result {
+///         Ok(())
+///     }
+///     fn push_child(
+///         &mut self,
+///         child: &dyn Node,
+///         x: i64,
+///         y: i64,
+///         h_dir: svg::HDir,
+///         geo: &NodeGeometry,
+///     ) -> fmt::Result {
+///         child.emit_with_geometry(self, x, y, h_dir, geo)
+///     }
+/// }
 ///
-/// Built-in nodes express their geometry-aware draw order in terms of this trait
-/// so the crate can keep `draw_with_geometry()` and `render_with_geometry()`
-/// behavior in sync without duplicating traversal logic.
-trait RenderBackend {
-    /// Append a path element to the current output.
+/// let seq = Sequence::new(vec![
+///     Box::new(Terminal::new("a".to_owned())) as Box<dyn Node>,
+///     Box::new(Optional::new(NonTerminal::new("b".to_owned()))),
+/// ]);
+/// let mut counter = BoxCounter::default();
+/// seq.emit(&mut counter, 0, 0, svg::HDir::LTR).unwrap();
+/// assert_eq!(counter.0, 2);
+/// ```
+pub trait RenderBackend {
+    /// Draw a path.
     fn push_path(&mut self, path: svg::PathData) -> fmt::Result;
 
-    /// Append an axis-aligned rectangle to the current output.
+    /// Draw an axis-aligned rectangle.
     fn push_rect(&mut self, x: i64, y: i64, width: i64, height: i64) -> fmt::Result;
 
-    /// Append a rounded rectangle to the current output.
+    /// Draw a rectangle whose corners are rounded with `radius`.
     fn push_rounded_rect(
         &mut self,
         x: i64,
@@ -494,29 +732,102 @@ trait RenderBackend {
         radius: i64,
     ) -> fmt::Result;
 
-    /// Append a centered text element at the given coordinates.
+    /// Draw a line of text, centered at the given coordinates.
     fn push_text(&mut self, x: i64, y: i64, text: &str) -> fmt::Result;
 
-    /// Append a child node using cached geometry.
-    fn push_child<N: Node + ?Sized>(
+    /// Draw text whose `lines`, `line_height` apart, are vertically centered
+    /// around the baseline at the given coordinates.
+    ///
+    /// Each line is a sequence of text fragments and the [`Span`] styling
+    /// them.
+    fn push_text_lines(
+        &mut self,
+        x: i64,
+        y: i64,
+        lines: &[Vec<(&Span, &str)>],
+        line_height: i64,
+    ) -> fmt::Result;
+
+    /// Draw a child node at `(x, y)` using its cached geometry.
+    fn push_child(
         &mut self,
-        child: &N,
+        child: &dyn Node,
         x: i64,
         y: i64,
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result;
+
+    /// Draw a node which can only draw itself as SVG.
+    ///
+    /// This is what [`Node::emit_with_geometry`] does by default, for nodes
+    /// that only implement [`Node::draw`]. Backends which can not draw SVG
+    /// ignore the element, which is the default.
+    fn push_element(&mut self, element: svg::Element) -> fmt::Result {
+        let _ = element;
+        Ok(())
+    }
 }
 
 /// `RenderBackend` implementation that accumulates child `svg::Element`s.
 ///
-/// This powers the compatibility `draw_with_geometry()` path.
-#[derive(Default)]
-struct ElementBackend {
+/// This powers the compatibility `draw_with_geometry()` path. Custom nodes
+/// which draw themselves through [`Node::emit_with_geometry`] can use it to
+/// implement [`Node::draw`].
+///
+/// # Example
+/// ```rust
+/// use std::fmt;
+/// use railroad::*;
+///
+/// /// A square box, drawn by any backend.
+/// struct Square;
+///
+/// impl Node for Square {
+///     fn entry_height(&self) -> i64 {
+///         10
+///     }
+///     fn height(&self) -> i64 {
+///         20
+///     }
+///     fn width(&self) -> i64 {
+///         20
+///     }
+///     fn draw(&self, x: i64, y: i64, h_dir: svg::HDir) -> svg::Element {
+///         let mut backend = ElementBackend::default();
+///         self.emit(&mut backend, x, y, h_dir).unwrap();
+///         backend.into_group()
+///     }
+///     fn emit_with_geometry(
+///         &self,
+///         backend: &mut dyn RenderBackend,
+///         x: i64,
+///         y: i64,
+///         _: svg::HDir,
+///         geo: &NodeGeometry,
+///     ) -> fmt::Result {
+///         backend.push_rect(x, y, geo.width, geo.height)
+///     }
+/// }
+///
+/// assert!(Diagram::new(Square).to_string().contains(r#"width="20" x="10" y="10""#));
+/// ```
+#[derive(Debug, Default)]
+pub struct ElementBackend {
     children: Vec<svg::Element>,
 }
 
 impl ElementBackend {
+    /// Wrap everything drawn so far in a `<g>` element.
+    #[must_use]
+    pub fn into_group(self) -> svg::Element {
+        let mut group = svg::Element::new("g");
+        for child in self.children {
+            group.push(child);
+        }
+        group
+    }
+
     /// Wrap the accumulated children in a `<g>` element with debug metadata.
     ///
     /// ```ignore
@@ -530,7 +841,7 @@ impl ElementBackend {
     ///     "demo",
     ///     0,
     ///     0,
-    ///     &NodeGeometry { entry_height: 0, height: 0, width: 0, children: vec![] },
+    ///     &NodeGeometry { entry_height: 0, height: 0, width: 0, ..Default::default() },
     /// );
     /// assert!(group.to_string().starts_with("<g"));
     /// ```
@@ -588,25 +899,42 @@ impl RenderBackend for ElementBackend {
     }
 
     fn push_text(&mut self, x: i64, y: i64, text: &str) -> fmt::Result {
-        self.children.push(
-            svg::Element::new("text")
-                .set("x", &x)
-                .set("y", &y)
-                .text(text),
-        );
+        self.children
+            .push(layout::place_text(svg::Element::new("text"), x, y).text(text));
+        Ok(())
+    }
+
+    fn push_text_lines(
+        &mut self,
+        x: i64,
+        y: i64,
+        lines: &[rich_text::Line<'_>],
+        line_height: i64,
+    ) -> fmt::Result {
+        self.children.push(layout::place_text_lines(
+            svg::Element::new("text"),
+            x,
+            y,
+            lines,
+            line_height,
+        ));
         Ok(())
     }
 
-    fn push_child<N: Node + ?Sized>(
+    fn push_child(
         &mut self,
-        child: &N,
+        child: &dyn Node,
         x: i64,
         y: i64,
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        self.children
-            .push(child.draw_with_geometry(x, y, h_dir, geo));
+        self.children.push(draw_child(child, x, y, h_dir, geo));
+        Ok(())
+    }
+
+    fn push_element(&mut self, element: svg::Element) -> fmt::Result {
+        self.children.push(element);
         Ok(())
     }
 }
@@ -649,22 +977,69 @@ impl RenderBackend for RendererBackend<'_, '_> {
     }
 
     fn push_text(&mut self, x: i64, y: i64, text: &str) -> fmt::Result {
-        self.out.text_element("text", text, |tag| {
-            tag.attr("x", x)?;
-            tag.attr("y", y)
-        })
+        self.out
+            .text_element("text", text, |tag| layout::write_text_position(tag, x, y))
     }
 
-    fn push_child<N: Node + ?Sized>(
+    fn push_text_lines(
         &mut self,
-        child: &N,
+        x: i64,
+        y: i64,
+        lines: &[rich_text::Line<'_>],
+        line_height: i64,
+    ) -> fmt::Result {
+        let mut text = self.out.start_element("text")?;
+        layout::write_text_lines_position(&mut text, x, y, lines.len(), line_height)?;
+        text.finish()?;
+        layout::write_text_lines(self.out, x, y, lines, line_height)?;
+        self.out.end_element("text")
+    }
+
+    fn push_child(
+        &mut self,
+        child: &dyn Node,
         x: i64,
         y: i64,
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        child.render_with_geometry(self.out, x, y, h_dir, geo)
+        render_child(self.out, child, x, y, h_dir, geo)
+    }
+
+    fn push_element(&mut self, element: svg::Element) -> fmt::Result {
+        self.out.write_element(element)
+    }
+}
+
+/// Draw a child node using cached geometry, giving it the id assigned to it
+/// by [`Diagram::set_element_ids`], if any.
+fn draw_child<N: Node + ?Sized>(
+    child: &N,
+    x: i64,
+    y: i64,
+    h_dir: HDir,
+    geo: &NodeGeometry,
+) -> svg::Element {
+    let element = child.draw_with_geometry(x, y, h_dir, geo);
+    match &geo.id {
+        Some(id) => element.with_default_id(id.clone()),
+        None => element,
+    }
+}
+
+/// Stream a child node using cached geometry; the counterpart to `draw_child`.
+fn render_child<N: Node + ?Sized>(
+    out: &mut svg::Renderer<'_>,
+    child: &N,
+    x: i64,
+    y: i64,
+    h_dir: HDir,
+    geo: &NodeGeometry,
+) -> fmt::Result {
+    if let Some(id) = &geo.id {
+        out.set_pending_id(id.clone());
     }
+    child.render_with_geometry(out, x, y, h_dir, geo)
 }
 
 /// Build a debug-aware `<g>` element from a shared emit closure.
@@ -679,7 +1054,7 @@ impl RenderBackend for RendererBackend<'_, '_> {
 ///     "demo",
 ///     0,
 ///     0,
-///     &NodeGeometry { entry_height: 0, height: 0, width: 10, children: vec![] },
+///     &NodeGeometry { entry_height: 0, height: 0, width: 10, ..Default::default() },
 ///     |backend| backend.push_path(svg::PathData::new(HDir::LTR).move_to(0, 0).horizontal(10)),
 /// );
 /// assert!(group.to_string().contains("<path"));
@@ -712,7 +1087,7 @@ fn draw_group_with_geometry(
 ///     "demo",
 ///     0,
 ///     0,
-///     &NodeGeometry { entry_height: 0, height: 0, width: 10, children: vec![] },
+///     &NodeGeometry { entry_height: 0, height: 0, width: 10, ..Default::default() },
 ///     |backend| backend.push_path(svg::PathData::new(HDir::LTR).move_to(0, 0).horizontal(10)),
 /// ).unwrap();
 /// assert!(out.contains("<g"));
@@ -746,7 +1121,7 @@ fn render_group_with_geometry(
 ///     "Demo",
 ///     0,
 ///     0,
-///     &NodeGeometry { entry_height: 0, height: 0, width: 10, children: vec![] },
+///     &NodeGeometry { entry_height: 0, height: 0, width: 10, ..Default::default() },
 ///     |backend| backend.push_path(svg::PathData::new(HDir::LTR).move_to(0, 0).horizontal(10)),
 /// );
 /// assert!(group.to_string().contains("class=\"demo\""));
@@ -781,7 +1156,7 @@ fn draw_class_group_with_geometry(
 ///     "Demo",
 ///     0,
 ///     0,
-///     &NodeGeometry { entry_height: 0, height: 0, width: 10, children: vec![] },
+///     &NodeGeometry { entry_height: 0, height: 0, width: 10, ..Default::default() },
 ///     |backend| backend.push_path(svg::PathData::new(HDir::LTR).move_to(0, 0).horizontal(10)),
 /// ).unwrap();
 /// assert!(out.contains("class=\"demo\""));
@@ -810,7 +1185,7 @@ fn render_class_group_with_geometry(
 ///
 /// ```ignore
 /// # use railroad::{NodeGeometry, notactuallysvg as svg, HDir};
-/// let geo = NodeGeometry { entry_height: 10, height: 20, width: 20, children: vec![] };
+/// let geo = NodeGeometry { entry_height: 10, height: 20, width: 20, ..Default::default() };
 /// let path = draw_debug_path(
 ///     "Start",
 ///     0,
@@ -834,7 +1209,7 @@ fn draw_debug_path(
 ///
 /// ```ignore
 /// # use railroad::{NodeGeometry, notactuallysvg as svg, HDir};
-/// let geo = NodeGeometry { entry_height: 10, height: 20, width: 20, children: vec![] };
+/// let geo = NodeGeometry { entry_height: 10, height: 20, width: 20, ..Default::default() };
 /// let mut out = String::new();
 /// let mut renderer = svg::Renderer::new(&mut out);
 /// render_debug_path(
@@ -874,18 +1249,20 @@ fn render_debug_path(
 ///     &mut backend,
 ///     0,
 ///     0,
-///     &NodeGeometry { entry_height: 11, height: 22, width: 60, children: vec![] },
-///     "item",
+///     &NodeGeometry { entry_height: 11, height: 22, width: 60, ..Default::default() },
+///     &[vec![(&Span::new("item"), "item")]],
+///     16,
 ///     true,
 /// ).unwrap();
 /// assert_eq!(backend.children.len(), 2);
 /// ```
-fn emit_text_box<B: RenderBackend>(
-    backend: &mut B,
+fn emit_text_box(
+    backend: &mut dyn RenderBackend,
     x: i64,
     y: i64,
     geo: &NodeGeometry,
-    label: &str,
+    lines: &[rich_text::Line<'_>],
+    line_height: i64,
     rounded: bool,
 ) -> fmt::Result {
     if rounded {
@@ -893,7 +1270,12 @@ fn emit_text_box<B: RenderBackend>(
     } else {
         backend.push_rect(x, y, geo.width, geo.height)?;
     }
-    backend.push_text(x + geo.width / 2, y + geo.entry_height + 5, label)
+    backend.push_text_lines(
+        x + geo.width / 2,
+        y + geo.entry_height + 5,
+        lines,
+        line_height,
+    )
 }
 
 /// Convenience aggregation helpers for iterators and collections of [`Node`]s.
@@ -1027,6 +1409,19 @@ where
 #[derive(Debug, Clone, Default)]
 pub struct End;
 
+impl End {
+    /// The path drawing the symbol at `(x, y)`.
+    fn path(x: i64, y: i64, h_dir: HDir) -> svg::PathData {
+        svg::PathData::new(h_dir)
+            .move_to(x, y + 10)
+            .horizontal(20)
+            .move_rel(-10, -10)
+            .vertical(20)
+            .move_rel(10, -20)
+            .vertical(20)
+    }
+}
+
 impl Node for End {
     fn entry_height(&self) -> i64 {
         10
@@ -1044,13 +1439,7 @@ impl Node for End {
             x,
             y,
             &self.compute_geometry(),
-            svg::PathData::new(h_dir)
-                .move_to(x, y + 10)
-                .horizontal(20)
-                .move_rel(-10, -10)
-                .vertical(20)
-                .move_rel(10, -20)
-                .vertical(20),
+            Self::path(x, y, h_dir),
         )
     }
 
@@ -1062,20 +1451,22 @@ impl Node for End {
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        render_debug_path(
-            out,
-            "End",
-            x,
-            y,
-            geo,
-            svg::PathData::new(h_dir)
-                .move_to(x, y + 10)
-                .horizontal(20)
-                .move_rel(-10, -10)
-                .vertical(20)
-                .move_rel(10, -20)
-                .vertical(20),
-        )
+        render_debug_path(out, "End", x, y, geo, Self::path(x, y, h_dir))
+    }
+
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        _geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_path(Self::path(x, y, h_dir))
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("end")
     }
 }
 
@@ -1083,6 +1474,20 @@ impl Node for End {
 #[derive(Debug, Clone, Default)]
 pub struct SimpleStart;
 
+impl SimpleStart {
+    /// The path drawing the symbol at `(x, y)`.
+    fn path(x: i64, y: i64, h_dir: HDir) -> svg::PathData {
+        svg::PathData::new(h_dir)
+            .move_to(x, y + 5)
+            .arc(5, svg::Arc::SouthToEast)
+            .arc(5, svg::Arc::WestToSouth)
+            .arc(5, svg::Arc::NorthToWest)
+            .arc(5, svg::Arc::EastToNorth)
+            .move_rel(10, 0)
+            .horizontal(5)
+    }
+}
+
 impl Node for SimpleStart {
     fn entry_height(&self) -> i64 {
         5
@@ -1100,14 +1505,7 @@ impl Node for SimpleStart {
             x,
             y,
             &self.compute_geometry(),
-            svg::PathData::new(h_dir)
-                .move_to(x, y + 5)
-                .arc(5, svg::Arc::SouthToEast)
-                .arc(5, svg::Arc::WestToSouth)
-                .arc(5, svg::Arc::NorthToWest)
-                .arc(5, svg::Arc::EastToNorth)
-                .move_rel(10, 0)
-                .horizontal(5),
+            Self::path(x, y, h_dir),
         )
     }
 
@@ -1119,21 +1517,22 @@ impl Node for SimpleStart {
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        render_debug_path(
-            out,
-            "SimpleStart",
-            x,
-            y,
-            geo,
-            svg::PathData::new(h_dir)
-                .move_to(x, y + 5)
-                .arc(5, svg::Arc::SouthToEast)
-                .arc(5, svg::Arc::WestToSouth)
-                .arc(5, svg::Arc::NorthToWest)
-                .arc(5, svg::Arc::EastToNorth)
-                .move_rel(10, 0)
-                .horizontal(5),
-        )
+        render_debug_path(out, "SimpleStart", x, y, geo, Self::path(x, y, h_dir))
+    }
+
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        _geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_path(Self::path(x, y, h_dir))
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("simplestart")
     }
 }
 
@@ -1141,6 +1540,19 @@ impl Node for SimpleStart {
 #[derive(Debug, Clone, Default)]
 pub struct SimpleEnd;
 
+impl SimpleEnd {
+    /// The path drawing the symbol at `(x, y)`.
+    fn path(x: i64, y: i64, h_dir: HDir) -> svg::PathData {
+        svg::PathData::new(h_dir)
+            .move_to(x, y + 5)
+            .horizontal(5)
+            .arc(5, svg::Arc::SouthToEast)
+            .arc(5, svg::Arc::WestToSouth)
+            .arc(5, svg::Arc::NorthToWest)
+            .arc(5, svg::Arc::EastToNorth)
+    }
+}
+
 impl Node for SimpleEnd {
     fn entry_height(&self) -> i64 {
         5
@@ -1158,13 +1570,7 @@ impl Node for SimpleEnd {
             x,
             y,
             &self.compute_geometry(),
-            svg::PathData::new(h_dir)
-                .move_to(x, y + 5)
-                .horizontal(5)
-                .arc(5, svg::Arc::SouthToEast)
-                .arc(5, svg::Arc::WestToSouth)
-                .arc(5, svg::Arc::NorthToWest)
-                .arc(5, svg::Arc::EastToNorth),
+            Self::path(x, y, h_dir),
         )
     }
 
@@ -1176,20 +1582,22 @@ impl Node for SimpleEnd {
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        render_debug_path(
-            out,
-            "SimpleEnd",
-            x,
-            y,
-            geo,
-            svg::PathData::new(h_dir)
-                .move_to(x, y + 5)
-                .horizontal(5)
-                .arc(5, svg::Arc::SouthToEast)
-                .arc(5, svg::Arc::WestToSouth)
-                .arc(5, svg::Arc::NorthToWest)
-                .arc(5, svg::Arc::EastToNorth),
-        )
+        render_debug_path(out, "SimpleEnd", x, y, geo, Self::path(x, y, h_dir))
+    }
+
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        _geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_path(Self::path(x, y, h_dir))
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("simpleend")
     }
 }
 
@@ -1197,6 +1605,19 @@ impl Node for SimpleEnd {
 #[derive(Debug, Clone, Default)]
 pub struct Start;
 
+impl Start {
+    /// The path drawing the symbol at `(x, y)`.
+    fn path(x: i64, y: i64, h_dir: HDir) -> svg::PathData {
+        svg::PathData::new(h_dir)
+            .move_to(x, y)
+            .vertical(20)
+            .move_rel(10, -20)
+            .vertical(20)
+            .move_rel(-10, -10)
+            .horizontal(20)
+    }
+}
+
 impl Node for Start {
     fn entry_height(&self) -> i64 {
         10
@@ -1214,13 +1635,7 @@ impl Node for Start {
             x,
             y,
             &self.compute_geometry(),
-            svg::PathData::new(h_dir)
-                .move_to(x, y)
-                .vertical(20)
-                .move_rel(10, -20)
-                .vertical(20)
-                .move_rel(-10, -10)
-                .horizontal(20),
+            Self::path(x, y, h_dir),
         )
     }
 
@@ -1232,20 +1647,22 @@ impl Node for Start {
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        render_debug_path(
-            out,
-            "Start",
-            x,
-            y,
-            geo,
-            svg::PathData::new(h_dir)
-                .move_to(x, y)
-                .vertical(20)
-                .move_rel(10, -20)
-                .vertical(20)
-                .move_rel(-10, -10)
-                .horizontal(20),
-        )
+        render_debug_path(out, "Start", x, y, geo, Self::path(x, y, h_dir))
+    }
+
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        _geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_path(Self::path(x, y, h_dir))
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("start")
     }
 }
 
@@ -1320,6 +1737,24 @@ impl Node for Debug {
         rect.finish_empty()?;
         write_debug_overlay(out, x, y, geo)
     }
+
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        _h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_rect(x, y, geo.width, geo.height)
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("debug")
+            .with_property("entry_height", self.entry_height.to_string())
+            .with_property("height", self.height.to_string())
+            .with_property("width", self.width.to_string())
+    }
 }
 
 /// A dummy-element which has no size and draws nothing.
@@ -1360,6 +1795,21 @@ impl Node for Empty {
         write_debug_overlay(out, x, y, geo)?;
         out.end_element("g")
     }
+
+    fn emit_with_geometry(
+        &self,
+        _backend: &mut dyn RenderBackend,
+        _x: i64,
+        _y: i64,
+        _h_dir: HDir,
+        _geo: &NodeGeometry,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("empty")
+    }
 }
 
 /// The top-level container that renders a node tree as a complete SVG document.
@@ -1392,6 +1842,12 @@ pub struct Diagram<N> {
     right_padding: i64,
     top_padding: i64,
     bottom_padding: i64,
+    embed_description: bool,
+    orientation: Orientation,
+    mirrored: bool,
+    title: Option<String>,
+    description: Option<String>,
+    element_ids: Option<String>,
 }
 
 impl<N: Node> Diagram<N> {
@@ -1417,6 +1873,12 @@ impl<N: Node> Diagram<N> {
             right_padding: 10,
             top_padding: 10,
             bottom_padding: 10,
+            embed_description: false,
+            orientation: Orientation::default(),
+            mirrored: false,
+            title: None,
+            description: None,
+            element_ids: None,
         }
     }
 
@@ -1476,6 +1938,367 @@ impl<N: Node> Diagram<N> {
         self
     }
 
+    /// Embed a machine-readable description of the node tree into the SVG.
+    ///
+    /// The [`Description`] is written as JSON into a `<metadata>`-element, from
+    /// which [`import::svg::import`] can recover it. The element carries no
+    /// attributes, so the SVG stays valid SVG 1.1. This is off by default.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut dia = Diagram::new(Terminal::new("Foobar".to_owned()));
+    /// dia.set_embed_description(true);
+    /// let desc = import::svg::import(&dia.to_string()).unwrap();
+    /// assert_eq!(desc.property("label"), Some("Foobar"));
+    /// ```
+    pub fn set_embed_description(&mut self, embed: bool) -> &mut Self {
+        self.embed_description = embed;
+        self
+    }
+
+    /// Set the direction in which the diagram flows.
+    ///
+    /// In a [`Orientation::Vertical`] diagram, every primitive is laid out
+    /// rotated by 90°: sequences run downwards, alternatives are placed side
+    /// by side and loops return on the side. Text is kept upright.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut dia = Diagram::new(Terminal::new("Foobar".to_owned()));
+    /// dia.set_orientation(Orientation::Vertical);
+    /// assert!(dia.to_string().contains("transform=\"matrix(0 1 1 0 10 10)\""));
+    /// ```
+    pub fn set_orientation(&mut self, orientation: Orientation) -> &mut Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Mirror the diagram, so it reads right-to-left.
+    ///
+    /// Every primitive is laid out mirrored: the diagram starts on the right,
+    /// sequences run leftwards and loops return on the right. Text is not
+    /// mirrored.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut dia = Diagram::new(Terminal::new("Foobar".to_owned()));
+    /// dia.set_mirrored(true);
+    /// assert!(dia.to_string().contains("transform=\"matrix(-1 0 0 1 78 10)\""));
+    /// ```
+    pub fn set_mirrored(&mut self, mirrored: bool) -> &mut Self {
+        self.mirrored = mirrored;
+        self
+    }
+
+    /// Set a short title, which makes the diagram accessible to screen readers.
+    ///
+    /// If a title or a description is set, the `<svg>`-tag gets `role="img"`
+    /// and is labelled via `aria-labelledby` by a `<title>`- and a
+    /// `<desc>`-element. Their ids are derived from the `id`-attribute of the
+    /// `<svg>`-tag (see [`Diagram::attr`]), or `railroad` if it has none; set
+    /// one to keep them unique if several diagrams are inlined into a page.
+    ///
+    /// `role` and `aria-labelledby` are defined by SVG 2 and WAI-ARIA, not by
+    /// SVG 1.1; a labelled diagram does not validate against the SVG 1.1 DTD.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut dia = Diagram::new(Terminal::new("Foobar".to_owned()));
+    /// dia.set_title(Some("The Foobar keyword".to_owned()));
+    /// let svg = dia.to_string();
+    /// assert!(svg.contains("role=\"img\""));
+    /// assert!(svg.contains("aria-labelledby=\"railroad-title\""));
+    /// assert!(svg.contains("<title id=\"railroad-title\">\nThe Foobar keyword</title>"));
+    /// ```
+    pub fn set_title(&mut self, title: Option<String>) -> &mut Self {
+        self.title = title;
+        self
+    }
+
+    /// The title of the diagram, if any.
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Set a longer description of the diagram for screen readers; see
+    /// [`Diagram::set_title`].
+    ///
+    /// A textual alternative to the diagram itself can be generated via
+    /// [`Diagram::text_alternative`].
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut dia = Diagram::new(Sequence::new(vec![
+    ///     Box::new(Terminal::new("SELECT".to_owned())) as Box<dyn Node>,
+    ///     Box::new(Optional::new(Terminal::new("DISTINCT".to_owned()))),
+    /// ]));
+    /// dia.set_description(Some(dia.text_alternative()));
+    /// assert!(dia.to_string().contains("<desc id=\"railroad-desc\">\n&quot;SELECT&quot; &quot;DISTINCT&quot;?</desc>"));
+    /// ```
+    pub fn set_description(&mut self, description: Option<String>) -> &mut Self {
+        self.description = description;
+        self
+    }
+
+    /// The description of the diagram for screen readers, if any.
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// A linear, EBNF-like rendering of the grammar the diagram depicts.
+    ///
+    /// See [`grammar::Expr::from_description`] for how primitives are
+    /// expressed.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let dia = Diagram::new(Sequence::new(vec![
+    ///     Box::new(Start) as Box<dyn Node>,
+    ///     Box::new(Repeat::new(
+    ///         NonTerminal::new("column".to_owned()),
+    ///         Terminal::new(",".to_owned()),
+    ///     )),
+    ///     Box::new(End),
+    /// ]));
+    /// assert_eq!(dia.text_alternative(), "column (\",\" column)*");
+    /// ```
+    #[must_use]
+    pub fn text_alternative(&self) -> String {
+        grammar::Expr::from_description(&self.root.describe()).to_string()
+    }
+
+    /// Give every node an `id`-attribute derived from its position in the tree.
+    ///
+    /// The root gets `prefix` as its id. The id of every other node is the id of
+    /// its parent, followed by the parent's kind (see [`Description::kind`]) and
+    /// the node's index among the parent's children, e.g. `rule.sequence.2` for
+    /// the third element of a root `Sequence` and `rule.sequence.2.choice.1` for
+    /// the second alternative of a `Choice` in its place. The alternatives of a
+    /// [`MultiChoice`] are indexed by column and row, e.g.
+    /// `rule.multichoice.1.0`. The id is set on the node's outermost element,
+    /// usually a `<g>`, so parts of a diagram can be linked to as
+    /// `diagram.svg#rule.sequence.2`. Nodes which have an explicit `id`
+    /// via their `attr()` keep it.
+    ///
+    /// Ids must be unique, so the diagram is checked for collisions once
+    /// the ids are assigned, see [`Diagram::element_ids`]. Attributes and
+    /// elements added afterwards are not covered by this check.
+    ///
+    /// # Errors
+    /// If the diagram would use an id more than once, e.g. because a node's
+    /// explicit id equals the one assigned to another node. The prefix is
+    /// not changed in that case.
+    ///
+    /// Ids are only assigned to nodes which describe their children (see
+    /// [`Node::describe`]) and draw them using the geometry they computed.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut dia = Diagram::new(Sequence::new(vec![
+    ///     Box::new(Terminal::new("SELECT".to_owned())) as Box<dyn Node>,
+    ///     Box::new(Choice::new(vec![
+    ///         Box::new(Terminal::new("*".to_owned())) as Box<dyn Node>,
+    ///         Box::new(NonTerminal::new("columns".to_owned())),
+    ///     ])),
+    /// ]));
+    /// dia.set_element_ids(Some("select".to_owned())).unwrap();
+    /// assert!(dia.to_string().contains("id=\"select.sequence.1.choice.1\""));
+    ///
+    /// let mut term = Terminal::new("x".to_owned());
+    /// term.attr("id".to_owned()).or_insert("r.sequence.1".to_owned());
+    /// let mut dia = Diagram::new(Sequence::new(vec![
+    ///     Box::new(term) as Box<dyn Node>,
+    ///     Box::new(Terminal::new("y".to_owned())),
+    /// ]));
+    /// assert_eq!(dia.set_element_ids(Some("r".to_owned())).unwrap_err().id, "r.sequence.1");
+    /// assert!(!dia.to_string().contains("id=\"r\""));
+    /// ```
+    pub fn set_element_ids(&mut self, prefix: Option<String>) -> Result<&mut Self, DuplicateId> {
+        let previous = std::mem::replace(&mut self.element_ids, prefix);
+        if self.element_ids.is_some()
+            && let Err(e) = self.element_ids()
+        {
+            self.element_ids = previous;
+            return Err(e);
+        }
+        Ok(self)
+    }
+
+    /// All `id`-attributes in the diagram's SVG, in document order.
+    ///
+    /// These are the ids set via [`Diagram::set_element_ids`], explicit ids of
+    /// nodes and ids of additional elements, such as the ones set via
+    /// [`Diagram::set_title`].
+    ///
+    /// # Errors
+    /// If an id is used more than once.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut term = Terminal::new("x".to_owned());
+    /// term.attr("id".to_owned()).or_insert("x".to_owned());
+    /// let mut dia = Diagram::new(Sequence::new(vec![
+    ///     Box::new(term.clone()) as Box<dyn Node>,
+    ///     Box::new(term),
+    /// ]));
+    /// assert_eq!(dia.element_ids().unwrap_err().id, "x");
+    /// ```
+    pub fn element_ids(&self) -> Result<Vec<String>, DuplicateId> {
+        let svg = self.draw(0, 0, HDir::LTR);
+        let ids = svg.ids();
+        match first_duplicate(&ids) {
+            Some(id) => Err(DuplicateId { id: id.to_owned() }),
+            None => Ok(ids.into_iter().map(str::to_owned).collect()),
+        }
+    }
+
+    /// The absolute position of every node in the diagram and what it draws,
+    /// for drawing the diagram without SVG; see [`placement`].
+    ///
+    /// The result is a node of kind `"diagram"`, labelled with the diagram's
+    /// title and covering its full size, whose only child is the root node.
+    /// Positions are on screen, with the orientation and mirroring applied.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let dia = Diagram::new(Terminal::new("x".to_owned()));
+    /// let layout = dia.layout();
+    /// assert_eq!((layout.width, layout.height), (dia.width(), dia.height()));
+    /// assert_eq!(layout.children[0].kind, "terminal");
+    /// ```
+    #[must_use]
+    pub fn layout(&self) -> placement::PlacedNode {
+        let geo = self.compute_geometry();
+        self.place_with_geometry(0, 0, HDir::LTR, &geo)
+    }
+
+    /// Draw this diagram as a TikZ picture, for LaTeX documents; see [`tikz`].
+    ///
+    /// The picture shows the diagram as the SVG output does, including its
+    /// canvas, orientation and mirroring.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let dia = Diagram::new(NonTerminal::new("expr".to_owned()));
+    /// let picture = dia.to_tikz();
+    /// assert!(picture.contains(r"\node[railroad/text, railroad/nonterminal text/.try]"));
+    /// assert!(picture.contains("{expr};"));
+    /// ```
+    #[must_use]
+    pub fn to_tikz(&self) -> String {
+        let geo = self.compute_geometry();
+        let mut backend = tikz::TikzBackend::default();
+        let result = backend.push_canvas(geo.width, geo.height).and_then(|()| {
+            match self.root_transform(0, 0, &geo.children[0]) {
+                None => backend.push_child(
+                    &self.root,
+                    self.left_padding,
+                    self.top_padding,
+                    HDir::LTR,
+                    &geo.children[0],
+                ),
+                Some(transform) => {
+                    backend.set_transform(transform);
+                    backend.push_child(&self.root, 0, 0, HDir::LTR, &geo.children[0])
+                }
+            }
+        });
+        result.expect("tikz backend is infallible");
+        backend.into_picture()
+    }
+
+    /// The [`SourceSpan`]s of all nodes in the diagram that carry one, in
+    /// document order, each with the id of the element it is written to.
+    ///
+    /// Together with [`Diagram::set_element_ids`], this lets editors map between
+    /// the elements of the diagram and the text it was imported from.
+    ///
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let src = "a b";
+    /// let mut a = Terminal::new("a".to_owned());
+    /// a.set_source_span(Some(SourceSpan::locate(src, 0..1)));
+    /// let mut b = Terminal::new("b".to_owned());
+    /// b.set_source_span(Some(SourceSpan::locate(src, 2..3)));
+    /// let mut dia = Diagram::new(Sequence::new(vec![
+    ///     Box::new(a) as Box<dyn Node>,
+    ///     Box::new(b),
+    /// ]));
+    /// dia.set_element_ids(Some("r".to_owned())).unwrap();
+    /// let spans = dia.source_spans();
+    /// assert_eq!(spans[1].0.as_deref(), Some("r.sequence.1"));
+    /// assert_eq!(spans[1].1.bytes, 2..3);
+    /// ```
+    #[must_use]
+    pub fn source_spans(&self) -> Vec<(Option<String>, SourceSpan)> {
+        self.draw(0, 0, HDir::LTR)
+            .source_spans()
+            .into_iter()
+            .map(|(id, span)| (id.map(str::to_owned), span))
+            .collect()
+    }
+
+    /// The ids of the `<title>`- and `<desc>`-elements, if they are written.
+    fn accessible_ids(&self) -> (Option<String>, Option<String>) {
+        let prefix = self
+            .extra_attributes
+            .get("id")
+            .map_or("railroad", String::as_str);
+        (
+            self.title.as_ref().map(|_| format!("{prefix}-title")),
+            self.description.as_ref().map(|_| format!("{prefix}-desc")),
+        )
+    }
+
+    /// The value of the `aria-labelledby`-attribute, if the diagram is labelled.
+    fn labelled_by(ids: &(Option<String>, Option<String>)) -> Option<String> {
+        match ids {
+            (None, None) => None,
+            (Some(id), None) | (None, Some(id)) => Some(id.clone()),
+            (Some(title), Some(desc)) => Some(format!("{title} {desc}")),
+        }
+    }
+
+    /// The transform mapping the root's layout onto the canvas, with the root
+    /// drawn at the origin; `None` if the root is drawn as laid out.
+    fn root_transform(&self, x: i64, y: i64, root_geo: &NodeGeometry) -> Option<layout::Transform> {
+        let transform = match (self.orientation, self.mirrored) {
+            (Orientation::Horizontal, false) => return None,
+            (Orientation::Horizontal, true) => layout::Transform::IDENTITY,
+            (Orientation::Vertical, _) => layout::Transform::transpose(0, 0),
+        };
+        let transform = if self.mirrored {
+            transform.then(layout::Transform::mirror(self.root_size(root_geo).0))
+        } else {
+            transform
+        };
+        Some(transform.then(layout::Transform::translate(
+            x + self.left_padding,
+            y + self.top_padding,
+        )))
+    }
+
+    /// The on-screen `(width, height)` of the root.
+    fn root_size(&self, root_geo: &NodeGeometry) -> (i64, i64) {
+        match self.orientation {
+            Orientation::Horizontal => (root_geo.width, root_geo.height),
+            Orientation::Vertical => (root_geo.height, root_geo.width),
+        }
+    }
+
     /// Write this diagram's SVG-code to the given writer.
     ///
     /// # Errors
@@ -1490,6 +2313,28 @@ impl<N: Node> Diagram<N> {
     }
 }
 
+/// An `id`-attribute used by more than one element of a diagram, see
+/// [`Diagram::element_ids`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateId {
+    /// The id used more than once.
+    pub id: String,
+}
+
+impl fmt::Display for DuplicateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the id `{}` is used more than once", self.id)
+    }
+}
+
+impl std::error::Error for DuplicateId {}
+
+/// The first id in `ids` which is used before.
+fn first_duplicate<'a>(ids: &[&'a str]) -> Option<&'a str> {
+    let mut seen = collections::HashSet::new();
+    ids.iter().copied().find(|id| !seen.insert(*id))
+}
+
 impl<N> Default for Diagram<N>
 where
     N: Default,
@@ -1503,6 +2348,12 @@ where
             right_padding: 10,
             top_padding: 10,
             bottom_padding: 10,
+            embed_description: false,
+            orientation: Orientation::default(),
+            mirrored: false,
+            title: None,
+            description: None,
+            element_ids: None,
         }
     }
 }
@@ -1516,11 +2367,11 @@ where
     }
 
     fn height(&self) -> i64 {
-        self.top_padding + self.root.height() + self.bottom_padding
+        self.compute_geometry().height
     }
 
     fn width(&self) -> i64 {
-        self.left_padding + self.root.width() + self.right_padding
+        self.compute_geometry().width
     }
 
     fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
@@ -1529,18 +2380,39 @@ where
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let root_geo = self.root.compute_geometry();
-        let height = self.top_padding + root_geo.height + self.bottom_padding;
-        let width = self.left_padding + root_geo.width + self.right_padding;
+        let mut root_geo = self.root.compute_geometry_in(self.orientation);
+        if let Some(prefix) = &self.element_ids {
+            ids::assign(&mut root_geo, prefix.clone(), &self.root.describe());
+        }
+        let (root_width, root_height) = self.root_size(&root_geo);
+        let height = self.top_padding + root_height + self.bottom_padding;
+        let width = self.left_padding + root_width + self.right_padding;
         NodeGeometry {
             entry_height: 0,
             height,
             width,
             children: vec![root_geo],
+            id: None,
+            orientation: Orientation::Horizontal,
         }
     }
 
     fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        let root = match self.root_transform(x, y, &geo.children[0]) {
+            None => draw_child(
+                &self.root,
+                x + self.left_padding,
+                y + self.top_padding,
+                h_dir,
+                &geo.children[0],
+            ),
+            Some(transform) => {
+                let _text = layout::enter_transform(transform);
+                svg::Element::new("g")
+                    .set("transform", &transform)
+                    .add(draw_child(&self.root, 0, 0, h_dir, &geo.children[0]))
+            }
+        };
         let mut e = svg::Element::new("svg")
             .set("xmlns", "http://www.w3.org/2000/svg")
             .set("xmlns:xlink", "http://www.w3.org/1999/xlink")
@@ -1554,21 +2426,29 @@ where
         for (k, v) in &self.extra_attributes {
             e = e.set(&k, &v);
         }
+        let ids = self.accessible_ids();
+        if let Some(labelled_by) = Self::labelled_by(&ids) {
+            e = e.set("role", "img").set("aria-labelledby", &labelled_by);
+        }
+        if let (Some(id), Some(title)) = (&ids.0, &self.title) {
+            e = e.add(svg::Element::new("title").set("id", id).text(title));
+        }
+        if let (Some(id), Some(description)) = (&ids.1, &self.description) {
+            e = e.add(svg::Element::new("desc").set("id", id).text(description));
+        }
         for extra_ele in self.extra_elements.iter().cloned() {
             e = e.add(extra_ele);
         }
+        if self.embed_description {
+            e = e.add(svg::Element::new("metadata").text(&self.root.describe().to_json()));
+        }
         e.add(
             svg::Element::new("rect")
                 .set("width", "100%")
                 .set("height", "100%")
                 .set("class", "railroad_canvas"),
         )
-        .add(self.root.draw_with_geometry(
-            x + self.left_padding,
-            y + self.top_padding,
-            h_dir,
-            &geo.children[0],
-        ))
+        .add(root)
     }
 
     fn render_with_geometry(
@@ -1587,11 +2467,26 @@ where
         #[cfg(feature = "visual-debug")]
         svg_tag.attr("xmlns:railroad", "http://www.github.com/lukaslueg/railroad")?;
         svg_tag.attr_hashmap(&self.extra_attributes)?;
+        let ids = self.accessible_ids();
+        if let Some(labelled_by) = Self::labelled_by(&ids) {
+            svg_tag.attr("role", "img")?;
+            svg_tag.attr("aria-labelledby", labelled_by)?;
+        }
         svg_tag.finish()?;
 
+        if let (Some(id), Some(title)) = (&ids.0, &self.title) {
+            out.text_element("title", title, |tag| tag.attr("id", id))?;
+        }
+        if let (Some(id), Some(description)) = (&ids.1, &self.description) {
+            out.text_element("desc", description, |tag| tag.attr("id", id))?;
+        }
+
         for extra in &self.extra_elements {
             out.write_display(extra)?;
         }
+        if self.embed_description {
+            out.text_element("metadata", &self.root.describe().to_json(), |_| Ok(()))?;
+        }
 
         let mut rect = out.start_element("rect")?;
         rect.attr("width", "100%")?;
@@ -1599,15 +2494,90 @@ where
         rect.attr("class", "railroad_canvas")?;
         rect.finish_empty()?;
 
-        self.root.render_with_geometry(
-            out,
-            x + self.left_padding,
-            y + self.top_padding,
-            h_dir,
-            &geo.children[0],
-        )?;
+        match self.root_transform(x, y, &geo.children[0]) {
+            None => render_child(
+                out,
+                &self.root,
+                x + self.left_padding,
+                y + self.top_padding,
+                h_dir,
+                &geo.children[0],
+            )?,
+            Some(transform) => {
+                let _text = layout::enter_transform(transform);
+                let mut g = out.start_element("g")?;
+                g.attr("transform", transform)?;
+                g.finish()?;
+                render_child(out, &self.root, 0, 0, h_dir, &geo.children[0])?;
+                out.end_element("g")?;
+            }
+        }
         out.end_element("svg")
     }
+
+    /// Emit the root node at its padded position, with the diagram's
+    /// orientation and mirroring applied to everything it draws.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        match self.root_transform(x, y, &geo.children[0]) {
+            None => backend.push_child(
+                &self.root,
+                x + self.left_padding,
+                y + self.top_padding,
+                h_dir,
+                &geo.children[0],
+            ),
+            Some(transform) => layout::TransformedBackend::new(backend, transform).push_child(
+                &self.root,
+                0,
+                0,
+                h_dir,
+                &geo.children[0],
+            ),
+        }
+    }
+
+    fn place_with_geometry(
+        &self,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> placement::PlacedNode {
+        let root = match self.root_transform(x, y, &geo.children[0]) {
+            None => self.root.place_with_geometry(
+                x + self.left_padding,
+                y + self.top_padding,
+                h_dir,
+                &geo.children[0],
+            ),
+            Some(transform) => self
+                .root
+                .place_with_geometry(0, 0, h_dir, &geo.children[0])
+                .transform(transform),
+        };
+        placement::PlacedNode {
+            kind: "diagram".to_owned(),
+            label: self.title.clone(),
+            x,
+            y,
+            width: geo.width,
+            height: geo.height,
+            entry_height: geo.entry_height,
+            primitives: Vec::new(),
+            children: vec![root],
+        }
+    }
+
+    fn describe(&self) -> Description {
+        self.root.describe()
+    }
 }
 
 impl<N> fmt::Display for Diagram<N>
@@ -1759,6 +2729,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn text_lines_break_and_wrap() {
+        assert_eq!(text_lines("a b", None), ["a b"]);
+        assert_eq!(text_lines("a\n\nb c", None), ["a", "", "b c"]);
+        assert_eq!(
+            text_lines("  one two  three\nfour", Some(7)),
+            ["one two", "three", "four"]
+        );
+        assert_eq!(
+            text_lines("a verylongword b", Some(3)),
+            ["a", "verylongword", "b"]
+        );
+        assert_eq!(text_lines("", Some(3)), [""]);
+    }
+
     /// Helper: build a nested Sequence tree of the given depth and width.
     fn make_deep_seq(depth: usize, width: usize) -> Box<dyn Node> {
         if depth == 0 {
@@ -1839,6 +2824,66 @@ mod tests {
         }
     }
 
+    /// The text and classes of rich text spans end up in `<tspan>`s.
+    #[test]
+    fn rich_text_no_injection() {
+        for payload in PAYLOADS {
+            let mut span = Span::bold(*payload);
+            span.class = Some(payload.to_string());
+            let text = RichText::new(vec![Span::new(*payload), span]);
+            let svg = format!("{}", Diagram::new(Terminal::with_rich_label(text.clone())));
+            assert_no_payload(&svg, payload);
+            let svg = format!("{}", Diagram::new(Comment::with_rich_text(text)));
+            assert_no_payload(&svg, payload);
+        }
+    }
+
+    /// A diagram's title and description end up in `<title>` and `<desc>`
+    /// elements, their ids in attributes.
+    #[test]
+    fn diagram_title_no_injection() {
+        for payload in PAYLOADS {
+            let mut dia = Diagram::new(Empty);
+            dia.set_title(Some(payload.to_string()))
+                .set_description(Some(payload.to_string()));
+            dia.attr("id".to_owned()).or_insert(payload.to_string());
+            assert_no_payload(&dia.to_string(), payload);
+        }
+    }
+
+    /// Tooltips end up in `<title>` and `<desc>` elements.
+    #[test]
+    fn tooltip_no_injection() {
+        for payload in PAYLOADS {
+            let mut node = Tooltip::new(Empty, payload.to_string());
+            node.set_description(Some(payload.to_string()));
+            let svg = format!("{}", Diagram::new(node));
+            assert_no_payload(&svg, payload);
+        }
+    }
+
+    /// The prefix of element ids ends up in id attributes.
+    #[test]
+    fn element_ids_no_injection() {
+        for payload in PAYLOADS {
+            let mut dia = Diagram::new(Optional::new(Empty));
+            dia.set_element_ids(Some(payload.to_string())).unwrap();
+            assert_no_payload(&dia.to_string(), payload);
+        }
+    }
+
+    /// The file of a source span ends up in a data-attribute.
+    #[test]
+    fn source_span_no_injection() {
+        for payload in PAYLOADS {
+            let mut span = SourceSpan::locate("x", 0..1);
+            span.file = Some(payload.to_string());
+            let mut nt = NonTerminal::new("x".to_owned());
+            nt.set_source_span(Some(span));
+            assert_no_payload(&Diagram::new(nt).to_string(), payload);
+        }
+    }
+
     /// Link URIs end up in an xlink:href attribute.
     #[test]
     fn link_uri_no_injection() {
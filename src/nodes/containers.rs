@@ -5,10 +5,77 @@ use std::{
 };
 
 use crate::{
-    ARC_RADIUS, HDir, Node, NodeGeometry, RenderBackend, draw_class_group_with_geometry,
-    draw_group_with_geometry, render_class_group_with_geometry, render_group_with_geometry, svg,
+    ARC_RADIUS, HDir, Node, NodeGeometry, Orientation, RenderBackend, description::Description,
+    draw_class_group_with_geometry, draw_group_with_geometry, layout,
+    render_class_group_with_geometry, render_group_with_geometry, svg, text_width,
 };
 
+/// How nodes narrower than their container are placed within it.
+///
+/// Used by [`Choice`] for its alternatives, by [`MultiChoice`] for the
+/// alternatives in each column and by [`Stack`] for its rows. The connecting
+/// path is extended to fill the remaining space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    /// Place nodes at the left edge.
+    #[default]
+    Left,
+    /// Place nodes in the middle.
+    Center,
+    /// Place nodes at the right edge.
+    Right,
+    /// Place nodes in the middle, extending the path on both sides to the
+    /// full width.
+    ///
+    /// In a `Choice` or `MultiChoice`, where every alternative's path runs
+    /// across the full width anyway, this is the same as `Center`. In a
+    /// `Stack`, each row's path runs all the way to the right edge before
+    /// turning down to the next row.
+    Justified,
+}
+
+impl Alignment {
+    /// The offset of a node from the left edge, given the space it leaves free.
+    fn offset(self, slack: i64) -> i64 {
+        match self {
+            Self::Left => 0,
+            Self::Center | Self::Justified => slack / 2,
+            Self::Right => slack,
+        }
+    }
+
+    /// The name used in a [`Description`].
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Center => "center",
+            Self::Right => "right",
+            Self::Justified => "justified",
+        }
+    }
+
+    /// The inverse of [`Alignment::name`].
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "left" => Self::Left,
+            "center" => Self::Center,
+            "right" => Self::Right,
+            "justified" => Self::Justified,
+            _ => return None,
+        })
+    }
+
+    /// Add this alignment as the `alignment`-property of `desc`, unless it is
+    /// the default.
+    fn describe(self, desc: Description) -> Description {
+        if self == Self::default() {
+            desc
+        } else {
+            desc.with_property("alignment", self.name())
+        }
+    }
+}
+
 /// A horizontal group of elements, connected from left to right.
 ///
 /// Also see `Stack` for a vertical group of elements.
@@ -61,42 +128,6 @@ impl<N> Sequence<N> {
     pub fn into_inner(self) -> Vec<N> {
         self.children
     }
-
-    /// Emit sequence children and their connecting segments in a single shared pass.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        h_dir: HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result
-    where
-        N: Node,
-    {
-        let mut running_x = 0;
-        for (child, child_geo) in self.children.iter().zip(geo.children.iter()) {
-            backend.push_child(
-                child,
-                x + running_x,
-                y + geo.entry_height - child_geo.entry_height,
-                h_dir,
-                child_geo,
-            )?;
-            running_x += child_geo.width + self.spacing;
-        }
-
-        let mut running_x = x;
-        for child_geo in geo.children.iter().rev().skip(1).rev() {
-            backend.push_path(
-                svg::PathData::new(h_dir)
-                    .move_to(running_x + child_geo.width, y + geo.entry_height)
-                    .horizontal(self.spacing),
-            )?;
-            running_x += child_geo.width + self.spacing;
-        }
-        Ok(())
-    }
 }
 
 impl<N> Default for Sequence<N> {
@@ -176,8 +207,15 @@ where
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let children: Vec<NodeGeometry> =
-            self.children.iter().map(|c| c.compute_geometry()).collect();
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .children
+            .iter()
+            .map(|c| c.compute_geometry_in(orientation))
+            .collect();
         let entry_height = children.iter().map(|g| g.entry_height).max().unwrap_or(0);
         let height_below = children
             .iter()
@@ -196,6 +234,8 @@ where
             height: entry_height + height_below,
             width,
             children,
+            id: None,
+            orientation,
         }
     }
 
@@ -217,6 +257,43 @@ where
             self.emit_with_geometry(backend, x, y, h_dir, geo)
         })
     }
+
+    /// Emit sequence children and their connecting segments in a single shared pass.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let mut running_x = 0;
+        for (child, child_geo) in self.children.iter().zip(geo.children.iter()) {
+            backend.push_child(
+                child,
+                x + running_x,
+                y + geo.entry_height - child_geo.entry_height,
+                h_dir,
+                child_geo,
+            )?;
+            running_x += child_geo.width + self.spacing;
+        }
+
+        let mut running_x = x;
+        for child_geo in geo.children.iter().rev().skip(1).rev() {
+            backend.push_path(
+                svg::PathData::new(h_dir)
+                    .move_to(running_x + child_geo.width, y + geo.entry_height)
+                    .horizontal(self.spacing),
+            )?;
+            running_x += child_geo.width + self.spacing;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("sequence").with_children(self.children.iter().map(Node::describe))
+    }
 }
 
 /// A vertical group of elements, drawn from top to bottom.
@@ -228,6 +305,7 @@ pub struct Stack<N> {
     left_padding: i64,
     right_padding: i64,
     spacing: i64,
+    alignment: Alignment,
     attributes: HashMap<String, String>,
 }
 
@@ -268,91 +346,63 @@ impl<N> Stack<N> {
         self.children
     }
 
+    /// Set how rows narrower than the widest row are placed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut stack = Stack::new(vec![
+    ///     Terminal::new("a long line".to_owned()),
+    ///     Terminal::new("short".to_owned()),
+    /// ]);
+    /// stack.set_alignment(Alignment::Right);
+    /// assert!(Diagram::new(stack).to_string().starts_with("<svg"));
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+
     /// Access an attribute on the main SVG-element that will be drawn.
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
     }
 
-    /// Emit the stack connectors and children once for both render backends.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        h_dir: HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result
-    where
-        N: Node,
-    {
-        let left_p = self.left_padding();
-        backend.push_path(
-            svg::PathData::new(h_dir)
-                .move_to(x, y + geo.entry_height)
-                .horizontal(left_p),
-        )?;
-
-        let mut running_y = y;
-        let n = self.children.len();
-        for i in 0..n.saturating_sub(1) {
-            let child = &self.children[i];
-            let child_geo = &geo.children[i];
-            let next_geo = &geo.children[i + 1];
-            backend.push_path(
-                svg::PathData::new(h_dir)
-                    .move_to(
-                        x + left_p + child_geo.width,
-                        running_y + child_geo.entry_height,
-                    )
-                    .arc(ARC_RADIUS, svg::Arc::WestToSouth)
-                    .vertical(cmp::max(
-                        0,
-                        child_geo.height_below_entry() + self.spacing - ARC_RADIUS * 2,
-                    ))
-                    .arc(ARC_RADIUS, svg::Arc::NorthToWest)
-                    .horizontal(-child_geo.width)
-                    .arc(ARC_RADIUS, svg::Arc::EastToSouth)
-                    .vertical(cmp::max(0, next_geo.entry_height - ARC_RADIUS))
-                    .vertical(cmp::max(
-                        0,
-                        (self.spacing - ARC_RADIUS * 2) / 2 + (self.spacing - ARC_RADIUS * 2) % 2,
-                    ))
-                    .arc(ARC_RADIUS, svg::Arc::NorthToEast),
-            )?;
-            backend.push_child(child, x + left_p, running_y, h_dir, child_geo)?;
-            let ph = child_geo.entry_height
-                + cmp::max(
-                    child_geo.height_below_entry() + self.spacing,
-                    ARC_RADIUS * 2,
-                )
-                + ARC_RADIUS
-                + cmp::max(0, ARC_RADIUS - next_geo.entry_height);
-            running_y += ph;
-        }
+    /// The offset of each row from the left padding, and how far its path
+    /// extends to the right before turning down to the next row.
+    fn row_extents(&self, widths: &[i64]) -> Vec<(i64, i64)> {
+        let max_width = widths.iter().copied().max().unwrap_or(0);
+        widths
+            .iter()
+            .enumerate()
+            .map(|(i, &width)| {
+                let offset = self.alignment.offset(max_width - width);
+                if self.alignment == Alignment::Justified && i + 1 < widths.len() {
+                    (offset, max_width)
+                } else {
+                    (offset, offset + width)
+                }
+            })
+            .collect()
+    }
 
-        if let Some(last_child) = self.children.last() {
-            let last_geo = geo.children.last().unwrap();
-            if self.children.len() > 1 {
-                backend.push_path(
-                    svg::PathData::new(h_dir)
-                        .move_to(
-                            x + left_p + last_geo.width,
-                            running_y + last_geo.entry_height,
-                        )
-                        .horizontal(geo.width - last_geo.width - left_p - ARC_RADIUS * 2)
-                        .arc(ARC_RADIUS, svg::Arc::WestToNorth)
-                        .vertical(
-                            -geo.height
-                                + last_geo.height_below_entry()
-                                + ARC_RADIUS * 2
-                                + geo.entry_height,
-                        )
-                        .arc(ARC_RADIUS, svg::Arc::SouthToEast),
-                )?;
-            }
-            backend.push_child(last_child, x + left_p, running_y, h_dir, last_geo)?;
+    fn width_from(&self, widths: &[i64]) -> i64 {
+        let left_p = self.left_padding();
+        let max_width = widths.iter().copied().max().unwrap_or(0);
+        let base_width = left_p + max_width + self.right_padding;
+        let rows = self.row_extents(widths);
+        let last_right = rows.last().map_or(0, |r| r.1);
+        // Make room between the rows turning down and the last row's path back up.
+        let needs_extra = rows
+            .iter()
+            .rev()
+            .skip(1)
+            .any(|&(_, right)| right >= last_right);
+        if needs_extra {
+            base_width + ARC_RADIUS
+        } else {
+            base_width
         }
-        Ok(())
     }
 
     fn padded_height(&self, child: &dyn Node, next_child: &dyn Node) -> i64 {
@@ -378,6 +428,7 @@ impl<N> Default for Stack<N> {
             left_padding: ARC_RADIUS * 2,
             right_padding: ARC_RADIUS * 2,
             spacing: 10,
+            alignment: Alignment::default(),
             attributes: HashMap::default(),
         }
     }
@@ -406,106 +457,28 @@ where
     }
 
     fn width(&self) -> i64 {
-        let left_p = self.left_padding();
-        let max_width = self.children.iter().map(Node::width).max().unwrap_or(0);
-        let last_width = self.children.last().map(Node::width).unwrap_or(0);
-        let base_width = left_p + max_width + self.right_padding;
-        let needs_extra = self
-            .children
-            .iter()
-            .rev()
-            .skip(1)
-            .rev()
-            .any(|c| c.width() >= last_width);
-        if needs_extra {
-            base_width + ARC_RADIUS
-        } else {
-            base_width
-        }
+        let widths: Vec<i64> = self.children.iter().map(Node::width).collect();
+        self.width_from(&widths)
     }
 
     fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
-        let left_p = self.left_padding();
-        let mut g = svg::Element::new("g").set_all(self.attributes.iter()).add(
-            svg::PathData::new(h_dir)
-                .move_to(x, y + self.entry_height())
-                .horizontal(left_p)
-                .into_path(),
-        );
-
-        let mut running_y = y;
-        let n = self.children.len();
-        for i in 0..n.saturating_sub(1) {
-            let child = &self.children[i];
-            let next_child = &self.children[i + 1];
-            g.push(
-                svg::PathData::new(h_dir)
-                    .move_to(x + left_p + child.width(), running_y + child.entry_height())
-                    .arc(ARC_RADIUS, svg::Arc::WestToSouth)
-                    .vertical(cmp::max(
-                        0,
-                        child.height_below_entry() + self.spacing - ARC_RADIUS * 2,
-                    ))
-                    .arc(ARC_RADIUS, svg::Arc::NorthToWest)
-                    .horizontal(-child.width())
-                    .arc(ARC_RADIUS, svg::Arc::EastToSouth)
-                    .vertical(cmp::max(0, next_child.entry_height() - ARC_RADIUS))
-                    .vertical(cmp::max(
-                        0,
-                        (self.spacing - ARC_RADIUS * 2) / 2 + (self.spacing - ARC_RADIUS * 2) % 2,
-                    ))
-                    .arc(ARC_RADIUS, svg::Arc::NorthToEast)
-                    .into_path(),
-            );
-            g.push(child.draw(x + left_p, running_y, h_dir));
-            running_y += self.padded_height(child, next_child);
-        }
-
-        if let Some(last_child) = self.children.last() {
-            if self.children.len() > 1 {
-                g.push(
-                    svg::PathData::new(h_dir)
-                        .move_to(
-                            x + left_p + last_child.width(),
-                            running_y + last_child.entry_height(),
-                        )
-                        .horizontal(self.width() - last_child.width() - left_p - ARC_RADIUS * 2)
-                        .arc(ARC_RADIUS, svg::Arc::WestToNorth)
-                        .vertical(
-                            -self.height()
-                                + last_child.height_below_entry()
-                                + ARC_RADIUS * 2
-                                + self.entry_height(),
-                        )
-                        .arc(ARC_RADIUS, svg::Arc::SouthToEast)
-                        .into_path(),
-                );
-            }
-            g.push(last_child.draw(x + left_p, running_y, h_dir));
-        }
-
-        g.debug("Stack", x, y, self)
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let children: Vec<NodeGeometry> =
-            self.children.iter().map(|c| c.compute_geometry()).collect();
-        let entry_height = children.first().map(|g| g.entry_height).unwrap_or(0);
-        let left_p = self.left_padding();
-        let max_width = children.iter().map(|g| g.width).max().unwrap_or(0);
-        let last_width = children.last().map(|g| g.width).unwrap_or(0);
-        let base_width = left_p + max_width + self.right_padding;
-        let needs_extra = children
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .children
             .iter()
-            .rev()
-            .skip(1)
-            .rev()
-            .any(|g| g.width >= last_width);
-        let width = if needs_extra {
-            base_width + ARC_RADIUS
-        } else {
-            base_width
-        };
+            .map(|c| c.compute_geometry_in(orientation))
+            .collect();
+        let entry_height = children.first().map(|g| g.entry_height).unwrap_or(0);
+        let widths: Vec<i64> = children.iter().map(|g| g.width).collect();
+        let width = self.width_from(&widths);
         let height = children
             .windows(2)
             .map(|w| {
@@ -522,6 +495,8 @@ where
             height,
             width,
             children,
+            id: None,
+            orientation,
         }
     }
 
@@ -543,28 +518,122 @@ where
             self.emit_with_geometry(backend, x, y, h_dir, geo)
         })
     }
-}
 
-/// A container of elements, drawn vertically, where exactly one element has to be picked
-///
-/// Use `Empty` as one of the elements to make the entire `Choice` optional (a shorthand for
-/// `Optional(Choice(..))`.
-#[derive(Debug, Clone)]
-pub struct Choice<N> {
-    children: Vec<N>,
-    spacing: i64,
-    attributes: HashMap<String, String>,
-}
+    /// Emit the stack connectors and children once for both render backends.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let left_p = self.left_padding();
+        let widths: Vec<i64> = geo.children.iter().map(|g| g.width).collect();
+        let rows = self.row_extents(&widths);
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, y + geo.entry_height)
+                .horizontal(left_p + rows.first().map_or(0, |r| r.0)),
+        )?;
 
-impl<N> Choice<N> {
-    /// Create a `Choice` from an ordered list of alternatives.
-    ///
-    /// The first child is drawn inline (on the main path); additional children
-    /// are drawn below, reachable via downward arcs.
-    ///
-    /// # Example
-    /// ```rust
-    /// use railroad::*;
+        let mut running_y = y;
+        let n = self.children.len();
+        for i in 0..n.saturating_sub(1) {
+            let child = &self.children[i];
+            let child_geo = &geo.children[i];
+            let next_geo = &geo.children[i + 1];
+            let (offset, right) = rows[i];
+            let mut path = svg::PathData::new(h_dir).move_to(
+                x + left_p + offset + child_geo.width,
+                running_y + child_geo.entry_height,
+            );
+            if right > offset + child_geo.width {
+                path = path.horizontal(right - offset - child_geo.width);
+            }
+            path = path
+                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                .vertical(cmp::max(
+                    0,
+                    child_geo.height_below_entry() + self.spacing - ARC_RADIUS * 2,
+                ))
+                .arc(ARC_RADIUS, svg::Arc::NorthToWest)
+                .horizontal(-right)
+                .arc(ARC_RADIUS, svg::Arc::EastToSouth)
+                .vertical(cmp::max(0, next_geo.entry_height - ARC_RADIUS))
+                .vertical(cmp::max(
+                    0,
+                    (self.spacing - ARC_RADIUS * 2) / 2 + (self.spacing - ARC_RADIUS * 2) % 2,
+                ))
+                .arc(ARC_RADIUS, svg::Arc::NorthToEast);
+            if rows[i + 1].0 > 0 {
+                path = path.horizontal(rows[i + 1].0);
+            }
+            backend.push_path(path)?;
+            backend.push_child(child, x + left_p + offset, running_y, h_dir, child_geo)?;
+            let ph = child_geo.entry_height
+                + cmp::max(
+                    child_geo.height_below_entry() + self.spacing,
+                    ARC_RADIUS * 2,
+                )
+                + ARC_RADIUS
+                + cmp::max(0, ARC_RADIUS - next_geo.entry_height);
+            running_y += ph;
+        }
+
+        if let Some(last_child) = self.children.last() {
+            let last_geo = geo.children.last().unwrap();
+            let (offset, right) = *rows.last().unwrap();
+            if self.children.len() > 1 {
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(x + left_p + right, running_y + last_geo.entry_height)
+                        .horizontal(geo.width - right - left_p - ARC_RADIUS * 2)
+                        .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                        .vertical(
+                            -geo.height
+                                + last_geo.height_below_entry()
+                                + ARC_RADIUS * 2
+                                + geo.entry_height,
+                        )
+                        .arc(ARC_RADIUS, svg::Arc::SouthToEast),
+                )?;
+            }
+            backend.push_child(last_child, x + left_p + offset, running_y, h_dir, last_geo)?;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> Description {
+        self.alignment
+            .describe(Description::new("stack"))
+            .with_children(self.children.iter().map(Node::describe))
+    }
+}
+
+/// A container of elements, drawn vertically, where exactly one element has to be picked
+///
+/// Use `Empty` as one of the elements to make the entire `Choice` optional (a shorthand for
+/// `Optional(Choice(..))`.
+#[derive(Debug, Clone)]
+pub struct Choice<N> {
+    children: Vec<N>,
+    spacing: i64,
+    alignment: Alignment,
+    default_branch: usize,
+    attributes: HashMap<String, String>,
+}
+
+impl<N> Choice<N> {
+    /// Create a `Choice` from an ordered list of alternatives.
+    ///
+    /// The first child is drawn inline (on the main path); additional children
+    /// are drawn below, reachable via downward arcs. See
+    /// [`Choice::set_default_branch`] to put another child on the main path.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
     ///
     /// let choice = Choice::new(vec![
     ///     Terminal::new("A".to_owned()),
@@ -587,6 +656,45 @@ impl<N> Choice<N> {
         self.children.push(child);
     }
 
+    /// Set how alternatives narrower than the widest one are placed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut choice = Choice::new(vec![
+    ///     Terminal::new("A".to_owned()),
+    ///     Terminal::new("BBBBBB".to_owned()),
+    /// ]);
+    /// choice.set_alignment(Alignment::Center);
+    /// assert!(Diagram::new(choice).to_string().starts_with("<svg"));
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+
+    /// Put the alternative at `index` on the main path.
+    ///
+    /// Alternatives before it are drawn above, alternatives after it are drawn
+    /// below. If `index` is out of bounds, the last alternative is used.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut choice = Choice::new(vec![
+    ///     Box::new(Terminal::new("ASC".to_owned())) as Box<dyn Node>,
+    ///     Box::new(Empty),
+    ///     Box::new(Terminal::new("DESC".to_owned())),
+    /// ]);
+    /// choice.set_default_branch(1);
+    /// // The entry is now below "ASC".
+    /// assert!(choice.entry_height() > Terminal::new("ASC".to_owned()).height());
+    /// ```
+    pub fn set_default_branch(&mut self, index: usize) {
+        self.default_branch = index;
+    }
+
     /// Access an attribute on the main SVG-element that will be drawn.
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
@@ -606,64 +714,271 @@ impl<N> Choice<N> {
         }
     }
 
-    fn padded_height(&self, child: &dyn Node) -> i64 {
-        cmp::max(ARC_RADIUS, child.entry_height()) + child.height_below_entry() + self.spacing
+    /// The index of the alternative on the main path.
+    fn default_index(&self) -> usize {
+        cmp::min(self.default_branch, self.children.len().saturating_sub(1))
+    }
+
+    /// The `(entry_height, height)` of this choice, given the `entry_height`
+    /// and `height_below_entry` of every alternative.
+    fn extents(&self, alternatives: &[(i64, i64)]) -> (i64, i64) {
+        let Some(&(main_above, main_below)) = alternatives.get(self.default_index()) else {
+            return (0, 0);
+        };
+        let (above, below) = alternatives.split_at(self.default_index());
+        let above = if above.is_empty() {
+            main_above
+        } else {
+            cmp::max(ARC_RADIUS, self.spacing + main_above)
+                + above
+                    .iter()
+                    .map(|&(eh, hbe)| cmp::max(ARC_RADIUS, hbe) + eh + self.spacing)
+                    .sum::<i64>()
+                - self.spacing
+        };
+        let below = if below.len() == 1 {
+            main_below
+        } else {
+            cmp::max(ARC_RADIUS, self.spacing + main_below)
+                + below[1..]
+                    .iter()
+                    .map(|&(eh, hbe)| cmp::max(ARC_RADIUS, eh) + hbe + self.spacing)
+                    .sum::<i64>()
+                - self.spacing
+        };
+        (above, above + below)
+    }
+}
+
+impl<N> iter::FromIterator<N> for Choice<N> {
+    fn from_iter<T: IntoIterator<Item = N>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<N> Default for Choice<N> {
+    fn default() -> Self {
+        Self {
+            children: Vec::default(),
+            spacing: 10,
+            alignment: Alignment::default(),
+            default_branch: 0,
+            attributes: HashMap::default(),
+        }
+    }
+}
+
+impl<N> Node for Choice<N>
+where
+    N: Node,
+{
+    fn entry_height(&self) -> i64 {
+        let alternatives: Vec<(i64, i64)> = self
+            .children
+            .iter()
+            .map(|c| (c.entry_height(), c.height_below_entry()))
+            .collect();
+        self.extents(&alternatives).0
+    }
+
+    fn height(&self) -> i64 {
+        let alternatives: Vec<(i64, i64)> = self
+            .children
+            .iter()
+            .map(|c| (c.entry_height(), c.height_below_entry()))
+            .collect();
+        self.extents(&alternatives).1
+    }
+
+    fn width(&self) -> i64 {
+        if self.children.len() > 1 {
+            self.inner_padding()
+                + self.children.iter().map(Node::width).max().unwrap_or(0)
+                + self.inner_padding()
+        } else {
+            self.children.iter().map(Node::width).max().unwrap_or(0)
+        }
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
+    }
+
+    fn compute_geometry(&self) -> NodeGeometry {
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .children
+            .iter()
+            .map(|c| c.compute_geometry_in(orientation))
+            .collect();
+        let inner_padding = self.inner_padding();
+        let max_width = children.iter().map(|g| g.width).max().unwrap_or(0);
+        let width = if children.len() > 1 {
+            inner_padding + max_width + inner_padding
+        } else {
+            max_width
+        };
+        let alternatives: Vec<(i64, i64)> = children
+            .iter()
+            .map(|g| (g.entry_height, g.height_below_entry()))
+            .collect();
+        let (entry_height, height) = self.extents(&alternatives);
+        NodeGeometry {
+            entry_height,
+            height,
+            width,
+            children,
+            id: None,
+            orientation,
+        }
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        draw_group_with_geometry(&self.attributes, "Choice", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
+
+    fn render_with_geometry(
+        &self,
+        out: &mut svg::Renderer<'_>,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        render_group_with_geometry(out, &self.attributes, "Choice", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
     }
 
     /// Emit all choice branches and their connecting arcs through the shared backend.
-    fn emit_with_geometry<B: RenderBackend>(
+    fn emit_with_geometry(
         &self,
-        backend: &mut B,
+        backend: &mut dyn RenderBackend,
         x: i64,
         y: i64,
         h_dir: HDir,
         geo: &NodeGeometry,
-    ) -> fmt::Result
-    where
-        N: Node,
-    {
+    ) -> fmt::Result {
         let inner_padding = self.inner_padding();
         let max_child_width = geo.children.iter().map(|g| g.width).max().unwrap_or(0);
+        let offset =
+            |child_geo: &NodeGeometry| self.alignment.offset(max_child_width - child_geo.width);
+        let main_index = self.default_index();
+        let main_offset = geo.children.get(main_index).map_or(0, offset);
+        let main_width = geo.children.get(main_index).map_or(0, |g| g.width);
+        let entry_y = y + geo.entry_height;
 
         backend.push_path(
             svg::PathData::new(h_dir)
-                .move_to(x, y + geo.entry_height)
-                .horizontal(inner_padding)
-                .move_rel(geo.children.first().map(|g| g.width).unwrap_or(0), 0)
-                .horizontal(
-                    geo.width - inner_padding - geo.children.first().map(|g| g.width).unwrap_or(0),
-                ),
+                .move_to(x, entry_y)
+                .horizontal(inner_padding + main_offset)
+                .move_rel(main_width, 0)
+                .horizontal(geo.width - inner_padding - main_offset - main_width),
         )?;
 
-        if let Some((first_child, first_child_geo)) =
-            self.children.first().zip(geo.children.first())
-        {
-            backend.push_child(first_child, x + inner_padding, y, h_dir, first_child_geo)?;
+        let Some((main_child, main_geo)) = self
+            .children
+            .get(main_index)
+            .zip(geo.children.get(main_index))
+        else {
+            return Ok(());
+        };
+        backend.push_child(
+            main_child,
+            x + inner_padding + main_offset,
+            entry_y - main_geo.entry_height,
+            h_dir,
+            main_geo,
+        )?;
+
+        // Alternatives above the main path, from the closest one upwards.
+        if main_index > 0 {
+            let spine = cmp::max(0, main_geo.entry_height + self.spacing - ARC_RADIUS);
+            backend.push_path(
+                svg::PathData::new(h_dir)
+                    .move_to(x, entry_y)
+                    .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                    .vertical(-spine)
+                    .move_rel(geo.width - ARC_RADIUS * 2, 0)
+                    .vertical(spine)
+                    .arc(ARC_RADIUS, svg::Arc::NorthToEast),
+            )?;
+
+            let base_y = entry_y - cmp::max(ARC_RADIUS, self.spacing + main_geo.entry_height);
+            let mut running_y = base_y;
+            for child_geo in geo.children[1..main_index].iter().rev() {
+                let padded = cmp::max(ARC_RADIUS, child_geo.height_below_entry())
+                    + child_geo.entry_height
+                    + self.spacing;
+                let zz = cmp::max(0, child_geo.height_below_entry() - ARC_RADIUS);
+                let z = padded - zz;
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(x + ARC_RADIUS, running_y - zz)
+                        .vertical(-z)
+                        .move_rel(geo.width - ARC_RADIUS * 2, 0)
+                        .vertical(z),
+                )?;
+                running_y -= z + zz;
+            }
+
+            let mut running_y = base_y;
+            for (child, child_geo) in self.children[..main_index]
+                .iter()
+                .zip(&geo.children[..main_index])
+                .rev()
+            {
+                let child_offset = offset(child_geo);
+                let zz = cmp::max(0, child_geo.height_below_entry() - ARC_RADIUS);
+                let mut path = svg::PathData::new(h_dir)
+                    .move_to(x + ARC_RADIUS, running_y)
+                    .vertical(-zz)
+                    .arc(ARC_RADIUS, svg::Arc::SouthToEast);
+                if child_offset > 0 {
+                    path = path.horizontal(child_offset);
+                }
+                backend.push_path(
+                    path.move_rel(child_geo.width, 0)
+                        .horizontal(max_child_width - child_offset - child_geo.width)
+                        .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                        .vertical(zz),
+                )?;
+                let child_entry_y =
+                    running_y - cmp::max(ARC_RADIUS, child_geo.height_below_entry());
+                backend.push_child(
+                    child,
+                    x + ARC_RADIUS * 2 + child_offset,
+                    child_entry_y - child_geo.entry_height,
+                    h_dir,
+                    child_geo,
+                )?;
+                running_y = child_entry_y - child_geo.entry_height - self.spacing;
+            }
         }
 
-        if self.children.len() > 1 {
-            let first_geo = &geo.children[0];
+        // Alternatives below the main path, from the closest one downwards.
+        if main_index + 1 < self.children.len() {
+            let spine = cmp::max(0, main_geo.height_below_entry() + self.spacing - ARC_RADIUS);
             backend.push_path(
                 svg::PathData::new(h_dir)
-                    .move_to(x, y + geo.entry_height)
+                    .move_to(x, entry_y)
                     .arc(ARC_RADIUS, svg::Arc::WestToSouth)
-                    .vertical(cmp::max(
-                        0,
-                        first_geo.height_below_entry() + self.spacing - ARC_RADIUS,
-                    ))
+                    .vertical(spine)
                     .move_rel(geo.width - ARC_RADIUS * 2, 0)
-                    .vertical(-cmp::max(
-                        0,
-                        first_geo.height_below_entry() + self.spacing - ARC_RADIUS,
-                    ))
+                    .vertical(-spine)
                     .arc(ARC_RADIUS, svg::Arc::SouthToEast),
             )?;
 
-            let base_y = y
-                + geo.entry_height
-                + cmp::max(ARC_RADIUS, self.spacing + first_geo.height_below_entry());
+            let base_y =
+                entry_y + cmp::max(ARC_RADIUS, self.spacing + main_geo.height_below_entry());
             let mut running_y = base_y;
-            for child_geo in geo.children.iter().skip(1).rev().skip(1).rev() {
+            for child_geo in geo.children[main_index + 1..].iter().rev().skip(1).rev() {
                 let padded = cmp::max(ARC_RADIUS, child_geo.entry_height)
                     + child_geo.height_below_entry()
                     + self.spacing;
@@ -680,25 +995,27 @@ impl<N> Choice<N> {
             }
 
             let mut running_y = base_y;
-            for (child, child_geo) in self
-                .children
+            for (child, child_geo) in self.children[main_index + 1..]
                 .iter()
-                .skip(1)
-                .zip(geo.children.iter().skip(1))
+                .zip(&geo.children[main_index + 1..])
             {
+                let child_offset = offset(child_geo);
+                let mut path = svg::PathData::new(h_dir)
+                    .move_to(x + ARC_RADIUS, running_y)
+                    .vertical(cmp::max(0, child_geo.entry_height - ARC_RADIUS))
+                    .arc(ARC_RADIUS, svg::Arc::NorthToEast);
+                if child_offset > 0 {
+                    path = path.horizontal(child_offset);
+                }
                 backend.push_path(
-                    svg::PathData::new(h_dir)
-                        .move_to(x + ARC_RADIUS, running_y)
-                        .vertical(cmp::max(0, child_geo.entry_height - ARC_RADIUS))
-                        .arc(ARC_RADIUS, svg::Arc::NorthToEast)
-                        .move_rel(child_geo.width, 0)
-                        .horizontal(max_child_width - child_geo.width)
+                    path.move_rel(child_geo.width, 0)
+                        .horizontal(max_child_width - child_offset - child_geo.width)
                         .arc(ARC_RADIUS, svg::Arc::WestToNorth)
                         .vertical(-cmp::max(0, child_geo.entry_height - ARC_RADIUS)),
                 )?;
                 backend.push_child(
                     child,
-                    x + ARC_RADIUS * 2,
+                    x + ARC_RADIUS * 2 + child_offset,
                     running_y + cmp::max(0, ARC_RADIUS - child_geo.entry_height),
                     h_dir,
                     child_geo,
@@ -710,214 +1027,1281 @@ impl<N> Choice<N> {
         }
         Ok(())
     }
-}
 
-impl<N> iter::FromIterator<N> for Choice<N> {
-    fn from_iter<T: IntoIterator<Item = N>>(iter: T) -> Self {
-        Self::new(iter.into_iter().collect())
+    fn describe(&self) -> Description {
+        let desc = self.alignment.describe(Description::new("choice"));
+        let desc = if self.default_branch == 0 {
+            desc
+        } else {
+            desc.with_property("default", self.default_branch.to_string())
+        };
+        desc.with_children(self.children.iter().map(Node::describe))
     }
 }
 
-impl<N> Default for Choice<N> {
-    fn default() -> Self {
-        Self {
-            children: Vec::default(),
-            spacing: 10,
-            attributes: HashMap::default(),
-        }
-    }
+/// A container of elements, drawn side by side, where exactly one element has to be picked.
+///
+/// The first alternative sits on the main path. The others are reached by
+/// dropping down from a rail running above the alternatives, and all but the
+/// last leave via a rail running below them. This takes up much less height
+/// than a [`Choice`] of short alternatives.
+#[derive(Debug, Clone)]
+pub struct HorizontalChoice<N> {
+    children: Vec<N>,
+    spacing: i64,
+    attributes: HashMap<String, String>,
 }
 
-impl<N> Node for Choice<N>
+impl<N> HorizontalChoice<N> {
+    /// Create a `HorizontalChoice` from an ordered list of alternatives.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let choice = HorizontalChoice::new(vec![
+    ///     Terminal::new("+".to_owned()),
+    ///     Terminal::new("-".to_owned()),
+    ///     Terminal::new("*".to_owned()),
+    /// ]);
+    /// assert!(Diagram::new(choice).to_string().contains("class=\"horizontalchoice\""));
+    /// ```
+    #[must_use]
+    pub fn new(children: Vec<N>) -> Self {
+        let mut c = Self {
+            children,
+            ..Self::default()
+        };
+        c.attributes
+            .insert("class".to_owned(), "horizontalchoice".to_owned());
+        c
+    }
+
+    /// Append an alternative child.
+    pub fn push(&mut self, child: N) {
+        self.children.push(child);
+    }
+
+    /// Access an attribute on the main SVG-element that will be drawn.
+    pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
+        self.attributes.entry(key)
+    }
+
+    /// Unwrap this choice, returning the children in order.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<N> {
+        self.children
+    }
+
+    /// The distance of the upper and of the lower rail from the main path,
+    /// given the `entry_height` and `height_below_entry` of every alternative.
+    fn rails(&self, alternatives: &[(i64, i64)]) -> (i64, i64) {
+        let Some((_, rest)) = alternatives.split_first() else {
+            return (0, 0);
+        };
+        // The upper rail runs above all but the last alternative, the lower
+        // rail below all but the first.
+        let upper = alternatives[..alternatives.len() - 1]
+            .iter()
+            .map(|&(eh, _)| eh + self.spacing)
+            .fold(ARC_RADIUS * 2, cmp::max);
+        let lower = rest
+            .iter()
+            .map(|&(_, hbe)| hbe + self.spacing)
+            .fold(ARC_RADIUS * 2, cmp::max);
+        (upper, lower)
+    }
+
+    /// The `(entry_height, height)` of this choice, given the `entry_height`
+    /// and `height_below_entry` of every alternative.
+    fn extents(&self, alternatives: &[(i64, i64)]) -> (i64, i64) {
+        let max_above = alternatives.iter().map(|a| a.0).max().unwrap_or(0);
+        let max_below = alternatives.iter().map(|a| a.1).max().unwrap_or(0);
+        if alternatives.len() < 2 {
+            return (max_above, max_above + max_below);
+        }
+        let (upper, lower) = self.rails(alternatives);
+        let above = cmp::max(upper, max_above);
+        (above, above + cmp::max(lower, max_below))
+    }
+
+    fn width_from(&self, widths: &[i64]) -> i64 {
+        let total: i64 = widths.iter().sum();
+        if widths.len() < 2 {
+            total
+        } else {
+            total + (i64::try_from(widths.len()).unwrap() + 1) * ARC_RADIUS * 2
+        }
+    }
+}
+
+impl<N> iter::FromIterator<N> for HorizontalChoice<N> {
+    fn from_iter<T: IntoIterator<Item = N>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<N> Default for HorizontalChoice<N> {
+    fn default() -> Self {
+        Self {
+            children: Vec::default(),
+            spacing: 10,
+            attributes: HashMap::default(),
+        }
+    }
+}
+
+impl<N> Node for HorizontalChoice<N>
 where
     N: Node,
 {
     fn entry_height(&self) -> i64 {
-        self.children
-            .first()
-            .map(Node::entry_height)
-            .unwrap_or_default()
+        let alternatives: Vec<(i64, i64)> = self
+            .children
+            .iter()
+            .map(|c| (c.entry_height(), c.height_below_entry()))
+            .collect();
+        self.extents(&alternatives).0
     }
 
     fn height(&self) -> i64 {
-        if self.children.is_empty() {
-            0
-        } else if self.children.len() == 1 {
-            self.children.iter().map(Node::height).sum()
-        } else {
-            self.entry_height()
-                + cmp::max(
-                    ARC_RADIUS,
-                    self.spacing + self.children[0].height_below_entry(),
-                )
-                + self
-                    .children
-                    .iter()
-                    .skip(1)
-                    .map(|c| self.padded_height(c))
-                    .sum::<i64>()
-                - self.spacing
-        }
+        let alternatives: Vec<(i64, i64)> = self
+            .children
+            .iter()
+            .map(|c| (c.entry_height(), c.height_below_entry()))
+            .collect();
+        self.extents(&alternatives).1
     }
 
     fn width(&self) -> i64 {
-        if self.children.len() > 1 {
-            self.inner_padding()
-                + self.children.iter().map(Node::width).max().unwrap_or(0)
-                + self.inner_padding()
-        } else {
-            self.children.iter().map(Node::width).max().unwrap_or(0)
-        }
+        let widths: Vec<i64> = self.children.iter().map(Node::width).collect();
+        self.width_from(&widths)
     }
 
     fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
-        let mut g = svg::Element::new("g").set_all(self.attributes.iter());
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
+    }
 
-        g.push(
-            svg::PathData::new(h_dir)
-                .move_to(x, y + self.entry_height())
-                .horizontal(self.inner_padding())
-                .move_rel(
-                    self.children.first().map(Node::width).unwrap_or_default(),
-                    0,
-                )
-                .horizontal(
-                    self.width()
-                        - self.inner_padding()
-                        - self.children.first().map(Node::width).unwrap_or_default(),
-                )
-                .into_path(),
-        );
+    fn compute_geometry(&self) -> NodeGeometry {
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
 
-        if let Some(child) = self.children.first() {
-            g.push(child.draw(x + self.inner_padding(), y, h_dir));
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .children
+            .iter()
+            .map(|c| c.compute_geometry_in(orientation))
+            .collect();
+        let alternatives: Vec<(i64, i64)> = children
+            .iter()
+            .map(|g| (g.entry_height, g.height_below_entry()))
+            .collect();
+        let (entry_height, height) = self.extents(&alternatives);
+        let widths: Vec<i64> = children.iter().map(|g| g.width).collect();
+        NodeGeometry {
+            entry_height,
+            height,
+            width: self.width_from(&widths),
+            children,
+            id: None,
+            orientation,
         }
+    }
 
-        if self.children.len() > 1 {
-            g.push(
-                svg::PathData::new(h_dir)
-                    .move_to(x, y + self.entry_height())
-                    .arc(ARC_RADIUS, svg::Arc::WestToSouth)
-                    .vertical(cmp::max(
-                        0,
-                        self.children[0].height_below_entry() + self.spacing - ARC_RADIUS,
-                    ))
-                    .move_rel(self.width() - ARC_RADIUS * 2, 0)
-                    .vertical(-cmp::max(
-                        0,
-                        self.children[0].height_below_entry() + self.spacing - ARC_RADIUS,
-                    ))
-                    .arc(ARC_RADIUS, svg::Arc::SouthToEast)
-                    .into_path(),
-            );
+    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        draw_group_with_geometry(&self.attributes, "HorizontalChoice", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
 
-            let mut running_y = y
-                + self.entry_height()
-                + cmp::max(
-                    ARC_RADIUS,
-                    self.spacing + self.children[0].height_below_entry(),
-                );
-            for child in self.children.iter().skip(1).rev().skip(1).rev() {
-                let z = self.padded_height(child);
-                let zz = cmp::max(0, child.entry_height() - ARC_RADIUS);
-                let z = z - zz;
-                g.push(
+    fn render_with_geometry(
+        &self,
+        out: &mut svg::Renderer<'_>,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        render_group_with_geometry(
+            out,
+            &self.attributes,
+            "HorizontalChoice",
+            x,
+            y,
+            geo,
+            |backend| self.emit_with_geometry(backend, x, y, h_dir, geo),
+        )
+    }
+
+    /// Emit the rails, the alternatives and their connectors through the shared backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let entry_y = y + geo.entry_height;
+        match (self.children.as_slice(), geo.children.as_slice()) {
+            ([], _) => {
+                return backend.push_path(
                     svg::PathData::new(h_dir)
-                        .move_to(x + ARC_RADIUS, running_y + zz)
-                        .vertical(z)
-                        .move_rel(self.width() - ARC_RADIUS * 2, 0)
-                        .vertical(-z)
-                        .into_path(),
+                        .move_to(x, entry_y)
+                        .horizontal(geo.width),
                 );
-                running_y += z + zz;
             }
+            ([child], [child_geo]) => {
+                return backend.push_child(child, x, y, h_dir, child_geo);
+            }
+            _ => {}
+        }
 
-            let mut running_y = y
-                + self.entry_height()
-                + cmp::max(
-                    ARC_RADIUS,
-                    self.spacing + self.children[0].height_below_entry(),
-                );
-            for child in self.children.iter().skip(1) {
-                g.push(
+        let alternatives: Vec<(i64, i64)> = geo
+            .children
+            .iter()
+            .map(|g| (g.entry_height, g.height_below_entry()))
+            .collect();
+        let (upper, lower) = self.rails(&alternatives);
+        let last = self.children.len() - 1;
+        let mut child_xs = Vec::with_capacity(self.children.len());
+        let mut running_x = x + ARC_RADIUS * 2;
+        for child_geo in &geo.children {
+            child_xs.push(running_x);
+            running_x += child_geo.width + ARC_RADIUS * 2;
+        }
+        let first_exit_x = child_xs[0] + geo.children[0].width;
+        let last_exit_x = child_xs[last] + geo.children[last].width;
+
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, entry_y)
+                .horizontal(ARC_RADIUS * 2),
+        )?;
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, entry_y)
+                .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                .vertical(-(upper - ARC_RADIUS * 2))
+                .arc(ARC_RADIUS, svg::Arc::SouthToEast)
+                .horizontal(child_xs[last] - ARC_RADIUS * 2 - (x + ARC_RADIUS * 2)),
+        )?;
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(first_exit_x + ARC_RADIUS * 2, entry_y + lower)
+                .horizontal(last_exit_x - first_exit_x - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                .vertical(-(lower - ARC_RADIUS * 2))
+                .arc(ARC_RADIUS, svg::Arc::SouthToEast),
+        )?;
+
+        for (i, ((child, child_geo), &child_x)) in self
+            .children
+            .iter()
+            .zip(&geo.children)
+            .zip(&child_xs)
+            .enumerate()
+        {
+            if i > 0 {
+                backend.push_path(
                     svg::PathData::new(h_dir)
-                        .move_to(x + ARC_RADIUS, running_y)
-                        .vertical(cmp::max(0, child.entry_height() - ARC_RADIUS))
-                        .arc(ARC_RADIUS, svg::Arc::NorthToEast)
-                        .move_rel(child.width(), 0)
-                        .horizontal(
-                            self.children.iter().map(Node::width).max().unwrap_or(0)
-                                - child.width(),
-                        )
-                        .arc(ARC_RADIUS, svg::Arc::WestToNorth)
-                        .vertical(-cmp::max(0, child.entry_height() - ARC_RADIUS))
-                        .into_path(),
-                );
-                g.push(child.draw(
-                    x + ARC_RADIUS * 2,
-                    running_y + cmp::max(0, ARC_RADIUS - child.entry_height()),
-                    h_dir,
-                ));
-                running_y += self.padded_height(child);
+                        .move_to(child_x - ARC_RADIUS * 2, entry_y - upper)
+                        .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                        .vertical(upper - ARC_RADIUS * 2)
+                        .arc(ARC_RADIUS, svg::Arc::NorthToEast),
+                )?;
+            }
+            backend.push_child(
+                child,
+                child_x,
+                entry_y - child_geo.entry_height,
+                h_dir,
+                child_geo,
+            )?;
+            let exit_x = child_x + child_geo.width;
+            if i < last {
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(exit_x, entry_y)
+                        .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                        .vertical(lower - ARC_RADIUS * 2)
+                        .arc(ARC_RADIUS, svg::Arc::NorthToEast),
+                )?;
+            } else {
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(exit_x, entry_y)
+                        .horizontal(ARC_RADIUS * 2),
+                )?;
             }
         }
+        Ok(())
+    }
 
-        g.debug("Choice", x, y, self)
+    fn describe(&self) -> Description {
+        Description::new("horizontalchoice").with_children(self.children.iter().map(Node::describe))
     }
+}
 
-    fn compute_geometry(&self) -> NodeGeometry {
-        let children: Vec<NodeGeometry> =
-            self.children.iter().map(|c| c.compute_geometry()).collect();
-        let entry_height = children.first().map(|g| g.entry_height).unwrap_or(0);
-        let inner_padding = self.inner_padding();
-        let max_width = children.iter().map(|g| g.width).max().unwrap_or(0);
-        let width = if children.len() > 1 {
-            inner_padding + max_width + inner_padding
-        } else {
-            max_width
+/// A container of elements, drawn side by side, of which any non-empty subset
+/// has to be picked, in order.
+///
+/// All elements sit on the main path. A rail below them lets the path skip
+/// leading elements, and a rail above them lets it skip elements after the
+/// first one picked; where the two rails meet the main path, their connectors
+/// cross. This is much more compact than spelling the subsets out with
+/// [`Choice`] and [`Optional`](crate::Optional).
+#[derive(Debug, Clone)]
+pub struct OptionalSequence<N> {
+    children: Vec<N>,
+    spacing: i64,
+    attributes: HashMap<String, String>,
+}
+
+impl<N> OptionalSequence<N> {
+    /// Create an `OptionalSequence` from an ordered list of elements.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let seq = OptionalSequence::new(vec![
+    ///     Terminal::new("UNIQUE".to_owned()),
+    ///     Terminal::new("NOT NULL".to_owned()),
+    ///     Terminal::new("PRIMARY KEY".to_owned()),
+    /// ]);
+    /// assert!(Diagram::new(seq).to_string().contains("class=\"optionalsequence\""));
+    /// ```
+    #[must_use]
+    pub fn new(children: Vec<N>) -> Self {
+        let mut s = Self {
+            children,
+            ..Self::default()
         };
-        let height = if children.is_empty() {
-            0
-        } else if children.len() == 1 {
-            children.iter().map(|g| g.height).sum()
-        } else {
-            let first = &children[0];
-            entry_height
-                + cmp::max(ARC_RADIUS, self.spacing + first.height_below_entry())
-                + children
-                    .iter()
-                    .skip(1)
-                    .map(|g| {
-                        cmp::max(ARC_RADIUS, g.entry_height) + g.height_below_entry() + self.spacing
-                    })
-                    .sum::<i64>()
-                - self.spacing
+        s.attributes
+            .insert("class".to_owned(), "optionalsequence".to_owned());
+        s
+    }
+
+    /// Append an element.
+    pub fn push(&mut self, child: N) {
+        self.children.push(child);
+    }
+
+    /// Access an attribute on the main SVG-element that will be drawn.
+    pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
+        self.attributes.entry(key)
+    }
+
+    /// Unwrap this sequence, returning the children in order.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<N> {
+        self.children
+    }
+
+    /// The distance of the upper and of the lower rail from the main path,
+    /// given the `entry_height` and `height_below_entry` of every element.
+    fn rails(&self, elements: &[(i64, i64)]) -> (i64, i64) {
+        let Some((_, rest)) = elements.split_first() else {
+            return (0, 0);
         };
-        NodeGeometry {
-            entry_height,
-            height,
-            width,
-            children,
+        // The upper rail starts after the first element, the lower rail ends
+        // before the last.
+        let upper = rest
+            .iter()
+            .map(|&(eh, _)| eh + self.spacing)
+            .fold(ARC_RADIUS * 2, cmp::max);
+        let lower = elements[..elements.len() - 1]
+            .iter()
+            .map(|&(_, hbe)| hbe + self.spacing)
+            .fold(ARC_RADIUS * 2, cmp::max);
+        (upper, lower)
+    }
+
+    /// The `(entry_height, height)` of this sequence, given the `entry_height`
+    /// and `height_below_entry` of every element.
+    fn extents(&self, elements: &[(i64, i64)]) -> (i64, i64) {
+        let max_above = elements.iter().map(|e| e.0).max().unwrap_or(0);
+        let max_below = elements.iter().map(|e| e.1).max().unwrap_or(0);
+        if elements.len() < 2 {
+            return (max_above, max_above + max_below);
         }
+        let (upper, lower) = self.rails(elements);
+        let above = cmp::max(upper, max_above);
+        (above, above + cmp::max(lower, max_below))
     }
 
-    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
-        draw_group_with_geometry(&self.attributes, "Choice", x, y, geo, |backend| {
-            self.emit_with_geometry(backend, x, y, h_dir, geo)
-        })
+    fn width_from(widths: &[i64]) -> i64 {
+        let total: i64 = widths.iter().sum();
+        if widths.len() < 2 {
+            total
+        } else {
+            // Room for the rails at both ends, and for the crossing connectors
+            // between each pair of elements.
+            total + ARC_RADIUS * 4 + (i64::try_from(widths.len()).unwrap() - 1) * ARC_RADIUS * 3
+        }
+    }
+}
+
+impl<N> iter::FromIterator<N> for OptionalSequence<N> {
+    fn from_iter<T: IntoIterator<Item = N>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<N> Default for OptionalSequence<N> {
+    fn default() -> Self {
+        Self {
+            children: Vec::default(),
+            spacing: 10,
+            attributes: HashMap::default(),
+        }
+    }
+}
+
+impl<N> Node for OptionalSequence<N>
+where
+    N: Node,
+{
+    fn entry_height(&self) -> i64 {
+        let elements: Vec<(i64, i64)> = self
+            .children
+            .iter()
+            .map(|c| (c.entry_height(), c.height_below_entry()))
+            .collect();
+        self.extents(&elements).0
+    }
+
+    fn height(&self) -> i64 {
+        let elements: Vec<(i64, i64)> = self
+            .children
+            .iter()
+            .map(|c| (c.entry_height(), c.height_below_entry()))
+            .collect();
+        self.extents(&elements).1
+    }
+
+    fn width(&self) -> i64 {
+        let widths: Vec<i64> = self.children.iter().map(Node::width).collect();
+        Self::width_from(&widths)
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
+    }
+
+    fn compute_geometry(&self) -> NodeGeometry {
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .children
+            .iter()
+            .map(|c| c.compute_geometry_in(orientation))
+            .collect();
+        let elements: Vec<(i64, i64)> = children
+            .iter()
+            .map(|g| (g.entry_height, g.height_below_entry()))
+            .collect();
+        let (entry_height, height) = self.extents(&elements);
+        let widths: Vec<i64> = children.iter().map(|g| g.width).collect();
+        NodeGeometry {
+            entry_height,
+            height,
+            width: Self::width_from(&widths),
+            children,
+            id: None,
+            orientation,
+        }
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        draw_group_with_geometry(&self.attributes, "OptionalSequence", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
+
+    fn render_with_geometry(
+        &self,
+        out: &mut svg::Renderer<'_>,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        render_group_with_geometry(
+            out,
+            &self.attributes,
+            "OptionalSequence",
+            x,
+            y,
+            geo,
+            |backend| self.emit_with_geometry(backend, x, y, h_dir, geo),
+        )
+    }
+
+    /// Emit the main path, the rails, the elements and their connectors
+    /// through the shared backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let entry_y = y + geo.entry_height;
+        match (self.children.as_slice(), geo.children.as_slice()) {
+            ([], _) => {
+                return backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(x, entry_y)
+                        .horizontal(geo.width),
+                );
+            }
+            ([child], [child_geo]) => {
+                return backend.push_child(child, x, y, h_dir, child_geo);
+            }
+            _ => {}
+        }
+
+        let elements: Vec<(i64, i64)> = geo
+            .children
+            .iter()
+            .map(|g| (g.entry_height, g.height_below_entry()))
+            .collect();
+        let (upper, lower) = self.rails(&elements);
+        let last = self.children.len() - 1;
+        let mut child_xs = Vec::with_capacity(self.children.len());
+        let mut running_x = x + ARC_RADIUS * 2;
+        for child_geo in &geo.children {
+            child_xs.push(running_x);
+            running_x += child_geo.width + ARC_RADIUS * 3;
+        }
+        let first_exit_x = child_xs[0] + geo.children[0].width;
+        let last_exit_x = child_xs[last] + geo.children[last].width;
+
+        // The lower rail leaves the main path at the start, the upper rail
+        // joins it at the end.
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, entry_y)
+                .horizontal(ARC_RADIUS * 2),
+        )?;
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, entry_y)
+                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                .vertical(lower - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::NorthToEast)
+                .horizontal(child_xs[last] - ARC_RADIUS * 2 - (x + ARC_RADIUS * 2)),
+        )?;
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(first_exit_x + ARC_RADIUS * 2, entry_y - upper)
+                .horizontal(last_exit_x - first_exit_x - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                .vertical(upper - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::NorthToEast),
+        )?;
+
+        for (i, ((child, child_geo), &child_x)) in self
+            .children
+            .iter()
+            .zip(&geo.children)
+            .zip(&child_xs)
+            .enumerate()
+        {
+            if i > 0 {
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(child_x - ARC_RADIUS * 2, entry_y + lower)
+                        .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                        .vertical(-(lower - ARC_RADIUS * 2))
+                        .arc(ARC_RADIUS, svg::Arc::SouthToEast),
+                )?;
+            }
+            // Dropping from the upper rail onto the second element is the
+            // same as following the main path.
+            if i > 1 {
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(child_x - ARC_RADIUS * 2, entry_y - upper)
+                        .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                        .vertical(upper - ARC_RADIUS * 2)
+                        .arc(ARC_RADIUS, svg::Arc::NorthToEast),
+                )?;
+            }
+            backend.push_child(
+                child,
+                child_x,
+                entry_y - child_geo.entry_height,
+                h_dir,
+                child_geo,
+            )?;
+            let exit_x = child_x + child_geo.width;
+            if i < last {
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(exit_x, entry_y)
+                        .horizontal(ARC_RADIUS * 3),
+                )?;
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(exit_x, entry_y)
+                        .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                        .vertical(-(upper - ARC_RADIUS * 2))
+                        .arc(ARC_RADIUS, svg::Arc::SouthToEast),
+                )?;
+            } else {
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(exit_x, entry_y)
+                        .horizontal(ARC_RADIUS * 2),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("optionalsequence").with_children(self.children.iter().map(Node::describe))
+    }
+}
+
+/// Two elements that alternate, starting and ending with either one.
+///
+/// The first element is drawn above the main path, the second below it. After
+/// either element the path may end, or loop back to the start of the other
+/// one; the two return rails cross between the elements.
+#[derive(Debug, Clone)]
+pub struct AlternatingSequence<A, B> {
+    first: A,
+    second: B,
+    spacing: i64,
+    attributes: HashMap<String, String>,
+}
+
+/// The vertical positions within an [`AlternatingSequence`], relative to its top.
+struct AlternatingRows {
+    /// The entry of the first element.
+    first: i64,
+    /// The rail returning from the second element to the first.
+    to_first: i64,
+    /// The main path.
+    entry: i64,
+    /// The rail returning from the first element to the second.
+    to_second: i64,
+    /// The entry of the second element.
+    second: i64,
+}
+
+impl<A, B> AlternatingSequence<A, B> {
+    /// Create an `AlternatingSequence` of `first` and `second`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let seq = AlternatingSequence::new(
+    ///     NonTerminal::new("digit".to_owned()),
+    ///     NonTerminal::new("letter".to_owned()),
+    /// );
+    /// assert!(Diagram::new(seq).to_string().contains("class=\"alternatingsequence\""));
+    /// ```
+    pub fn new(first: A, second: B) -> Self {
+        let mut s = Self {
+            first,
+            second,
+            spacing: 10,
+            attributes: HashMap::default(),
+        };
+        s.attributes
+            .insert("class".to_owned(), "alternatingsequence".to_owned());
+        s
+    }
+
+    /// Access an attribute on the main SVG-element that will be drawn.
+    pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
+        self.attributes.entry(key)
+    }
+
+    /// Unwrap this sequence, returning both elements.
+    #[must_use]
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+
+    /// The rows, given the `entry_height` and `height_below_entry` of both elements.
+    fn rows(&self, first: (i64, i64), second: (i64, i64)) -> AlternatingRows {
+        let to_first = first.0 + cmp::max(ARC_RADIUS * 2, first.1 + self.spacing);
+        let to_second = to_first + ARC_RADIUS * 2;
+        AlternatingRows {
+            first: first.0,
+            to_first,
+            entry: to_first + ARC_RADIUS,
+            to_second,
+            second: to_second + cmp::max(ARC_RADIUS * 2, second.0 + self.spacing),
+        }
+    }
+
+    fn width_from(first: i64, second: i64) -> i64 {
+        ARC_RADIUS * 4 + cmp::max(first, second) + ARC_RADIUS * 4
+    }
+}
+
+impl<A, B> Default for AlternatingSequence<A, B>
+where
+    A: Default,
+    B: Default,
+{
+    fn default() -> Self {
+        Self {
+            first: Default::default(),
+            second: Default::default(),
+            spacing: 10,
+            attributes: HashMap::default(),
+        }
+    }
+}
+
+impl<A, B> Node for AlternatingSequence<A, B>
+where
+    A: Node,
+    B: Node,
+{
+    fn entry_height(&self) -> i64 {
+        self.rows(
+            (self.first.entry_height(), self.first.height_below_entry()),
+            (self.second.entry_height(), self.second.height_below_entry()),
+        )
+        .entry
+    }
+
+    fn height(&self) -> i64 {
+        self.rows(
+            (self.first.entry_height(), self.first.height_below_entry()),
+            (self.second.entry_height(), self.second.height_below_entry()),
+        )
+        .second
+            + self.second.height_below_entry()
+    }
+
+    fn width(&self) -> i64 {
+        Self::width_from(self.first.width(), self.second.width())
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
+    }
+
+    fn compute_geometry(&self) -> NodeGeometry {
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let first_geo = self.first.compute_geometry_in(orientation);
+        let second_geo = self.second.compute_geometry_in(orientation);
+        let rows = self.rows(
+            (first_geo.entry_height, first_geo.height_below_entry()),
+            (second_geo.entry_height, second_geo.height_below_entry()),
+        );
+        NodeGeometry {
+            entry_height: rows.entry,
+            height: rows.second + second_geo.height_below_entry(),
+            width: Self::width_from(first_geo.width, second_geo.width),
+            children: vec![first_geo, second_geo],
+            id: None,
+            orientation,
+        }
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        draw_group_with_geometry(
+            &self.attributes,
+            "AlternatingSequence",
+            x,
+            y,
+            geo,
+            |backend| self.emit_with_geometry(backend, x, y, h_dir, geo),
+        )
+    }
+
+    fn render_with_geometry(
+        &self,
+        out: &mut svg::Renderer<'_>,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        render_group_with_geometry(
+            out,
+            &self.attributes,
+            "AlternatingSequence",
+            x,
+            y,
+            geo,
+            |backend| self.emit_with_geometry(backend, x, y, h_dir, geo),
+        )
+    }
+
+    /// Emit both elements, their connectors and the crossing return rails
+    /// through the shared backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let first_geo = &geo.children[0];
+        let second_geo = &geo.children[1];
+        let rows = self.rows(
+            (first_geo.entry_height, first_geo.height_below_entry()),
+            (second_geo.entry_height, second_geo.height_below_entry()),
+        );
+        let inner_width = cmp::max(first_geo.width, second_geo.width);
+        let left = x + ARC_RADIUS * 4;
+        let right = left + inner_width;
+
+        // From the start up to the first element and down to the second.
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, y + rows.entry)
+                .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                .vertical(-(rows.entry - rows.first - ARC_RADIUS * 2))
+                .arc(ARC_RADIUS, svg::Arc::SouthToEast)
+                .horizontal(ARC_RADIUS * 2),
+        )?;
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, y + rows.entry)
+                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                .vertical(rows.second - rows.entry - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::NorthToEast)
+                .horizontal(ARC_RADIUS * 2),
+        )?;
+
+        // From the first element down to the end, and back to the second.
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(left + first_geo.width, y + rows.first)
+                .horizontal(inner_width - first_geo.width + ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                .vertical(rows.entry - rows.first - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::NorthToEast),
+        )?;
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(right, y + rows.first)
+                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                .vertical(rows.to_second - rows.first - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::NorthToWest)
+                .horizontal(-inner_width)
+                .arc(ARC_RADIUS, svg::Arc::EastToSouth)
+                .vertical(rows.second - rows.to_second - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::NorthToEast),
+        )?;
+
+        // From the second element up to the end, and back to the first. The
+        // return rail turns one radius further right, crossing the other one.
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(left + second_geo.width, y + rows.second)
+                .horizontal(inner_width - second_geo.width + ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                .vertical(-(rows.second - rows.entry - ARC_RADIUS * 2))
+                .arc(ARC_RADIUS, svg::Arc::SouthToEast),
+        )?;
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(right, y + rows.second)
+                .horizontal(ARC_RADIUS)
+                .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                .vertical(-(rows.second - rows.to_first - ARC_RADIUS * 2))
+                .arc(ARC_RADIUS, svg::Arc::SouthToWest)
+                .horizontal(-(inner_width + ARC_RADIUS))
+                .arc(ARC_RADIUS, svg::Arc::EastToNorth)
+                .vertical(-(rows.to_first - rows.first - ARC_RADIUS * 2))
+                .arc(ARC_RADIUS, svg::Arc::SouthToEast),
+        )?;
+
+        backend.push_child(
+            &self.first,
+            left,
+            y + rows.first - first_geo.entry_height,
+            h_dir,
+            first_geo,
+        )?;
+        backend.push_child(
+            &self.second,
+            left,
+            y + rows.second - second_geo.entry_height,
+            h_dir,
+            second_geo,
+        )
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("alternatingsequence")
+            .with_child(self.first.describe())
+            .with_child(self.second.describe())
+    }
+}
+
+/// The default marker of an [`Unordered`] container.
+pub(crate) const UNORDERED_MARKER: &str = "any order";
+
+/// A container of elements which all have to be picked once, in any order.
+///
+/// The elements are drawn side by side like a [`Sequence`], inside a frame
+/// carrying a marker (`"any order"` by default) which tells that their order
+/// is free. Optional elements get a bypass path above them.
+#[derive(Debug, Clone)]
+pub struct Unordered<N> {
+    children: Vec<N>,
+    optional: Vec<bool>,
+    marker: String,
+    spacing: i64,
+    padding: i64,
+    attributes: HashMap<String, String>,
+}
+
+impl<N> Unordered<N> {
+    /// Create an `Unordered` container of required elements.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut options = Unordered::new(vec![
+    ///     Terminal::new("--verbose".to_owned()),
+    ///     Terminal::new("--quiet".to_owned()),
+    /// ]);
+    /// options.push_optional(Terminal::new("--color".to_owned()));
+    /// assert!(!options.is_required(2));
+    /// let svg = Diagram::new(options).to_string();
+    /// assert!(svg.contains("class=\"unordered\""));
+    /// assert!(svg.contains("any order"));
+    /// ```
+    #[must_use]
+    pub fn new(children: Vec<N>) -> Self {
+        let mut u = Self {
+            optional: vec![false; children.len()],
+            children,
+            ..Self::default()
+        };
+        u.attributes
+            .insert("class".to_owned(), "unordered".to_owned());
+        u
+    }
+
+    /// Append a required element.
+    pub fn push(&mut self, child: N) {
+        self.children.push(child);
+        self.optional.push(false);
+    }
+
+    /// Append an optional element.
+    pub fn push_optional(&mut self, child: N) {
+        self.children.push(child);
+        self.optional.push(true);
+    }
+
+    /// Set whether the element at `index` has to be picked.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn set_required(&mut self, index: usize, required: bool) {
+        self.optional[index] = !required;
+    }
+
+    /// Whether the element at `index` has to be picked.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    #[must_use]
+    pub fn is_required(&self, index: usize) -> bool {
+        !self.optional[index]
+    }
+
+    /// Set the text of the marker drawn at the top of the frame.
+    pub fn set_marker(&mut self, marker: String) {
+        self.marker = marker;
+    }
+
+    /// The text of the marker drawn at the top of the frame.
+    #[must_use]
+    pub fn marker(&self) -> &str {
+        &self.marker
+    }
+
+    /// Access an attribute on the main SVG-element that will be drawn.
+    pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
+        self.attributes.entry(key)
+    }
+
+    /// Unwrap this container, returning the children in order.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<N> {
+        self.children
+    }
+
+    /// The layout geometry `(entry_height, height, width)` of the marker in
+    /// `orientation`.
+    fn marker_box(&self, orientation: Orientation) -> (i64, i64, i64) {
+        layout::text_box(
+            orientation,
+            10,
+            20,
+            i64::try_from(text_width(&self.marker)).unwrap() * 7 + 10,
+        )
+    }
+
+    /// The `(entry_height, height_below_entry, width)` of an element, given
+    /// that of its child; optional elements are widened by their bypass.
+    fn member(&self, index: usize, (eh, hbe, width): (i64, i64, i64)) -> (i64, i64, i64) {
+        if self.optional[index] {
+            (
+                ARC_RADIUS + cmp::max(ARC_RADIUS, eh),
+                hbe,
+                ARC_RADIUS * 2 + width + ARC_RADIUS * 2,
+            )
+        } else {
+            (eh, hbe, width)
+        }
+    }
+
+    /// The `(entry_height, height, width)` of this container in `orientation`,
+    /// given the `(entry_height, height_below_entry, width)` of every element.
+    fn extents(&self, orientation: Orientation, members: &[(i64, i64, i64)]) -> (i64, i64, i64) {
+        let (_, marker_height, marker_width) = self.marker_box(orientation);
+        let entry_height = self.padding
+            + marker_height
+            + self.padding
+            + members.iter().map(|m| m.0).max().unwrap_or(0);
+        let height_below_entry = members.iter().map(|m| m.1).max().unwrap_or(0) + self.padding;
+        (
+            entry_height,
+            entry_height + height_below_entry,
+            self.padding
+                + cmp::max(
+                    marker_width,
+                    ARC_RADIUS + self.row_width(members) + ARC_RADIUS,
+                )
+                + self.padding,
+        )
+    }
+
+    /// The width of the elements side by side.
+    fn row_width(&self, members: &[(i64, i64, i64)]) -> i64 {
+        let gaps = i64::try_from(members.len().saturating_sub(1)).unwrap();
+        members.iter().map(|m| m.2).sum::<i64>() + gaps * self.spacing
+    }
+
+    fn members(&self) -> Vec<(i64, i64, i64)>
+    where
+        N: Node,
+    {
+        self.children
+            .iter()
+            .enumerate()
+            .map(|(i, c)| self.member(i, (c.entry_height(), c.height_below_entry(), c.width())))
+            .collect()
+    }
+}
+
+impl<N> iter::FromIterator<N> for Unordered<N> {
+    fn from_iter<T: IntoIterator<Item = N>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<N> Default for Unordered<N> {
+    fn default() -> Self {
+        Self {
+            children: Vec::default(),
+            optional: Vec::default(),
+            marker: UNORDERED_MARKER.to_owned(),
+            spacing: 10,
+            padding: 8,
+            attributes: HashMap::default(),
+        }
+    }
+}
+
+impl<N> Node for Unordered<N>
+where
+    N: Node,
+{
+    fn entry_height(&self) -> i64 {
+        self.extents(Orientation::Horizontal, &self.members()).0
+    }
+
+    fn height(&self) -> i64 {
+        self.extents(Orientation::Horizontal, &self.members()).1
+    }
+
+    fn width(&self) -> i64 {
+        self.extents(Orientation::Horizontal, &self.members()).2
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
+    }
+
+    fn compute_geometry(&self) -> NodeGeometry {
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .children
+            .iter()
+            .map(|c| c.compute_geometry_in(orientation))
+            .collect();
+        let members: Vec<(i64, i64, i64)> = children
+            .iter()
+            .enumerate()
+            .map(|(i, g)| self.member(i, (g.entry_height, g.height_below_entry(), g.width)))
+            .collect();
+        let (entry_height, height, width) = self.extents(orientation, &members);
+        NodeGeometry {
+            entry_height,
+            height,
+            width,
+            children,
+            id: None,
+            orientation,
+        }
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        draw_group_with_geometry(&self.attributes, "Unordered", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
+
+    fn render_with_geometry(
+        &self,
+        out: &mut svg::Renderer<'_>,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        render_group_with_geometry(out, &self.attributes, "Unordered", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
+
+    /// Emit the frame, the marker, the elements and their connectors through
+    /// the shared backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let members: Vec<(i64, i64, i64)> = geo
+            .children
+            .iter()
+            .enumerate()
+            .map(|(i, g)| self.member(i, (g.entry_height, g.height_below_entry(), g.width)))
+            .collect();
+        let (marker_entry, _, marker_width) = self.marker_box(geo.orientation);
+        let entry_y = y + geo.entry_height;
+        let left = x + self.padding + ARC_RADIUS;
+        let row_width = self.row_width(&members);
+
+        backend.push_rect(x, y, geo.width, geo.height)?;
+        backend.push_text(
+            x + self.padding + marker_width / 2,
+            y + self.padding + marker_entry + 5,
+            &self.marker,
+        )?;
+
+        // The main path, leaving gaps for the elements.
+        let mut path = svg::PathData::new(h_dir)
+            .move_to(x, entry_y)
+            .horizontal(left - x);
+        for (i, &(_, _, width)) in members.iter().enumerate() {
+            if i > 0 {
+                path = path.horizontal(self.spacing);
+            }
+            path = path.move_rel(width, 0);
+        }
+        backend.push_path(path.horizontal(x + geo.width - left - row_width))?;
+
+        let mut member_x = left;
+        for (i, ((child, child_geo), &(_, _, width))) in self
+            .children
+            .iter()
+            .zip(&geo.children)
+            .zip(&members)
+            .enumerate()
+        {
+            if self.optional[i] {
+                backend.push_path(
+                    svg::PathData::new(h_dir)
+                        .move_to(member_x, entry_y)
+                        .horizontal(ARC_RADIUS * 2)
+                        .move_rel(-ARC_RADIUS * 2, 0)
+                        .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+                        .vertical(cmp::min(0, -child_geo.entry_height + ARC_RADIUS))
+                        .arc(ARC_RADIUS, svg::Arc::SouthToEast)
+                        .horizontal(child_geo.width)
+                        .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                        .vertical(cmp::max(0, child_geo.entry_height - ARC_RADIUS))
+                        .arc(ARC_RADIUS, svg::Arc::NorthToEast)
+                        .horizontal(-ARC_RADIUS * 2),
+                )?;
+                backend.push_child(
+                    child,
+                    member_x + ARC_RADIUS * 2,
+                    entry_y - child_geo.entry_height,
+                    h_dir,
+                    child_geo,
+                )?;
+            } else {
+                backend.push_child(
+                    child,
+                    member_x,
+                    entry_y - child_geo.entry_height,
+                    h_dir,
+                    child_geo,
+                )?;
+            }
+            member_x += width + self.spacing;
+        }
+        Ok(())
     }
 
-    fn render_with_geometry(
-        &self,
-        out: &mut svg::Renderer<'_>,
-        x: i64,
-        y: i64,
-        h_dir: HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result {
-        render_group_with_geometry(out, &self.attributes, "Choice", x, y, geo, |backend| {
-            self.emit_with_geometry(backend, x, y, h_dir, geo)
-        })
+    fn describe(&self) -> Description {
+        let mut desc = Description::new("unordered");
+        if self.optional.contains(&true) {
+            let optional: Vec<String> = self
+                .optional
+                .iter()
+                .enumerate()
+                .filter(|&(_, &o)| o)
+                .map(|(i, _)| i.to_string())
+                .collect();
+            desc = desc.with_property("optional", optional.join(","));
+        }
+        if self.marker != UNORDERED_MARKER {
+            desc = desc.with_property("marker", self.marker.clone());
+        }
+        desc.with_children(self.children.iter().map(Node::describe))
     }
 }
 
@@ -950,6 +2334,7 @@ pub struct MultiChoice<N> {
     columns: Vec<Vec<N>>,
     spacing: i64,
     column_spacing: i64,
+    column_alignments: Vec<Alignment>,
     attributes: HashMap<String, String>,
 }
 
@@ -974,6 +2359,35 @@ impl<N> MultiChoice<N> {
         self.columns.push(column);
     }
 
+    /// Set how alternatives narrower than the widest one in the column at
+    /// `index` are placed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut choice = MultiChoice::new(vec![
+    ///     vec![Terminal::new("A".to_owned()), Terminal::new("BBBBBB".to_owned())],
+    ///     vec![Terminal::new("C".to_owned()), Terminal::new("DDDDDD".to_owned())],
+    /// ]);
+    /// choice.set_column_alignment(1, Alignment::Right);
+    /// assert!(Diagram::new(choice).to_string().starts_with("<svg"));
+    /// ```
+    pub fn set_column_alignment(&mut self, index: usize, alignment: Alignment) {
+        if self.column_alignments.len() <= index {
+            self.column_alignments
+                .resize(index + 1, Alignment::default());
+        }
+        self.column_alignments[index] = alignment;
+    }
+
+    fn column_alignment(&self, index: usize) -> Alignment {
+        self.column_alignments
+            .get(index)
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Access an attribute on the main SVG-element that will be drawn.
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
@@ -1097,8 +2511,8 @@ impl<N> MultiChoice<N> {
         }
     }
 
-    fn emit_sectioned_vertical<B: RenderBackend>(
-        backend: &mut B,
+    fn emit_sectioned_vertical(
+        backend: &mut dyn RenderBackend,
         h_dir: HDir,
         x: i64,
         start_y: i64,
@@ -1124,8 +2538,8 @@ impl<N> MultiChoice<N> {
         Ok(())
     }
 
-    fn emit_sectioned_horizontal<B: RenderBackend>(
-        backend: &mut B,
+    fn emit_sectioned_horizontal(
+        backend: &mut dyn RenderBackend,
         h_dir: HDir,
         y: i64,
         start_x: i64,
@@ -1149,8 +2563,8 @@ impl<N> MultiChoice<N> {
     }
 
     #[allow(clippy::too_many_arguments)]
-    fn emit_incoming_column_spine<B: RenderBackend>(
-        backend: &mut B,
+    fn emit_incoming_column_spine(
+        backend: &mut dyn RenderBackend,
         h_dir: HDir,
         column_x: i64,
         branch_x: i64,
@@ -1184,19 +2598,137 @@ impl<N> MultiChoice<N> {
             )
         }
     }
+}
+
+impl<N> iter::FromIterator<Vec<N>> for MultiChoice<N> {
+    fn from_iter<T: IntoIterator<Item = Vec<N>>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<N> Default for MultiChoice<N> {
+    fn default() -> Self {
+        Self {
+            columns: Vec::default(),
+            spacing: 10,
+            column_spacing: ARC_RADIUS,
+            column_alignments: Vec::default(),
+            attributes: HashMap::default(),
+        }
+    }
+}
+
+impl<N> Node for MultiChoice<N>
+where
+    N: Node,
+{
+    fn entry_height(&self) -> i64 {
+        self.compute_geometry().entry_height
+    }
+
+    fn height(&self) -> i64 {
+        self.compute_geometry().height
+    }
+
+    fn width(&self) -> i64 {
+        self.compute_geometry().width
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
+    }
+
+    fn compute_geometry(&self) -> NodeGeometry {
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .columns
+            .iter()
+            .flat_map(|column| {
+                column
+                    .iter()
+                    .map(|child| child.compute_geometry_in(orientation))
+            })
+            .collect();
+        let layout = self.build_layout(&children);
+
+        // Empty case: match empty Choice geometry.
+        if layout.columns.is_empty() {
+            return NodeGeometry {
+                entry_height: 0,
+                height: 0,
+                width: 0,
+                children,
+                id: None,
+                orientation,
+            };
+        }
+
+        // Single-column case: keep geometry compatible with Choice.
+        if layout.columns.len() == 1 {
+            let column = &layout.columns[0];
+            return NodeGeometry {
+                entry_height: column.entry_height,
+                height: column.height,
+                width: column.width,
+                children,
+                id: None,
+                orientation,
+            };
+        }
+
+        // Multi-column case: reserve top space for cross-column entry routes and
+        // a right gutter for alternatives that must route around later columns.
+        let width = layout.columns.last().map_or(0, |column| {
+            column.x_offset + column.width + layout.exit_gutter
+        });
+        let max_column_bottom = layout
+            .columns
+            .iter()
+            .map(|column| column.y_offset + column.height)
+            .max()
+            .unwrap_or(0);
+        NodeGeometry {
+            entry_height: layout.top_padding + layout.columns[0].entry_height,
+            height: max_column_bottom + ARC_RADIUS * 2,
+            width,
+            children,
+            id: None,
+            orientation,
+        }
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        draw_group_with_geometry(&self.attributes, "MultiChoice", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
+
+    fn render_with_geometry(
+        &self,
+        out: &mut svg::Renderer<'_>,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        render_group_with_geometry(out, &self.attributes, "MultiChoice", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
 
     /// Emit all branches and their connecting routes through the shared backend.
-    fn emit_with_geometry<B: RenderBackend>(
+    fn emit_with_geometry(
         &self,
-        backend: &mut B,
+        backend: &mut dyn RenderBackend,
         x: i64,
         y: i64,
         h_dir: HDir,
         geo: &NodeGeometry,
-    ) -> fmt::Result
-    where
-        N: Node,
-    {
+    ) -> fmt::Result {
         let layout = self.build_layout(&geo.children);
 
         // Empty case: preserve Choice-compatible geometry and draw a zero-length path.
@@ -1245,7 +2777,7 @@ impl<N> MultiChoice<N> {
             }
         }
 
-        for column in &self.columns {
+        for (column_index, column) in self.columns.iter().enumerate() {
             let flat_end = flat_index + column.len();
             let Some(column_layout) = layout
                 .columns
@@ -1270,24 +2802,31 @@ impl<N> MultiChoice<N> {
                 column_layout.flat_start == layout.columns.last().unwrap().flat_start;
             let route_x = column_x + column_layout.width;
             let mut column_merge_starts = Vec::new();
+            let alignment = self.column_alignment(column_index);
+            let offset = |child_geo: &NodeGeometry| {
+                alignment.offset(column_layout.max_child_width - child_geo.width)
+            };
+            let first_offset = offset(&geo.children[flat_index]);
 
             // First active column: the node entry splits downward directly.
             if column_layout.flat_start == layout.columns[0].flat_start {
                 backend.push_path(
                     svg::PathData::new(h_dir)
                         .move_to(x, exit_y)
-                        .horizontal(child_x - x),
+                        .horizontal(child_x + first_offset - x),
                 )?;
             // Later columns: route from the node entry above the first column, then branch down.
             } else {
                 let top_y = y + ARC_RADIUS;
-                backend.push_path(
-                    svg::PathData::new(h_dir)
-                        .move_to(branch_x - ARC_RADIUS, top_y)
-                        .arc(ARC_RADIUS, svg::Arc::WestToSouth)
-                        .vertical(column_entry_y - top_y - ARC_RADIUS * 2)
-                        .arc(ARC_RADIUS, svg::Arc::NorthToEast),
-                )?;
+                let mut path = svg::PathData::new(h_dir)
+                    .move_to(branch_x - ARC_RADIUS, top_y)
+                    .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                    .vertical(column_entry_y - top_y - ARC_RADIUS * 2)
+                    .arc(ARC_RADIUS, svg::Arc::NorthToEast);
+                if first_offset > 0 {
+                    path = path.horizontal(first_offset);
+                }
+                backend.push_path(path)?;
             }
 
             // Multi-row column: add the vertical branch spine, mirroring Choice-style rows.
@@ -1318,6 +2857,7 @@ impl<N> MultiChoice<N> {
                 let child_geo = &geo.children[flat_index + row_index];
                 let child_y = column_y + column_layout.child_y_offsets[row_index];
                 let child_entry_y = child_y + child_geo.entry_height;
+                let child_offset = offset(child_geo);
 
                 // Non-first rows enter from the column branch spine.
                 if row_index > 0 {
@@ -1325,13 +2865,13 @@ impl<N> MultiChoice<N> {
                         svg::PathData::new(h_dir)
                             .move_to(branch_x, child_entry_y - ARC_RADIUS)
                             .arc(ARC_RADIUS, svg::Arc::NorthToEast)
-                            .horizontal(child_x - branch_x - ARC_RADIUS),
+                            .horizontal(child_x + child_offset - branch_x - ARC_RADIUS),
                     )?;
                 }
 
-                backend.push_child(child, child_x, child_y, h_dir, child_geo)?;
+                backend.push_child(child, child_x + child_offset, child_y, h_dir, child_geo)?;
 
-                let child_right_x = child_x + child_geo.width;
+                let child_right_x = child_x + child_offset + child_geo.width;
                 let padded_right_x = child_x + column_layout.max_child_width;
 
                 // Single active column: exit exactly like Choice, straight to this node's end.
@@ -1455,110 +2995,14 @@ impl<N> MultiChoice<N> {
 
         Ok(())
     }
-}
-
-impl<N> iter::FromIterator<Vec<N>> for MultiChoice<N> {
-    fn from_iter<T: IntoIterator<Item = Vec<N>>>(iter: T) -> Self {
-        Self::new(iter.into_iter().collect())
-    }
-}
-
-impl<N> Default for MultiChoice<N> {
-    fn default() -> Self {
-        Self {
-            columns: Vec::default(),
-            spacing: 10,
-            column_spacing: ARC_RADIUS,
-            attributes: HashMap::default(),
-        }
-    }
-}
-
-impl<N> Node for MultiChoice<N>
-where
-    N: Node,
-{
-    fn entry_height(&self) -> i64 {
-        self.compute_geometry().entry_height
-    }
-
-    fn height(&self) -> i64 {
-        self.compute_geometry().height
-    }
-
-    fn width(&self) -> i64 {
-        self.compute_geometry().width
-    }
-
-    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
-        let geo = self.compute_geometry();
-        self.draw_with_geometry(x, y, h_dir, &geo)
-    }
 
-    fn compute_geometry(&self) -> NodeGeometry {
-        let children: Vec<NodeGeometry> = self
-            .columns
-            .iter()
-            .flat_map(|column| column.iter().map(|child| child.compute_geometry()))
-            .collect();
-        let layout = self.build_layout(&children);
-
-        // Empty case: match empty Choice geometry.
-        if layout.columns.is_empty() {
-            return NodeGeometry {
-                entry_height: 0,
-                height: 0,
-                width: 0,
-                children,
-            };
-        }
-
-        // Single-column case: keep geometry compatible with Choice.
-        if layout.columns.len() == 1 {
-            let column = &layout.columns[0];
-            return NodeGeometry {
-                entry_height: column.entry_height,
-                height: column.height,
-                width: column.width,
-                children,
-            };
-        }
-
-        // Multi-column case: reserve top space for cross-column entry routes and
-        // a right gutter for alternatives that must route around later columns.
-        let width = layout.columns.last().map_or(0, |column| {
-            column.x_offset + column.width + layout.exit_gutter
-        });
-        let max_column_bottom = layout
-            .columns
-            .iter()
-            .map(|column| column.y_offset + column.height)
-            .max()
-            .unwrap_or(0);
-        NodeGeometry {
-            entry_height: layout.top_padding + layout.columns[0].entry_height,
-            height: max_column_bottom + ARC_RADIUS * 2,
-            width,
-            children,
-        }
-    }
-
-    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
-        draw_group_with_geometry(&self.attributes, "MultiChoice", x, y, geo, |backend| {
-            self.emit_with_geometry(backend, x, y, h_dir, geo)
-        })
-    }
-
-    fn render_with_geometry(
-        &self,
-        out: &mut svg::Renderer<'_>,
-        x: i64,
-        y: i64,
-        h_dir: HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result {
-        render_group_with_geometry(out, &self.attributes, "MultiChoice", x, y, geo, |backend| {
-            self.emit_with_geometry(backend, x, y, h_dir, geo)
-        })
+    fn describe(&self) -> Description {
+        Description::new("multichoice").with_children(self.columns.iter().enumerate().map(
+            |(index, column)| {
+                self.column_alignment(index)
+                    .describe(Description::new("column"))
+                    .with_children(column.iter().map(Node::describe))
+            },
+        ))
     }
 }
@@ -5,7 +5,8 @@ use std::{
 };
 
 use crate::{
-    HDir, Node, NodeGeometry, RenderBackend, draw_group_with_geometry, render_group_with_geometry,
+    HDir, Node, NodeGeometry, Orientation, RenderBackend, description::Description,
+    draw_group_with_geometry, render_group_with_geometry,
 };
 
 /// A vertical group of unconnected elements.
@@ -48,26 +49,6 @@ impl<N> VerticalGrid<N> {
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
     }
-
-    /// Emit all children in top-to-bottom order using cached geometry.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        h_dir: crate::svg::HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result
-    where
-        N: Node,
-    {
-        let mut running_y = y;
-        for (child, child_geo) in self.children.iter().zip(geo.children.iter()) {
-            backend.push_child(child, x, running_y, h_dir, child_geo)?;
-            running_y += child_geo.height + self.spacing;
-        }
-        Ok(())
-    }
 }
 
 impl<N> Default for VerticalGrid<N> {
@@ -111,8 +92,15 @@ impl<N: Node> Node for VerticalGrid<N> {
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let children: Vec<NodeGeometry> =
-            self.children.iter().map(|c| c.compute_geometry()).collect();
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .children
+            .iter()
+            .map(|c| c.compute_geometry_in(orientation))
+            .collect();
         let total_height: i64 = children.iter().map(|g| g.height).sum();
         let n = cmp::max(1, i64::try_from(children.len()).unwrap());
         let height = total_height + (n - 1) * self.spacing;
@@ -122,6 +110,8 @@ impl<N: Node> Node for VerticalGrid<N> {
             height,
             width,
             children,
+            id: None,
+            orientation,
         }
     }
 
@@ -155,6 +145,27 @@ impl<N: Node> Node for VerticalGrid<N> {
             |backend| self.emit_with_geometry(backend, x, y, h_dir, geo),
         )
     }
+
+    /// Emit all children in top-to-bottom order using cached geometry.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: crate::svg::HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let mut running_y = y;
+        for (child, child_geo) in self.children.iter().zip(geo.children.iter()) {
+            backend.push_child(child, x, running_y, h_dir, child_geo)?;
+            running_y += child_geo.height + self.spacing;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("verticalgrid").with_children(self.children.iter().map(Node::describe))
+    }
 }
 
 /// A horizontal group of unconnected elements.
@@ -197,26 +208,6 @@ impl<N> HorizontalGrid<N> {
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
     }
-
-    /// Emit all children in left-to-right order using cached geometry.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        h_dir: HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result
-    where
-        N: Node,
-    {
-        let mut running_x = x;
-        for (child, child_geo) in self.children.iter().zip(geo.children.iter()) {
-            backend.push_child(child, running_x, y, h_dir, child_geo)?;
-            running_x += child_geo.width + self.spacing;
-        }
-        Ok(())
-    }
 }
 
 impl<N> Default for HorizontalGrid<N> {
@@ -263,8 +254,15 @@ where
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let children: Vec<NodeGeometry> =
-            self.children.iter().map(|c| c.compute_geometry()).collect();
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let children: Vec<NodeGeometry> = self
+            .children
+            .iter()
+            .map(|c| c.compute_geometry_in(orientation))
+            .collect();
         let height = children.iter().map(|g| g.height).max().unwrap_or(0);
         let total_width: i64 = children.iter().map(|g| g.width).sum();
         let n = cmp::max(1, i64::try_from(children.len()).unwrap());
@@ -274,6 +272,8 @@ where
             height,
             width,
             children,
+            id: None,
+            orientation,
         }
     }
 
@@ -307,4 +307,25 @@ where
             |backend| self.emit_with_geometry(backend, x, y, h_dir, geo),
         )
     }
+
+    /// Emit all children in left-to-right order using cached geometry.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let mut running_x = x;
+        for (child, child_geo) in self.children.iter().zip(geo.children.iter()) {
+            backend.push_child(child, running_x, y, h_dir, child_geo)?;
+            running_x += child_geo.width + self.spacing;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("horizontalgrid").with_children(self.children.iter().map(Node::describe))
+    }
 }
@@ -4,14 +4,118 @@ use std::{
 };
 
 use crate::{
-    HDir, Node, NodeGeometry, RenderBackend, emit_text_box, render_group_with_geometry, svg,
-    text_width,
+    HDir, Node, NodeGeometry, Orientation, RenderBackend, RichText, SourceSpan,
+    description::Description, emit_text_box, layout, render_group_with_geometry, rich_text::Line,
+    svg,
 };
 
+/// How the lines of some text are laid out in a box.
+struct TextStyle {
+    /// The height of a box holding a single line.
+    height: i64,
+    /// The distance between the baselines of two lines.
+    line_height: i64,
+    /// The width of a single character.
+    char_width: i64,
+    /// The horizontal space around the longest line.
+    padding: i64,
+}
+
+impl TextStyle {
+    /// The layout geometry `(entry_height, height, width)` of a box holding
+    /// `lines` in `orientation`.
+    ///
+    /// The path enters halfway down the box, which is the middle line if there
+    /// is an odd number of lines.
+    fn text_box(&self, orientation: Orientation, lines: &[Line<'_>]) -> (i64, i64, i64) {
+        let height =
+            self.height + i64::try_from(lines.len().saturating_sub(1)).unwrap() * self.line_height;
+        let width = lines
+            .iter()
+            .map(|line| RichText::line_width(line, self.char_width))
+            .max()
+            .unwrap_or(0);
+        layout::text_box(orientation, height / 2, height, width + self.padding)
+    }
+}
+
+/// The geometry of a text node, whose box is laid out as `text_box` in
+/// `orientation`.
+fn text_geometry(
+    (entry_height, height, width): (i64, i64, i64),
+    orientation: Orientation,
+) -> NodeGeometry {
+    NodeGeometry {
+        entry_height,
+        height,
+        width,
+        children: vec![],
+        id: None,
+        orientation,
+    }
+}
+
+/// The style of labels in `Terminal`s and `NonTerminal`s.
+const LABEL: TextStyle = TextStyle {
+    height: 22,
+    line_height: 16,
+    char_width: 8,
+    padding: 20,
+};
+
+/// The style of `Comment`s.
+const COMMENT: TextStyle = TextStyle {
+    height: 20,
+    line_height: 14,
+    char_width: 7,
+    padding: 10,
+};
+
+/// The prefix of the attributes a [`SourceSpan`] is written to.
+const SOURCE_ATTRIBUTE_PREFIX: &str = "data-source-";
+
+/// Replace the attributes written for a previous source span by the ones for `span`.
+fn set_source_attributes(attributes: &mut HashMap<String, String>, span: Option<&SourceSpan>) {
+    attributes.retain(|key, _| !key.starts_with(SOURCE_ATTRIBUTE_PREFIX));
+    attributes.extend(
+        span.into_iter()
+            .flat_map(|s| s.properties(SOURCE_ATTRIBUTE_PREFIX)),
+    );
+}
+
+/// Describe a text node of the given `kind`, whose `text` is stored as the
+/// property `key`; styled spans become children of kind `"span"`.
+fn describe_text(
+    kind: &str,
+    key: &str,
+    text: &RichText,
+    max_line_width: Option<usize>,
+    source_span: Option<&SourceSpan>,
+) -> Description {
+    let mut desc = Description::new(kind).with_property(key, text.to_plain_text());
+    if let Some(max_line_width) = max_line_width {
+        desc = desc.with_property("max_line_width", max_line_width.to_string());
+    }
+    for (key, value) in source_span
+        .into_iter()
+        .flat_map(|s| s.properties("source_"))
+    {
+        desc = desc.with_property(key, value);
+    }
+    if !text.is_plain() {
+        for span in text.spans() {
+            desc = desc.with_child(span.describe());
+        }
+    }
+    desc
+}
+
 /// A `Terminal`-symbol, drawn as a rectangle with rounded corners.
 #[derive(Debug, Clone)]
 pub struct Terminal {
-    label: String,
+    label: RichText,
+    max_line_width: Option<usize>,
+    source_span: Option<SourceSpan>,
     attributes: HashMap<String, String>,
 }
 
@@ -19,6 +123,7 @@ impl Terminal {
     /// Construct a `Terminal` with the given visible label.
     ///
     /// The label is HTML-escaped when rendered, so arbitrary text is safe to pass.
+    /// Every `'\n'` in it starts a new line.
     ///
     /// # Example
     /// ```rust
@@ -29,8 +134,27 @@ impl Terminal {
     /// ```
     #[must_use]
     pub fn new(label: String) -> Self {
+        Self::with_rich_label(RichText::from(label))
+    }
+
+    /// Construct a `Terminal` whose label is made of styled spans.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let t = Terminal::with_rich_label(RichText::new(vec![
+    ///     Span::new("x"),
+    ///     Span::subscript("1"),
+    /// ]));
+    /// assert!(Diagram::new(t).to_string().contains(">1</tspan>"));
+    /// ```
+    #[must_use]
+    pub fn with_rich_label(label: RichText) -> Self {
         let mut t = Self {
             label,
+            max_line_width: None,
+            source_span: None,
             attributes: HashMap::default(),
         };
         t.attributes
@@ -43,43 +167,77 @@ impl Terminal {
         self.attributes.entry(key)
     }
 
-    /// Emit the terminal box and centered label through the chosen backend.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        geo: &NodeGeometry,
-    ) -> fmt::Result {
-        emit_text_box(backend, x, y, geo, &self.label, true)
+    /// Wrap the label into lines of at most `max_line_width` columns, breaking
+    /// between words; `None`, the default, only breaks at explicit line breaks.
+    pub fn set_max_line_width(&mut self, max_line_width: Option<usize>) {
+        self.max_line_width = max_line_width;
+    }
+
+    /// The width, in columns, at which the label is wrapped.
+    #[must_use]
+    pub fn max_line_width(&self) -> Option<usize> {
+        self.max_line_width
+    }
+
+    /// Record where this node was defined in the text it was imported from,
+    /// written to the SVG as `data-source-*` attributes; see [`SourceSpan`].
+    pub fn set_source_span(&mut self, source_span: Option<SourceSpan>) {
+        set_source_attributes(&mut self.attributes, source_span.as_ref());
+        self.source_span = source_span;
+    }
+
+    /// Where this node was defined, if known.
+    #[must_use]
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        self.source_span.as_ref()
+    }
+
+    fn lines(&self) -> Vec<Line<'_>> {
+        self.label.lines(self.max_line_width)
+    }
+
+    /// The layout geometry `(entry_height, height, width)` in `orientation`.
+    fn text_box(&self, orientation: Orientation) -> (i64, i64, i64) {
+        LABEL.text_box(orientation, &self.lines())
     }
 }
 
 impl Node for Terminal {
     fn entry_height(&self) -> i64 {
-        11
+        self.text_box(Orientation::Horizontal).0
     }
     fn height(&self) -> i64 {
-        self.entry_height() * 2
+        self.text_box(Orientation::Horizontal).1
     }
     fn width(&self) -> i64 {
-        i64::try_from(text_width(&self.label)).unwrap() * 8 + 20
+        self.text_box(Orientation::Horizontal).2
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        text_geometry(self.text_box(orientation), orientation)
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        self.draw_with_geometry(x, y, h_dir, &self.compute_geometry())
     }
 
-    fn draw(&self, x: i64, y: i64, _: HDir) -> svg::Element {
+    fn draw_with_geometry(&self, x: i64, y: i64, _: HDir, geo: &NodeGeometry) -> svg::Element {
         let r = svg::Element::new("rect")
             .set("x", &x)
             .set("y", &y)
-            .set("height", &self.height())
-            .set("width", &self.width())
+            .set("height", &geo.height)
+            .set("width", &geo.width)
             .set("rx", &10)
             .set("ry", &10);
-        let t = svg::Element::new("text")
-            .set("x", &(x + self.width() / 2))
-            .set("y", &(y + self.entry_height() + 5))
-            .text(&self.label);
+        let t = layout::place_text_lines(
+            svg::Element::new("text"),
+            x + geo.width / 2,
+            y + geo.entry_height + 5,
+            &self.lines(),
+            LABEL.line_height,
+        );
         svg::Element::new("g")
-            .debug("terminal", x, y, self)
+            .debug_with_geometry("terminal", x, y, geo)
             .set_all(self.attributes.iter())
             .add(r)
             .add(t)
@@ -90,19 +248,43 @@ impl Node for Terminal {
         out: &mut svg::Renderer<'_>,
         x: i64,
         y: i64,
-        _h_dir: HDir,
+        h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
         render_group_with_geometry(out, &self.attributes, "terminal", x, y, geo, |backend| {
-            self.emit_with_geometry(backend, x, y, geo)
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
         })
     }
+
+    /// Emit the terminal box and centered label through the chosen backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        _h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        emit_text_box(backend, x, y, geo, &self.lines(), LABEL.line_height, true)
+    }
+
+    fn describe(&self) -> Description {
+        describe_text(
+            "terminal",
+            "label",
+            &self.label,
+            self.max_line_width,
+            self.source_span.as_ref(),
+        )
+    }
 }
 
 /// A `NonTerminal`, drawn as a rectangle.
 #[derive(Debug, Clone)]
 pub struct NonTerminal {
-    label: String,
+    label: RichText,
+    max_line_width: Option<usize>,
+    source_span: Option<SourceSpan>,
     attributes: HashMap<String, String>,
 }
 
@@ -110,6 +292,7 @@ impl NonTerminal {
     /// Construct a `NonTerminal` with the given visible label.
     ///
     /// The label is HTML-escaped when rendered, so arbitrary text is safe to pass.
+    /// Every `'\n'` in it starts a new line.
     ///
     /// # Example
     /// ```rust
@@ -120,8 +303,16 @@ impl NonTerminal {
     /// ```
     #[must_use]
     pub fn new(label: String) -> Self {
+        Self::with_rich_label(RichText::from(label))
+    }
+
+    /// Construct a `NonTerminal` whose label is made of styled spans.
+    #[must_use]
+    pub fn with_rich_label(label: RichText) -> Self {
         let mut nt = Self {
             label,
+            max_line_width: None,
+            source_span: None,
             attributes: HashMap::default(),
         };
         nt.attributes
@@ -134,46 +325,78 @@ impl NonTerminal {
         self.attributes.entry(key)
     }
 
-    /// Emit the non-terminal box and centered label through the chosen backend.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        geo: &NodeGeometry,
-    ) -> fmt::Result {
-        emit_text_box(backend, x, y, geo, &self.label, false)
+    /// Wrap the label into lines of at most `max_line_width` columns, breaking
+    /// between words; `None`, the default, only breaks at explicit line breaks.
+    pub fn set_max_line_width(&mut self, max_line_width: Option<usize>) {
+        self.max_line_width = max_line_width;
+    }
+
+    /// The width, in columns, at which the label is wrapped.
+    #[must_use]
+    pub fn max_line_width(&self) -> Option<usize> {
+        self.max_line_width
+    }
+
+    /// Record where this node was defined in the text it was imported from,
+    /// written to the SVG as `data-source-*` attributes; see [`SourceSpan`].
+    pub fn set_source_span(&mut self, source_span: Option<SourceSpan>) {
+        set_source_attributes(&mut self.attributes, source_span.as_ref());
+        self.source_span = source_span;
+    }
+
+    /// Where this node was defined, if known.
+    #[must_use]
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        self.source_span.as_ref()
+    }
+
+    fn lines(&self) -> Vec<Line<'_>> {
+        self.label.lines(self.max_line_width)
+    }
+
+    /// The layout geometry `(entry_height, height, width)` in `orientation`.
+    fn text_box(&self, orientation: Orientation) -> (i64, i64, i64) {
+        LABEL.text_box(orientation, &self.lines())
     }
 }
 
 impl Node for NonTerminal {
     fn entry_height(&self) -> i64 {
-        11
+        self.text_box(Orientation::Horizontal).0
     }
     fn height(&self) -> i64 {
-        self.entry_height() * 2
+        self.text_box(Orientation::Horizontal).1
     }
     fn width(&self) -> i64 {
-        i64::try_from(text_width(&self.label)).unwrap() * 8 + 20
+        self.text_box(Orientation::Horizontal).2
     }
 
-    fn draw(&self, x: i64, y: i64, _: HDir) -> svg::Element {
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        text_geometry(self.text_box(orientation), orientation)
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        self.draw_with_geometry(x, y, h_dir, &self.compute_geometry())
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, _: HDir, geo: &NodeGeometry) -> svg::Element {
         svg::Element::new("g")
-            .debug("NonTerminal", x, y, self)
+            .debug_with_geometry("NonTerminal", x, y, geo)
             .set_all(self.attributes.iter())
             .add(
                 svg::Element::new("rect")
                     .set("x", &x)
                     .set("y", &y)
-                    .set("height", &self.height())
-                    .set("width", &self.width()),
-            )
-            .add(
-                svg::Element::new("text")
-                    .set("x", &(x + self.width() / 2))
-                    .set("y", &(y + self.entry_height() + 5))
-                    .text(&self.label),
+                    .set("height", &geo.height)
+                    .set("width", &geo.width),
             )
+            .add(layout::place_text_lines(
+                svg::Element::new("text"),
+                x + geo.width / 2,
+                y + geo.entry_height + 5,
+                &self.lines(),
+                LABEL.line_height,
+            ))
     }
 
     fn render_with_geometry(
@@ -181,13 +404,35 @@ impl Node for NonTerminal {
         out: &mut svg::Renderer<'_>,
         x: i64,
         y: i64,
-        _h_dir: HDir,
+        h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
         render_group_with_geometry(out, &self.attributes, "NonTerminal", x, y, geo, |backend| {
-            self.emit_with_geometry(backend, x, y, geo)
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
         })
     }
+
+    /// Emit the non-terminal box and centered label through the chosen backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        _h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        emit_text_box(backend, x, y, geo, &self.lines(), LABEL.line_height, false)
+    }
+
+    fn describe(&self) -> Description {
+        describe_text(
+            "nonterminal",
+            "label",
+            &self.label,
+            self.max_line_width,
+            self.source_span.as_ref(),
+        )
+    }
 }
 
 /// A label / verbatim text drawn inline on the connecting path.
@@ -196,7 +441,9 @@ impl Node for NonTerminal {
 /// within a [`crate::Sequence`].
 #[derive(Debug, Clone)]
 pub struct Comment {
-    text: String,
+    text: RichText,
+    max_line_width: Option<usize>,
+    source_span: Option<SourceSpan>,
     attributes: HashMap<String, String>,
 }
 
@@ -204,6 +451,7 @@ impl Comment {
     /// Construct a `Comment` with the given text.
     ///
     /// The text is HTML-escaped when rendered, so arbitrary strings are safe to pass.
+    /// Every `'\n'` in it starts a new line.
     ///
     /// # Example
     /// ```rust
@@ -214,8 +462,16 @@ impl Comment {
     /// ```
     #[must_use]
     pub fn new(text: String) -> Self {
+        Self::with_rich_text(RichText::from(text))
+    }
+
+    /// Construct a `Comment` whose text is made of styled spans.
+    #[must_use]
+    pub fn with_rich_text(text: RichText) -> Self {
         let mut c = Self {
             text,
+            max_line_width: None,
+            source_span: None,
             attributes: HashMap::default(),
         };
         c.attributes
@@ -227,26 +483,70 @@ impl Comment {
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
     }
+
+    /// Wrap the text into lines of at most `max_line_width` columns, breaking
+    /// between words; `None`, the default, only breaks at explicit line breaks.
+    pub fn set_max_line_width(&mut self, max_line_width: Option<usize>) {
+        self.max_line_width = max_line_width;
+    }
+
+    /// The width, in columns, at which the text is wrapped.
+    #[must_use]
+    pub fn max_line_width(&self) -> Option<usize> {
+        self.max_line_width
+    }
+
+    /// Record where this node was defined in the text it was imported from,
+    /// written to the SVG as `data-source-*` attributes; see [`SourceSpan`].
+    pub fn set_source_span(&mut self, source_span: Option<SourceSpan>) {
+        set_source_attributes(&mut self.attributes, source_span.as_ref());
+        self.source_span = source_span;
+    }
+
+    /// Where this node was defined, if known.
+    #[must_use]
+    pub fn source_span(&self) -> Option<&SourceSpan> {
+        self.source_span.as_ref()
+    }
+
+    fn lines(&self) -> Vec<Line<'_>> {
+        self.text.lines(self.max_line_width)
+    }
+
+    /// The layout geometry `(entry_height, height, width)` in `orientation`.
+    fn text_box(&self, orientation: Orientation) -> (i64, i64, i64) {
+        COMMENT.text_box(orientation, &self.lines())
+    }
 }
 
 impl Node for Comment {
     fn entry_height(&self) -> i64 {
-        10
+        self.text_box(Orientation::Horizontal).0
     }
     fn height(&self) -> i64 {
-        20
+        self.text_box(Orientation::Horizontal).1
     }
     fn width(&self) -> i64 {
-        i64::try_from(text_width(&self.text)).unwrap() * 7 + 10
+        self.text_box(Orientation::Horizontal).2
     }
 
-    fn draw(&self, x: i64, y: i64, _: HDir) -> svg::Element {
-        svg::Element::new("text")
-            .set_all(self.attributes.iter())
-            .set("x", &(x + self.width() / 2))
-            .set("y", &(y + 15))
-            .text(&self.text)
-            .debug("Comment", x, y, self)
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        text_geometry(self.text_box(orientation), orientation)
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        self.draw_with_geometry(x, y, h_dir, &self.compute_geometry())
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, _: HDir, geo: &NodeGeometry) -> svg::Element {
+        layout::place_text_lines(
+            svg::Element::new("text").set_all(self.attributes.iter()),
+            x + geo.width / 2,
+            y + geo.entry_height + 5,
+            &self.lines(),
+            COMMENT.line_height,
+        )
+        .debug_with_geometry("Comment", x, y, geo)
     }
 
     fn render_with_geometry(
@@ -257,14 +557,47 @@ impl Node for Comment {
         _h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
+        let lines = self.lines();
+        let (text_x, text_y) = (x + geo.width / 2, y + geo.entry_height + 5);
         let mut text = out.start_element("text")?;
         text.attr_hashmap(&self.attributes)?;
-        text.attr("x", x + geo.width / 2)?;
-        text.attr("y", y + 15)?;
+        layout::write_text_lines_position(
+            &mut text,
+            text_x,
+            text_y,
+            lines.len(),
+            COMMENT.line_height,
+        )?;
         crate::add_debug_attrs(&mut text, "Comment", x, y, geo)?;
         text.finish()?;
-        out.write_text(&self.text)?;
+        layout::write_text_lines(out, text_x, text_y, &lines, COMMENT.line_height)?;
         out.end_element("text")?;
         crate::write_debug_overlay(out, x, y, geo)
     }
+
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        _h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_text_lines(
+            x + geo.width / 2,
+            y + geo.entry_height + 5,
+            &self.lines(),
+            COMMENT.line_height,
+        )
+    }
+
+    fn describe(&self) -> Description {
+        describe_text(
+            "comment",
+            "text",
+            &self.text,
+            self.max_line_width,
+            self.source_span.as_ref(),
+        )
+    }
 }
@@ -5,8 +5,9 @@ use std::{
 };
 
 use crate::{
-    ARC_RADIUS, Empty, HDir, Node, NodeGeometry, RenderBackend, draw_group_with_geometry,
-    render_group_with_geometry, svg,
+    ARC_RADIUS, Empty, HDir, Node, NodeGeometry, Orientation, RenderBackend,
+    description::Description, draw_group_with_geometry, layout, render_group_with_geometry, svg,
+    text_width,
 };
 
 /// Possible targets for `Link`.
@@ -67,21 +68,6 @@ impl<N> Link<N> {
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
     }
-
-    /// Emit the wrapped child once, letting the outer `<a>` wrapper choose the backend.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        h_dir: HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result
-    where
-        N: Node,
-    {
-        backend.push_child(&self.inner, x, y, h_dir, &geo.children[0])
-    }
 }
 
 impl<N> Node for Link<N>
@@ -113,7 +99,11 @@ where
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let inner_geo = self.inner.compute_geometry();
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let inner_geo = self.inner.compute_geometry_in(orientation);
         let entry_height = inner_geo.entry_height;
         let height = inner_geo.height;
         let width = inner_geo.width;
@@ -122,6 +112,8 @@ where
             height,
             width,
             children: vec![inner_geo],
+            id: None,
+            orientation,
         }
     }
 
@@ -168,6 +160,188 @@ where
         crate::write_debug_overlay(out, x, y, geo)?;
         out.end_element("a")
     }
+
+    /// Emit the wrapped child once, letting the outer `<a>` wrapper choose the backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_child(&self.inner, x, y, h_dir, &geo.children[0])
+    }
+
+    fn describe(&self) -> Description {
+        let desc = Description::new("link").with_property("uri", self.uri.clone());
+        let desc = match self.target {
+            None => desc,
+            Some(LinkTarget::Blank) => desc.with_property("target", "blank"),
+            Some(LinkTarget::Parent) => desc.with_property("target", "parent"),
+            Some(LinkTarget::Top) => desc.with_property("target", "top"),
+        };
+        desc.with_child(self.inner.describe())
+    }
+}
+
+/// Wraps another primitive, attaching a tooltip and an optional, longer
+/// description to it.
+///
+/// The tooltip and the description become the `<title>` and `<desc>` of a
+/// group around `inner`; viewers typically show the title when hovering the
+/// element. The group carries no `visual-debug` information of its own, so the
+/// `<title>` which that feature attaches to `inner` stays separate.
+#[derive(Debug, Clone)]
+pub struct Tooltip<N> {
+    inner: N,
+    title: String,
+    description: Option<String>,
+    attributes: HashMap<String, String>,
+}
+
+impl<N> Tooltip<N> {
+    /// Wrap `inner`, showing `title` when hovered.
+    ///
+    /// The title is HTML-escaped when rendered, so arbitrary text is safe to pass.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut node = Tooltip::new(
+    ///     NonTerminal::new("expr".to_owned()),
+    ///     "An arithmetic expression".to_owned(),
+    /// );
+    /// node.set_description(Some("Operators bind as usual.".to_owned()));
+    /// let svg = Diagram::new(node).to_string();
+    /// assert!(svg.contains("<title>\nAn arithmetic expression</title>"));
+    /// assert!(svg.contains("<desc>\nOperators bind as usual.</desc>"));
+    /// ```
+    pub fn new(inner: N, title: String) -> Self {
+        let mut t = Self {
+            inner,
+            title,
+            description: None,
+            attributes: HashMap::default(),
+        };
+        t.attributes
+            .insert("class".to_owned(), "tooltip".to_owned());
+        t
+    }
+
+    /// The text shown when hovering the element.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Set a longer description of the element, or remove it with `None`.
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+    }
+
+    /// The longer description of the element, if any.
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Access an attribute on the main SVG-element that will be drawn.
+    pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
+        self.attributes.entry(key)
+    }
+}
+
+impl<N> Node for Tooltip<N>
+where
+    N: Node,
+{
+    fn entry_height(&self) -> i64 {
+        self.inner.entry_height()
+    }
+    fn height(&self) -> i64 {
+        self.inner.height()
+    }
+    fn width(&self) -> i64 {
+        self.inner.width()
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
+    }
+
+    fn compute_geometry(&self) -> NodeGeometry {
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let inner_geo = self.inner.compute_geometry_in(orientation);
+        NodeGeometry {
+            entry_height: inner_geo.entry_height,
+            height: inner_geo.height,
+            width: inner_geo.width,
+            children: vec![inner_geo],
+            id: None,
+            orientation,
+        }
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        let mut g = svg::Element::new("g")
+            .set_all(self.attributes.iter())
+            .add(svg::Element::new("title").text(&self.title));
+        if let Some(description) = &self.description {
+            g = g.add(svg::Element::new("desc").text(description));
+        }
+        g.add(crate::draw_child(
+            &self.inner,
+            x,
+            y,
+            h_dir,
+            &geo.children[0],
+        ))
+    }
+
+    fn render_with_geometry(
+        &self,
+        out: &mut svg::Renderer<'_>,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let mut g = out.start_element("g")?;
+        g.attr_hashmap(&self.attributes)?;
+        g.finish()?;
+        out.text_element("title", &self.title, |_| Ok(()))?;
+        if let Some(description) = &self.description {
+            out.text_element("desc", description, |_| Ok(()))?;
+        }
+        crate::render_child(out, &self.inner, x, y, h_dir, &geo.children[0])?;
+        out.end_element("g")
+    }
+
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_child(&self.inner, x, y, h_dir, &geo.children[0])
+    }
+
+    fn describe(&self) -> Description {
+        let desc = Description::new("tooltip").with_property("title", self.title.clone());
+        let desc = match &self.description {
+            Some(description) => desc.with_property("description", description.clone()),
+            None => desc,
+        };
+        desc.with_child(self.inner.describe())
+    }
 }
 
 /// Wraps another element to make that element logically optional.
@@ -208,19 +382,99 @@ impl<N> Optional<N> {
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
     }
+}
+
+impl<N> Node for Optional<N>
+where
+    N: Node,
+{
+    fn entry_height(&self) -> i64 {
+        ARC_RADIUS + cmp::max(ARC_RADIUS, self.inner.entry_height())
+    }
+
+    fn height(&self) -> i64 {
+        self.entry_height() + self.inner.height_below_entry()
+    }
+
+    fn width(&self) -> i64 {
+        ARC_RADIUS * 2 + self.inner.width() + ARC_RADIUS * 2
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let i = self.inner.draw(
+            x + ARC_RADIUS * 2,
+            y + self.entry_height() - self.inner.entry_height(),
+            h_dir,
+        );
+
+        let v = svg::PathData::new(h_dir)
+            .move_to(x, y + self.entry_height())
+            .horizontal(ARC_RADIUS * 2)
+            .move_rel(-ARC_RADIUS * 2, 0)
+            .arc(ARC_RADIUS, svg::Arc::WestToNorth)
+            .vertical(cmp::min(0, -self.inner.entry_height() + ARC_RADIUS))
+            .arc(ARC_RADIUS, svg::Arc::SouthToEast)
+            .horizontal(self.inner.width())
+            .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+            .vertical(cmp::max(0, self.inner.entry_height() - ARC_RADIUS))
+            .arc(ARC_RADIUS, svg::Arc::NorthToEast)
+            .horizontal(-ARC_RADIUS * 2)
+            .into_path();
+
+        svg::Element::new("g")
+            .debug("Optional", x, y, self)
+            .set_all(self.attributes.iter())
+            .add(v)
+            .add(i)
+    }
+
+    fn compute_geometry(&self) -> NodeGeometry {
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let inner_geo = self.inner.compute_geometry_in(orientation);
+        let entry_height = ARC_RADIUS + cmp::max(ARC_RADIUS, inner_geo.entry_height);
+        let height = entry_height + inner_geo.height_below_entry();
+        let width = ARC_RADIUS * 2 + inner_geo.width + ARC_RADIUS * 2;
+        NodeGeometry {
+            entry_height,
+            height,
+            width,
+            children: vec![inner_geo],
+            id: None,
+            orientation,
+        }
+    }
+
+    fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
+        draw_group_with_geometry(&self.attributes, "Optional", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
+
+    fn render_with_geometry(
+        &self,
+        out: &mut svg::Renderer<'_>,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        render_group_with_geometry(out, &self.attributes, "Optional", x, y, geo, |backend| {
+            self.emit_with_geometry(backend, x, y, h_dir, geo)
+        })
+    }
 
     /// Emit the bypass arc and wrapped child once for both render backends.
-    fn emit_with_geometry<B: RenderBackend>(
+    fn emit_with_geometry(
         &self,
-        backend: &mut B,
+        backend: &mut dyn RenderBackend,
         x: i64,
         y: i64,
         h_dir: HDir,
         geo: &NodeGeometry,
-    ) -> fmt::Result
-    where
-        N: Node,
-    {
+    ) -> fmt::Result {
         let inner_geo = &geo.children[0];
         backend.push_path(
             svg::PathData::new(h_dir)
@@ -244,67 +498,436 @@ impl<N> Optional<N> {
             inner_geo,
         )
     }
+
+    fn describe(&self) -> Description {
+        Description::new("optional").with_child(self.inner.describe())
+    }
 }
 
-impl<N> Node for Optional<N>
-where
-    N: Node,
-{
-    fn entry_height(&self) -> i64 {
-        ARC_RADIUS + cmp::max(ARC_RADIUS, self.inner.entry_height())
+/// How often the body of a loop is traversed: at least `min` and at most
+/// `max` times, with `None` meaning there is no upper bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Bounds {
+    pub(crate) min: usize,
+    pub(crate) max: Option<usize>,
+}
+
+impl Bounds {
+    const ONE_OR_MORE: Self = Self { min: 1, max: None };
+
+    /// The given bounds, with a `max` below `min` raised to `min`. A `max` of
+    /// zero is kept: the body is never traversed.
+    pub(crate) fn new(min: usize, max: Option<usize>) -> Self {
+        Self {
+            min,
+            max: max.map(|max| max.max(min)),
+        }
     }
 
-    fn height(&self) -> i64 {
-        self.entry_height() + self.inner.height_below_entry()
+    /// The annotation for bounds the shape of the loop does not express.
+    pub(crate) fn annotation(self) -> Option<String> {
+        match (self.min, self.max) {
+            (0 | 1, None) => None,
+            (min, None) => Some(format!("{{{min},}}")),
+            (min, Some(max)) if min == max => Some(format!("{{{min}}}")),
+            (min, Some(max)) => Some(format!("{{{min},{max}}}")),
+        }
     }
 
-    fn width(&self) -> i64 {
-        ARC_RADIUS * 2 + self.inner.width() + ARC_RADIUS * 2
+    /// The layout geometry `(entry_height, height, width)` of the annotation
+    /// in `orientation`, if there is one.
+    fn annotation_box(self, orientation: Orientation) -> Option<(i64, i64, i64)> {
+        self.annotation().map(|annotation| {
+            layout::text_box(
+                orientation,
+                10,
+                20,
+                i64::try_from(text_width(&annotation)).unwrap() * 7 + 10,
+            )
+        })
     }
 
-    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
-        let i = self.inner.draw(
-            x + ARC_RADIUS * 2,
-            y + self.entry_height() - self.inner.entry_height(),
-            h_dir,
+    /// Add the bounds to a description, unless they are one-or-more.
+    fn describe(self, mut desc: Description) -> Description {
+        if self.min != 1 {
+            desc = desc.with_property("min", self.min.to_string());
+        }
+        if let Some(max) = self.max {
+            desc = desc.with_property("max", max.to_string());
+        }
+        desc
+    }
+}
+
+/// The loop shared by [`Repeat`] and [`SeparatedList`]: `inner` on the main
+/// path, `repeat` on the return path and the annotation of `bounds` below.
+struct Loop {
+    bounds: Bounds,
+    spacing: i64,
+    orientation: Orientation,
+}
+
+impl Loop {
+    /// The width between the arcs of the loop, given the width of `inner`
+    /// and of `repeat`.
+    fn body_width(&self, inner_width: i64, repeat_width: i64) -> i64 {
+        let annotation_width = self
+            .bounds
+            .annotation_box(self.orientation)
+            .map_or(0, |(_, _, w)| w);
+        cmp::max(cmp::max(inner_width, repeat_width), annotation_width)
+    }
+
+    fn height_between(&self, inner: (i64, i64, i64), repeat: (i64, i64, i64)) -> i64 {
+        cmp::max(ARC_RADIUS * 2, inner.1 + self.spacing + repeat.0)
+    }
+
+    /// The `(entry_height, height_below_entry, width)` of the loop, given the
+    /// `(entry_height, height_below_entry, width)` of `inner` and of `repeat`.
+    fn extents(&self, inner: (i64, i64, i64), repeat: (i64, i64, i64)) -> (i64, i64, i64) {
+        let annotation_height = self
+            .bounds
+            .annotation_box(self.orientation)
+            .map_or(0, |(_, h, _)| h);
+        (
+            inner.0,
+            self.height_between(inner, repeat) + repeat.1 + annotation_height,
+            ARC_RADIUS + self.body_width(inner.2, repeat.2) + ARC_RADIUS,
+        )
+    }
+
+    /// Emit the forward path, repeat arm, annotation and inner branch, with
+    /// the main path entering at `(x, entry_y)`.
+    #[allow(clippy::too_many_arguments)]
+    fn emit<I: Node, R: Node>(
+        &self,
+        backend: &mut dyn RenderBackend,
+        inner: &I,
+        repeat: &R,
+        x: i64,
+        entry_y: i64,
+        h_dir: HDir,
+        inner_geo: &NodeGeometry,
+        repeat_geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let height_between = self.height_between(
+            (inner_geo.entry_height, inner_geo.height_below_entry(), 0),
+            (repeat_geo.entry_height, 0, 0),
         );
+        let body_width = self.body_width(inner_geo.width, repeat_geo.width);
 
-        let v = svg::PathData::new(h_dir)
-            .move_to(x, y + self.entry_height())
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, entry_y)
+                .horizontal(ARC_RADIUS)
+                .move_rel(inner_geo.width, 0)
+                .horizontal(body_width - inner_geo.width + ARC_RADIUS)
+                .move_rel(-ARC_RADIUS, 0)
+                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
+                .vertical(height_between - ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::NorthToWest)
+                .move_rel(-repeat_geo.width, 0)
+                .horizontal(repeat_geo.width - body_width)
+                .arc(ARC_RADIUS, svg::Arc::EastToNorth)
+                .vertical(-height_between + ARC_RADIUS * 2)
+                .arc(ARC_RADIUS, svg::Arc::SouthToEast),
+        )?;
+        backend.push_child(
+            repeat,
+            x + ARC_RADIUS + body_width - repeat_geo.width,
+            entry_y + height_between - repeat_geo.entry_height,
+            h_dir.invert(),
+            repeat_geo,
+        )?;
+        if let (Some(annotation), Some((entry_height, _, width))) = (
+            self.bounds.annotation(),
+            self.bounds.annotation_box(self.orientation),
+        ) {
+            backend.push_text(
+                x + ARC_RADIUS + body_width - width / 2,
+                entry_y + height_between + repeat_geo.height_below_entry() + entry_height + 5,
+                &annotation,
+            )?;
+        }
+        backend.push_child(
+            inner,
+            x + ARC_RADIUS,
+            entry_y - inner_geo.entry_height,
+            h_dir,
+            inner_geo,
+        )
+    }
+}
+
+/// The `(entry_height, height_below_entry, width)` of something which may be
+/// skipped via a bypass above it, as drawn by [`Optional`].
+fn bypassed((entry_height, height_below_entry, width): (i64, i64, i64)) -> (i64, i64, i64) {
+    (
+        ARC_RADIUS + cmp::max(ARC_RADIUS, entry_height),
+        height_below_entry,
+        ARC_RADIUS * 2 + width + ARC_RADIUS * 2,
+    )
+}
+
+/// Emit the bypass of something which is `width` wide and reaches
+/// `entry_height` above the main path entering at `(x, entry_y)`.
+fn emit_bypass(
+    backend: &mut dyn RenderBackend,
+    x: i64,
+    entry_y: i64,
+    h_dir: HDir,
+    entry_height: i64,
+    width: i64,
+) -> fmt::Result {
+    backend.push_path(
+        svg::PathData::new(h_dir)
+            .move_to(x, entry_y)
             .horizontal(ARC_RADIUS * 2)
             .move_rel(-ARC_RADIUS * 2, 0)
             .arc(ARC_RADIUS, svg::Arc::WestToNorth)
-            .vertical(cmp::min(0, -self.inner.entry_height() + ARC_RADIUS))
+            .vertical(cmp::min(0, -entry_height + ARC_RADIUS))
             .arc(ARC_RADIUS, svg::Arc::SouthToEast)
-            .horizontal(self.inner.width())
+            .horizontal(width)
             .arc(ARC_RADIUS, svg::Arc::WestToSouth)
-            .vertical(cmp::max(0, self.inner.entry_height() - ARC_RADIUS))
+            .vertical(cmp::max(0, entry_height - ARC_RADIUS))
             .arc(ARC_RADIUS, svg::Arc::NorthToEast)
-            .horizontal(-ARC_RADIUS * 2)
-            .into_path();
+            .horizontal(-ARC_RADIUS * 2),
+    )
+}
+
+/// Wraps one element by providing a backwards-path through another element.
+///
+/// The main path flows through `inner` left-to-right. A return arc curves below
+/// and carries the path through `repeat` right-to-left, allowing the sequence to
+/// be traversed multiple times. Use [`Empty`] for `repeat` when no label or
+/// content is needed on the return path.
+///
+/// By default `inner` is traversed at least once. See [`Repeat::set_bounds`] to
+/// limit how often it is traversed; bounds the shape can not express are shown
+/// as an annotation below the loop.
+#[derive(Debug, Clone)]
+pub struct Repeat<I, R> {
+    inner: I,
+    repeat: R,
+    bounds: Bounds,
+    spacing: i64,
+    attributes: HashMap<String, String>,
+}
+
+impl<I, R> Repeat<I, R> {
+    /// Create a `Repeat` that loops `inner` via the `repeat` node on the return path.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// // One-or-more repetitions with no label on the back-arc
+    /// let r = Repeat::new(Terminal::new("item".to_owned()), Empty);
+    /// assert!(Diagram::new(r).to_string().starts_with("<svg"));
+    /// ```
+    pub fn new(inner: I, repeat: R) -> Self {
+        let mut r = Self {
+            inner,
+            repeat,
+            bounds: Bounds::ONE_OR_MORE,
+            spacing: 10,
+            attributes: HashMap::default(),
+        };
+        r.attributes.insert("class".to_owned(), "repeat".to_owned());
+        r
+    }
+
+    /// Create a `Repeat` that traverses `inner` at least once.
+    ///
+    /// This is the same as [`Repeat::new`].
+    pub fn one_or_more(inner: I, repeat: R) -> Self {
+        Self::new(inner, repeat)
+    }
+
+    /// Create a `Repeat` which may also skip `inner` altogether.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let r = Repeat::zero_or_more(Terminal::new("item".to_owned()), Empty);
+    /// assert_eq!(r.bounds(), (0, None));
+    /// let plain = Repeat::new(Terminal::new("item".to_owned()), Empty);
+    /// assert!(r.height() > plain.height());
+    /// ```
+    pub fn zero_or_more(inner: I, repeat: R) -> Self {
+        let mut r = Self::new(inner, repeat);
+        r.bounds = Bounds::new(0, None);
+        r
+    }
+
+    /// Limit how often `inner` is traversed to at least `min` and at most
+    /// `max` times, with `None` meaning there is no upper bound.
+    ///
+    /// If `min` is zero, a bypass is drawn above the loop. Bounds other than
+    /// zero-or-more and one-or-more are annotated as `{min,max}` below the
+    /// loop, e.g. `{2,5}`, `{3}` for exactly three times or `{2,}` for at
+    /// least two times.
+    ///
+    /// A `max` below `min` is raised to `min`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use railroad::*;
+    ///
+    /// let mut r = Repeat::new(Terminal::new("digit".to_owned()), Empty);
+    /// r.set_bounds(2, Some(5));
+    /// assert_eq!(r.bounds(), (2, Some(5)));
+    /// assert!(Diagram::new(r).to_string().contains("{2,5}"));
+    /// ```
+    pub fn set_bounds(&mut self, min: usize, max: Option<usize>) {
+        self.bounds = Bounds::new(min, max);
+    }
+
+    /// How often `inner` is traversed at least, and at most.
+    #[must_use]
+    pub fn bounds(&self) -> (usize, Option<usize>) {
+        (self.bounds.min, self.bounds.max)
+    }
+
+    /// Access an attribute on the main SVG-element that will be drawn.
+    pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
+        self.attributes.entry(key)
+    }
+
+    /// The loop of this node, laid out in `orientation`.
+    fn as_loop(&self, orientation: Orientation) -> Loop {
+        Loop {
+            bounds: self.bounds,
+            spacing: self.spacing,
+            orientation,
+        }
+    }
+
+    /// The `(entry_height, height, width)` of this repeat in `orientation`,
+    /// given the `(entry_height, height_below_entry, width)` of `inner` and
+    /// of `repeat`.
+    fn extents(
+        &self,
+        orientation: Orientation,
+        inner: (i64, i64, i64),
+        repeat: (i64, i64, i64),
+    ) -> (i64, i64, i64) {
+        let mut extents = self.as_loop(orientation).extents(inner, repeat);
+        if self.bounds.min == 0 {
+            extents = bypassed(extents);
+        }
+        (extents.0, extents.0 + extents.1, extents.2)
+    }
+}
+
+impl<I, R> Repeat<I, R>
+where
+    I: Node,
+    R: Node,
+{
+    fn inner_extents(&self) -> (i64, i64, i64) {
+        (
+            self.inner.entry_height(),
+            self.inner.height_below_entry(),
+            self.inner.width(),
+        )
+    }
+
+    fn repeat_extents(&self) -> (i64, i64, i64) {
+        (
+            self.repeat.entry_height(),
+            self.repeat.height_below_entry(),
+            self.repeat.width(),
+        )
+    }
+}
+
+impl<I, R> Default for Repeat<I, R>
+where
+    I: Default,
+    R: Default,
+{
+    fn default() -> Self {
+        Self {
+            inner: Default::default(),
+            repeat: Default::default(),
+            bounds: Bounds::ONE_OR_MORE,
+            spacing: 10,
+            attributes: HashMap::default(),
+        }
+    }
+}
+
+impl<I, R> Node for Repeat<I, R>
+where
+    I: Node,
+    R: Node,
+{
+    fn entry_height(&self) -> i64 {
+        self.extents(
+            Orientation::Horizontal,
+            self.inner_extents(),
+            self.repeat_extents(),
+        )
+        .0
+    }
 
-        svg::Element::new("g")
-            .debug("Optional", x, y, self)
-            .set_all(self.attributes.iter())
-            .add(v)
-            .add(i)
+    fn height(&self) -> i64 {
+        self.extents(
+            Orientation::Horizontal,
+            self.inner_extents(),
+            self.repeat_extents(),
+        )
+        .1
+    }
+
+    fn width(&self) -> i64 {
+        self.extents(
+            Orientation::Horizontal,
+            self.inner_extents(),
+            self.repeat_extents(),
+        )
+        .2
+    }
+
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let inner_geo = self.inner.compute_geometry();
-        let entry_height = ARC_RADIUS + cmp::max(ARC_RADIUS, inner_geo.entry_height);
-        let height = entry_height + inner_geo.height_below_entry();
-        let width = ARC_RADIUS * 2 + inner_geo.width + ARC_RADIUS * 2;
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let inner_geo = self.inner.compute_geometry_in(orientation);
+        let repeat_geo = self.repeat.compute_geometry_in(orientation);
+        let (entry_height, height, width) = self.extents(
+            orientation,
+            (
+                inner_geo.entry_height,
+                inner_geo.height_below_entry(),
+                inner_geo.width,
+            ),
+            (
+                repeat_geo.entry_height,
+                repeat_geo.height_below_entry(),
+                repeat_geo.width,
+            ),
+        );
         NodeGeometry {
             entry_height,
             height,
             width,
-            children: vec![inner_geo],
+            children: vec![inner_geo, repeat_geo],
+            id: None,
+            orientation,
         }
     }
 
     fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
-        draw_group_with_geometry(&self.attributes, "Optional", x, y, geo, |backend| {
+        draw_group_with_geometry(&self.attributes, "Repeat", x, y, geo, |backend| {
             self.emit_with_geometry(backend, x, y, h_dir, geo)
         })
     }
@@ -317,196 +940,291 @@ where
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        render_group_with_geometry(out, &self.attributes, "Optional", x, y, geo, |backend| {
+        render_group_with_geometry(out, &self.attributes, "Repeat", x, y, geo, |backend| {
             self.emit_with_geometry(backend, x, y, h_dir, geo)
         })
     }
+
+    /// Emit the bypass, forward path, repeat arm, annotation and inner branch
+    /// through the shared backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let inner_geo = &geo.children[0];
+        let repeat_geo = &geo.children[1];
+        let entry_y = y + geo.entry_height;
+        let x = if self.bounds.min == 0 {
+            emit_bypass(
+                backend,
+                x,
+                entry_y,
+                h_dir,
+                inner_geo.entry_height,
+                geo.width - ARC_RADIUS * 4,
+            )?;
+            x + ARC_RADIUS * 2
+        } else {
+            x
+        };
+        self.as_loop(geo.orientation).emit(
+            backend,
+            &self.inner,
+            &self.repeat,
+            x,
+            entry_y,
+            h_dir,
+            inner_geo,
+            repeat_geo,
+        )
+    }
+
+    fn describe(&self) -> Description {
+        self.bounds
+            .describe(Description::new("repeat"))
+            .with_child(self.inner.describe())
+            .with_child(self.repeat.describe())
+    }
 }
 
-/// Wraps one element by providing a backwards-path through another element.
+/// A list of items with a separator between each pair of them.
 ///
-/// The main path flows through `inner` left-to-right. A return arc curves below
-/// and carries the path through `repeat` right-to-left, allowing the sequence to
-/// be traversed multiple times. Use [`Empty`] for `repeat` when no label or
-/// content is needed on the return path.
+/// Drawn as a single loop with `item` on the main path and `separator` on the
+/// return path. The list may be allowed to be empty, in which case a bypass is
+/// drawn above it, or to end with a separator, in which case a skippable copy
+/// of `separator` follows the loop. Bounds on the number of items are shown as
+/// an annotation below the loop, as for [`Repeat`].
 #[derive(Debug, Clone)]
-pub struct Repeat<I, R> {
-    inner: I,
-    repeat: R,
+pub struct SeparatedList<I, S> {
+    item: I,
+    separator: S,
+    bounds: Bounds,
+    trailing_separator: bool,
     spacing: i64,
     attributes: HashMap<String, String>,
 }
 
-impl<I, R> Repeat<I, R> {
-    /// Create a `Repeat` that loops `inner` via the `repeat` node on the return path.
+impl<I, S> SeparatedList<I, S> {
+    /// Create a `SeparatedList` of at least one `item`, without a trailing `separator`.
     ///
     /// # Example
     /// ```rust
     /// use railroad::*;
     ///
-    /// // Zero-or-more repetitions with no label on the back-arc
-    /// let r = Repeat::new(Terminal::new("item".to_owned()), Empty);
-    /// assert!(Diagram::new(r).to_string().starts_with("<svg"));
+    /// let mut list = SeparatedList::new(
+    ///     NonTerminal::new("expr".to_owned()),
+    ///     Terminal::new(",".to_owned()),
+    /// );
+    /// list.set_allow_empty(true);
+    /// list.set_trailing_separator(true);
+    /// assert!(Diagram::new(list).to_string().contains("class=\"separatedlist\""));
     /// ```
-    pub fn new(inner: I, repeat: R) -> Self {
-        let mut r = Self {
-            inner,
-            repeat,
+    pub fn new(item: I, separator: S) -> Self {
+        let mut l = Self {
+            item,
+            separator,
+            bounds: Bounds::ONE_OR_MORE,
+            trailing_separator: false,
             spacing: 10,
             attributes: HashMap::default(),
         };
-        r.attributes.insert("class".to_owned(), "repeat".to_owned());
-        r
+        l.attributes
+            .insert("class".to_owned(), "separatedlist".to_owned());
+        l
+    }
+
+    /// Set whether the list may have no items at all.
+    ///
+    /// This is the same as setting the minimal number of items to zero, or
+    /// back to one, via [`SeparatedList::set_bounds`].
+    pub fn set_allow_empty(&mut self, allow_empty: bool) {
+        match (allow_empty, self.bounds.min) {
+            (true, _) => self.bounds.min = 0,
+            (false, 0) => self.bounds = Bounds::new(1, self.bounds.max),
+            (false, _) => {}
+        }
+    }
+
+    /// Whether the list may have no items at all.
+    #[must_use]
+    pub fn allows_empty(&self) -> bool {
+        self.bounds.min == 0
+    }
+
+    /// Set whether the list may end with a separator.
+    pub fn set_trailing_separator(&mut self, trailing_separator: bool) {
+        self.trailing_separator = trailing_separator;
+    }
+
+    /// Whether the list may end with a separator.
+    #[must_use]
+    pub fn trailing_separator(&self) -> bool {
+        self.trailing_separator
+    }
+
+    /// Limit the number of items to at least `min` and at most `max`, with
+    /// `None` meaning there is no upper bound; see [`Repeat::set_bounds`].
+    pub fn set_bounds(&mut self, min: usize, max: Option<usize>) {
+        self.bounds = Bounds::new(min, max);
+    }
+
+    /// How many items the list has at least, and at most.
+    #[must_use]
+    pub fn bounds(&self) -> (usize, Option<usize>) {
+        (self.bounds.min, self.bounds.max)
     }
 
     /// Access an attribute on the main SVG-element that will be drawn.
     pub fn attr(&mut self, key: String) -> collections::hash_map::Entry<'_, String, String> {
         self.attributes.entry(key)
     }
+
+    /// The loop of this node, laid out in `orientation`.
+    fn as_loop(&self, orientation: Orientation) -> Loop {
+        Loop {
+            bounds: self.bounds,
+            spacing: self.spacing,
+            orientation,
+        }
+    }
+
+    /// The `(entry_height, height, width)` of this list in `orientation`,
+    /// given the `(entry_height, height_below_entry, width)` of `item` and of
+    /// `separator`.
+    fn extents(
+        &self,
+        orientation: Orientation,
+        item: (i64, i64, i64),
+        separator: (i64, i64, i64),
+    ) -> (i64, i64, i64) {
+        let mut extents = self.as_loop(orientation).extents(item, separator);
+        if self.trailing_separator {
+            let trailing = bypassed(separator);
+            extents = (
+                cmp::max(extents.0, trailing.0),
+                cmp::max(extents.1, trailing.1),
+                extents.2 + trailing.2,
+            );
+        }
+        if self.bounds.min == 0 {
+            extents = bypassed(extents);
+        }
+        (extents.0, extents.0 + extents.1, extents.2)
+    }
 }
 
-impl<I, R> Repeat<I, R>
+impl<I, S> SeparatedList<I, S>
 where
     I: Node,
-    R: Node,
+    S: Node,
 {
-    fn height_between_entries(&self) -> i64 {
-        cmp::max(
-            ARC_RADIUS * 2,
-            self.inner.height_below_entry() + self.spacing + self.repeat.entry_height(),
+    fn item_extents(&self) -> (i64, i64, i64) {
+        (
+            self.item.entry_height(),
+            self.item.height_below_entry(),
+            self.item.width(),
         )
     }
 
-    /// Emit the forward path, repeat arm, and inner branch through the shared backend.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        h_dir: HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result {
-        let inner_geo = &geo.children[0];
-        let repeat_geo = &geo.children[1];
-        let height_between = cmp::max(
-            ARC_RADIUS * 2,
-            inner_geo.height_below_entry() + self.spacing + repeat_geo.entry_height,
-        );
-
-        backend.push_path(
-            svg::PathData::new(h_dir)
-                .move_to(x, y + geo.entry_height)
-                .horizontal(ARC_RADIUS)
-                .move_rel(inner_geo.width, 0)
-                .horizontal(cmp::max(
-                    ARC_RADIUS,
-                    repeat_geo.width - inner_geo.width + ARC_RADIUS,
-                ))
-                .move_rel(-ARC_RADIUS, 0)
-                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
-                .vertical(height_between - ARC_RADIUS * 2)
-                .arc(ARC_RADIUS, svg::Arc::NorthToWest)
-                .move_rel(-repeat_geo.width, 0)
-                .horizontal(cmp::min(0, repeat_geo.width - inner_geo.width))
-                .arc(ARC_RADIUS, svg::Arc::EastToNorth)
-                .vertical(-height_between + ARC_RADIUS * 2)
-                .arc(ARC_RADIUS, svg::Arc::SouthToEast),
-        )?;
-        backend.push_child(
-            &self.repeat,
-            x + geo.width - repeat_geo.width - ARC_RADIUS,
-            y + geo.height - repeat_geo.height_below_entry() - repeat_geo.entry_height,
-            h_dir.invert(),
-            repeat_geo,
-        )?;
-        backend.push_child(&self.inner, x + ARC_RADIUS, y, h_dir, inner_geo)
+    fn separator_extents(&self) -> (i64, i64, i64) {
+        (
+            self.separator.entry_height(),
+            self.separator.height_below_entry(),
+            self.separator.width(),
+        )
     }
 }
 
-impl<I, R> Default for Repeat<I, R>
+impl<I, S> Default for SeparatedList<I, S>
 where
     I: Default,
-    R: Default,
+    S: Default,
 {
     fn default() -> Self {
         Self {
-            inner: Default::default(),
-            repeat: Default::default(),
+            item: Default::default(),
+            separator: Default::default(),
+            bounds: Bounds::ONE_OR_MORE,
+            trailing_separator: false,
             spacing: 10,
             attributes: HashMap::default(),
         }
     }
 }
 
-impl<I, R> Node for Repeat<I, R>
+impl<I, S> Node for SeparatedList<I, S>
 where
     I: Node,
-    R: Node,
+    S: Node,
 {
     fn entry_height(&self) -> i64 {
-        self.inner.entry_height()
+        self.extents(
+            Orientation::Horizontal,
+            self.item_extents(),
+            self.separator_extents(),
+        )
+        .0
     }
 
     fn height(&self) -> i64 {
-        self.inner.entry_height() + self.height_between_entries() + self.repeat.height_below_entry()
+        self.extents(
+            Orientation::Horizontal,
+            self.item_extents(),
+            self.separator_extents(),
+        )
+        .1
     }
 
     fn width(&self) -> i64 {
-        ARC_RADIUS + cmp::max(self.repeat.width(), self.inner.width()) + ARC_RADIUS
+        self.extents(
+            Orientation::Horizontal,
+            self.item_extents(),
+            self.separator_extents(),
+        )
+        .2
     }
 
     fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
-        let mut g = svg::Element::new("g").set_all(self.attributes.iter());
-
-        g.push(
-            svg::PathData::new(h_dir)
-                .move_to(x, y + self.entry_height())
-                .horizontal(ARC_RADIUS)
-                .move_rel(self.inner.width(), 0)
-                .horizontal(cmp::max(
-                    ARC_RADIUS,
-                    self.repeat.width() - self.inner.width() + ARC_RADIUS,
-                ))
-                .move_rel(-ARC_RADIUS, 0)
-                .arc(ARC_RADIUS, svg::Arc::WestToSouth)
-                .vertical(self.height_between_entries() - ARC_RADIUS * 2)
-                .arc(ARC_RADIUS, svg::Arc::NorthToWest)
-                .move_rel(-self.repeat.width(), 0)
-                .horizontal(cmp::min(0, self.repeat.width() - self.inner.width()))
-                .arc(ARC_RADIUS, svg::Arc::EastToNorth)
-                .vertical(-self.height_between_entries() + ARC_RADIUS * 2)
-                .arc(ARC_RADIUS, svg::Arc::SouthToEast)
-                .into_path(),
-        )
-        .push(self.repeat.draw(
-            x + self.width() - self.repeat.width() - ARC_RADIUS,
-            y + self.height() - self.repeat.height_below_entry() - self.repeat.entry_height(),
-            h_dir.invert(),
-        ));
-        g.push(self.inner.draw(x + ARC_RADIUS, y, h_dir));
-        g.debug("Repeat", x, y, self)
+        let geo = self.compute_geometry();
+        self.draw_with_geometry(x, y, h_dir, &geo)
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let inner_geo = self.inner.compute_geometry();
-        let repeat_geo = self.repeat.compute_geometry();
-        let height_between = cmp::max(
-            ARC_RADIUS * 2,
-            inner_geo.height_below_entry() + self.spacing + repeat_geo.entry_height,
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let item_geo = self.item.compute_geometry_in(orientation);
+        let separator_geo = self.separator.compute_geometry_in(orientation);
+        let (entry_height, height, width) = self.extents(
+            orientation,
+            (
+                item_geo.entry_height,
+                item_geo.height_below_entry(),
+                item_geo.width,
+            ),
+            (
+                separator_geo.entry_height,
+                separator_geo.height_below_entry(),
+                separator_geo.width,
+            ),
         );
-        let entry_height = inner_geo.entry_height;
-        let height = inner_geo.entry_height + height_between + repeat_geo.height_below_entry();
-        let width = ARC_RADIUS + cmp::max(repeat_geo.width, inner_geo.width) + ARC_RADIUS;
         NodeGeometry {
             entry_height,
             height,
             width,
-            children: vec![inner_geo, repeat_geo],
+            children: vec![item_geo, separator_geo],
+            id: None,
+            orientation,
         }
     }
 
     fn draw_with_geometry(&self, x: i64, y: i64, h_dir: HDir, geo: &NodeGeometry) -> svg::Element {
-        draw_group_with_geometry(&self.attributes, "Repeat", x, y, geo, |backend| {
+        draw_group_with_geometry(&self.attributes, "SeparatedList", x, y, geo, |backend| {
             self.emit_with_geometry(backend, x, y, h_dir, geo)
         })
     }
@@ -519,9 +1237,102 @@ where
         h_dir: HDir,
         geo: &NodeGeometry,
     ) -> fmt::Result {
-        render_group_with_geometry(out, &self.attributes, "Repeat", x, y, geo, |backend| {
-            self.emit_with_geometry(backend, x, y, h_dir, geo)
-        })
+        render_group_with_geometry(
+            out,
+            &self.attributes,
+            "SeparatedList",
+            x,
+            y,
+            geo,
+            |backend| self.emit_with_geometry(backend, x, y, h_dir, geo),
+        )
+    }
+
+    /// Emit the bypass, the loop and the trailing separator through the
+    /// shared backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let item_geo = &geo.children[0];
+        let separator_geo = &geo.children[1];
+        let entry_y = y + geo.entry_height;
+        let list = self.as_loop(geo.orientation);
+        let (loop_entry_height, _, loop_width) = list.extents(
+            (
+                item_geo.entry_height,
+                item_geo.height_below_entry(),
+                item_geo.width,
+            ),
+            (
+                separator_geo.entry_height,
+                separator_geo.height_below_entry(),
+                separator_geo.width,
+            ),
+        );
+        let (x, width) = if self.bounds.min == 0 {
+            let entry_height = if self.trailing_separator {
+                cmp::max(
+                    loop_entry_height,
+                    bypassed((separator_geo.entry_height, 0, 0)).0,
+                )
+            } else {
+                loop_entry_height
+            };
+            emit_bypass(
+                backend,
+                x,
+                entry_y,
+                h_dir,
+                entry_height,
+                geo.width - ARC_RADIUS * 4,
+            )?;
+            (x + ARC_RADIUS * 2, geo.width - ARC_RADIUS * 4)
+        } else {
+            (x, geo.width)
+        };
+        list.emit(
+            backend,
+            &self.item,
+            &self.separator,
+            x,
+            entry_y,
+            h_dir,
+            item_geo,
+            separator_geo,
+        )?;
+        if self.trailing_separator {
+            let x = x + loop_width;
+            emit_bypass(
+                backend,
+                x,
+                entry_y,
+                h_dir,
+                separator_geo.entry_height,
+                width - loop_width - ARC_RADIUS * 4,
+            )?;
+            backend.push_child(
+                &self.separator,
+                x + ARC_RADIUS * 2,
+                entry_y - separator_geo.entry_height,
+                h_dir,
+                &separator_geo.without_ids(),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> Description {
+        let mut desc = self.bounds.describe(Description::new("separatedlist"));
+        if self.trailing_separator {
+            desc = desc.with_property("trailing", "true");
+        }
+        desc.with_child(self.item.describe())
+            .with_child(self.separator.describe())
     }
 }
 
@@ -615,47 +1426,6 @@ where
             0
         }
     }
-
-    /// Emit the box frame, label, and inner node through the shared backend.
-    fn emit_with_geometry<B: RenderBackend>(
-        &self,
-        backend: &mut B,
-        x: i64,
-        y: i64,
-        h_dir: HDir,
-        geo: &NodeGeometry,
-    ) -> fmt::Result {
-        let inner_geo = &geo.children[0];
-        let label_geo = &geo.children[1];
-        let padding = if label_geo.height + inner_geo.height + label_geo.width + inner_geo.width > 0
-        {
-            self.padding
-        } else {
-            0
-        };
-        let spacing = if label_geo.height > 0 {
-            self.spacing
-        } else {
-            0
-        };
-
-        backend.push_rect(x, y, geo.width, geo.height)?;
-        backend.push_path(
-            svg::PathData::new(h_dir)
-                .move_to(x, y + geo.entry_height)
-                .horizontal(padding)
-                .move_rel(inner_geo.width, 0)
-                .horizontal(geo.width - inner_geo.width - padding),
-        )?;
-        backend.push_child(&self.label, x + padding, y + padding, h_dir, label_geo)?;
-        backend.push_child(
-            &self.inner,
-            x + padding,
-            y + padding + label_geo.height + spacing,
-            h_dir,
-            inner_geo,
-        )
-    }
 }
 
 impl<T, U> Node for LabeledBox<T, U>
@@ -706,8 +1476,12 @@ where
     }
 
     fn compute_geometry(&self) -> NodeGeometry {
-        let inner_geo = self.inner.compute_geometry();
-        let label_geo = self.label.compute_geometry();
+        self.compute_geometry_in(Orientation::Horizontal)
+    }
+
+    fn compute_geometry_in(&self, orientation: Orientation) -> NodeGeometry {
+        let inner_geo = self.inner.compute_geometry_in(orientation);
+        let label_geo = self.label.compute_geometry_in(orientation);
         let padding = if label_geo.height + inner_geo.height + label_geo.width + inner_geo.width > 0
         {
             self.padding
@@ -727,6 +1501,8 @@ where
             height,
             width,
             children: vec![inner_geo, label_geo],
+            id: None,
+            orientation,
         }
     }
 
@@ -748,4 +1524,51 @@ where
             self.emit_with_geometry(backend, x, y, h_dir, geo)
         })
     }
+
+    /// Emit the box frame, label, and inner node through the shared backend.
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let inner_geo = &geo.children[0];
+        let label_geo = &geo.children[1];
+        let padding = if label_geo.height + inner_geo.height + label_geo.width + inner_geo.width > 0
+        {
+            self.padding
+        } else {
+            0
+        };
+        let spacing = if label_geo.height > 0 {
+            self.spacing
+        } else {
+            0
+        };
+
+        backend.push_rect(x, y, geo.width, geo.height)?;
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, y + geo.entry_height)
+                .horizontal(padding)
+                .move_rel(inner_geo.width, 0)
+                .horizontal(geo.width - inner_geo.width - padding),
+        )?;
+        backend.push_child(&self.label, x + padding, y + padding, h_dir, label_geo)?;
+        backend.push_child(
+            &self.inner,
+            x + padding,
+            y + padding + label_geo.height + spacing,
+            h_dir,
+            inner_geo,
+        )
+    }
+
+    fn describe(&self) -> Description {
+        Description::new("labeledbox")
+            .with_child(self.inner.describe())
+            .with_child(self.label.describe())
+    }
 }
@@ -184,6 +184,7 @@ impl HDir {
 /// ```
 pub struct Renderer<'a> {
     out: &'a mut dyn fmt::Write,
+    pending_id: Option<String>,
 }
 
 /// A builder for an element's opening tag.
@@ -193,6 +194,7 @@ pub struct Renderer<'a> {
 /// [`StartTag::finish_empty`].
 pub struct StartTag<'a, 'b> {
     renderer: &'a mut Renderer<'b>,
+    id: Option<String>,
 }
 
 struct EscapingWriter<'a> {
@@ -202,7 +204,10 @@ struct EscapingWriter<'a> {
 impl<'a> Renderer<'a> {
     /// Create a renderer that writes SVG markup into `out`.
     pub fn new(out: &'a mut dyn fmt::Write) -> Self {
-        Self { out }
+        Self {
+            out,
+            pending_id: None,
+        }
     }
 
     /// Start an element opening tag.
@@ -230,7 +235,23 @@ impl<'a> Renderer<'a> {
         validate_tag_name(name)?;
         self.out.write_char('<')?;
         self.out.write_str(name)?;
-        Ok(StartTag { renderer: self })
+        let id = self.pending_id.take();
+        Ok(StartTag { renderer: self, id })
+    }
+
+    /// Give the next element started an `id`-attribute, unless it sets one
+    /// itself.
+    pub(crate) fn set_pending_id(&mut self, id: String) {
+        self.pending_id = Some(id);
+    }
+
+    /// Write an element, giving it the pending `id`-attribute if there is one;
+    /// see [`Renderer::set_pending_id`].
+    pub(crate) fn write_element(&mut self, element: Element) -> fmt::Result {
+        match self.pending_id.take() {
+            Some(id) => self.write_display(element.with_default_id(id)),
+            None => self.write_display(element),
+        }
     }
 
     /// Write a closing tag for `name`.
@@ -243,6 +264,16 @@ impl<'a> Renderer<'a> {
         self.out.write_str(">\n")
     }
 
+    /// Write a closing tag for `name`, without a line break after it.
+    ///
+    /// Counterpart to [`StartTag::finish_inline`].
+    pub fn end_inline_element(&mut self, name: &str) -> fmt::Result {
+        validate_tag_name(name)?;
+        self.out.write_str("</")?;
+        self.out.write_str(name)?;
+        self.out.write_char('>')
+    }
+
     /// Write text content with minimal XML escaping.
     pub fn write_text(&mut self, text: &str) -> fmt::Result {
         let mut escaping = EscapingWriter { out: self.out };
@@ -350,6 +381,9 @@ impl StartTag<'_, '_> {
     ///
     /// Both key and value are minimally XML-escaped before being written.
     pub fn attr(&mut self, key: impl fmt::Display, value: impl fmt::Display) -> fmt::Result {
+        if self.id.is_some() && key.to_string() == "id" {
+            self.id = None;
+        }
         self.renderer.out.write_char(' ')?;
         {
             let mut escaping = EscapingWriter {
@@ -377,11 +411,30 @@ impl StartTag<'_, '_> {
         Ok(())
     }
 
+    /// Write the pending id, unless the element set one itself.
+    fn write_pending_id(&mut self) -> fmt::Result {
+        match self.id.take() {
+            Some(id) => self.attr("id", id),
+            None => Ok(()),
+        }
+    }
+
     /// Finish the opening tag as a non-empty element.
-    pub fn finish(self) -> fmt::Result {
+    pub fn finish(mut self) -> fmt::Result {
+        self.write_pending_id()?;
         self.renderer.out.write_str(">\n")
     }
 
+    /// Finish the opening tag as a non-empty element, without a line break
+    /// after it.
+    ///
+    /// Line breaks between the parts of a text element would be drawn as
+    /// spaces; close the element with [`Renderer::end_inline_element`].
+    pub fn finish_inline(mut self) -> fmt::Result {
+        self.write_pending_id()?;
+        self.renderer.out.write_char('>')
+    }
+
     /// Finish the opening tag as an empty element.
     ///
     /// # Example
@@ -401,7 +454,8 @@ impl StartTag<'_, '_> {
     /// build(&mut out).unwrap();
     /// assert_eq!(out, "<rect width=\"100%\" height=\"100%\"/>\n");
     /// ```
-    pub fn finish_empty(self) -> fmt::Result {
+    pub fn finish_empty(mut self) -> fmt::Result {
+        self.write_pending_id()?;
         self.renderer.out.write_str("/>\n")
     }
 }
@@ -597,6 +651,7 @@ pub struct Element {
     text: Option<String>,
     children: Vec<Element>,
     siblings: Vec<Element>,
+    inline: bool,
 }
 
 impl Element {
@@ -611,6 +666,7 @@ impl Element {
             text: None,
             children: Vec::default(),
             siblings: Vec::default(),
+            inline: false,
         }
     }
 
@@ -659,6 +715,54 @@ impl Element {
         self
     }
 
+    /// Write this Element without a line break after its opening and closing tag.
+    ///
+    /// Line breaks between the parts of a text element would be drawn as spaces.
+    #[must_use]
+    pub fn inline(mut self) -> Self {
+        self.inline = true;
+        self
+    }
+
+    /// Set this Element's `id`-attribute, unless it already has one.
+    pub(crate) fn with_default_id(mut self, id: String) -> Self {
+        self.attributes.entry("id".to_owned()).or_insert(id);
+        self
+    }
+
+    /// The `id`-attributes of this Element, its children and its siblings, in
+    /// the order they are written.
+    pub(crate) fn ids(&self) -> Vec<&str> {
+        let mut ids = Vec::new();
+        self.collect_ids(&mut ids);
+        ids
+    }
+
+    fn collect_ids<'a>(&'a self, ids: &mut Vec<&'a str>) {
+        ids.extend(self.attributes.get("id").map(String::as_str));
+        for e in self.children.iter().chain(&self.siblings) {
+            e.collect_ids(ids);
+        }
+    }
+
+    /// The source spans written to this element and its descendants as
+    /// `data-source-*` attributes, along with the element's id, in document order.
+    pub(crate) fn source_spans(&self) -> Vec<(Option<&str>, crate::SourceSpan)> {
+        let mut spans = Vec::new();
+        self.collect_source_spans(&mut spans);
+        spans
+    }
+
+    fn collect_source_spans<'a>(&'a self, spans: &mut Vec<(Option<&'a str>, crate::SourceSpan)>) {
+        let get = |key: &str| self.attributes.get(key).map(String::as_str);
+        if let Ok(Some(span)) = crate::SourceSpan::from_properties("data-source-", get) {
+            spans.push((get("id"), span));
+        }
+        for e in self.children.iter().chain(&self.siblings) {
+            e.collect_source_spans(spans);
+        }
+    }
+
     /// Add a child to this Element
     ///
     /// Children is written within the opening and closing tag of this Element.
@@ -781,10 +885,11 @@ impl ::std::fmt::Display for Element {
         for (k, v) in attrs {
             write!(f, " {}=\"{}\"", encode_minimal(k), encode_minimal(v))?;
         }
+        let line_break = if self.inline { "" } else { "\n" };
         if self.text.is_none() && self.children.is_empty() {
-            f.write_str("/>\n")?;
+            write!(f, "/>{line_break}")?;
         } else {
-            f.write_str(">\n")?;
+            write!(f, ">{line_break}")?;
         }
         if let Some(t) = &self.text {
             f.write_str(t)?;
@@ -794,7 +899,7 @@ impl ::std::fmt::Display for Element {
         }
 
         if self.text.is_some() || !self.children.is_empty() {
-            writeln!(f, "</{}>", self.name)?;
+            write!(f, "</{}>{line_break}", self.name)?;
         }
         for sibling in &self.siblings {
             write!(f, "{sibling}")?;
@@ -1211,6 +1316,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pending_id_goes_to_the_next_element_unless_it_has_one() {
+        let mut out = String::new();
+        let mut renderer = super::Renderer::new(&mut out);
+        renderer.set_pending_id("a".to_owned());
+        let mut g = renderer.start_element("g").unwrap();
+        g.attr("class", "x").unwrap();
+        g.finish().unwrap();
+        renderer.set_pending_id("b".to_owned());
+        let mut rect = renderer.start_element("rect").unwrap();
+        rect.attr("id", "explicit").unwrap();
+        rect.finish_empty().unwrap();
+        renderer
+            .path(&super::PathData::new(super::HDir::LTR))
+            .unwrap();
+        renderer.set_pending_id("c".to_owned());
+        renderer
+            .write_element(super::Element::new("text").set("id", "explicit"))
+            .unwrap();
+        renderer.end_element("g").unwrap();
+        assert_eq!(
+            out,
+            "<g class=\"x\" id=\"a\">\n<rect id=\"explicit\"/>\n<path d=\"\"/>\n\
+             <text id=\"explicit\"/>\n</g>\n"
+        );
+    }
+
     const PAYLOADS: &[&str] = &[
         r#""><script>alert(1)</script>"#,
         r#"' onload='alert(1)"#,
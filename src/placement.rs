@@ -0,0 +1,390 @@
+//! The absolute layout of a node tree, for drawing diagrams without SVG.
+//!
+//! [`Diagram::layout`](crate::Diagram::layout) places every node of a diagram
+//! using the same geometry the SVG output is built from, and returns a tree of
+//! [`PlacedNode`]s. Each of them knows its kind, label and bounding box on
+//! screen, and the [`Primitive`]s it draws itself with; children draw
+//! themselves. Drawing all primitives of all nodes reproduces the diagram.
+//!
+//! # Example
+//! ```rust
+//! use railroad::*;
+//! use railroad::placement::Primitive;
+//!
+//! let diagram = Diagram::new(Sequence::new(vec![
+//!     Box::new(SimpleStart) as Box<dyn Node>,
+//!     Box::new(Terminal::new("BEGIN".to_owned())),
+//!     Box::new(SimpleEnd),
+//! ]));
+//! let layout = diagram.layout();
+//! let sequence = &layout.children[0];
+//! let terminal = &sequence.children[1];
+//! assert_eq!(terminal.kind, "terminal");
+//! assert_eq!(terminal.label.as_deref(), Some("BEGIN"));
+//! assert!(terminal.x > sequence.x);
+//! assert!(matches!(terminal.primitives[0], Primitive::Rect { radius: 10, .. }));
+//! ```
+
+use std::fmt;
+
+use crate::{HDir, Node, NodeGeometry, RenderBackend, layout::Transform, rich_text, svg};
+
+/// A node, placed at its absolute position in a diagram.
+///
+/// For diagrams laid out top-to-bottom, the `entry_height` is measured from
+/// the node's left edge, as that is where the path enters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedNode {
+    /// The kind of node, as in its [`Description`](crate::description::Description).
+    pub kind: String,
+    /// The node's label or text, if it has one.
+    pub label: Option<String>,
+    /// The left edge of the node's bounding box.
+    pub x: i64,
+    /// The top edge of the node's bounding box.
+    pub y: i64,
+    /// The width of the node's bounding box.
+    pub width: i64,
+    /// The height of the node's bounding box.
+    pub height: i64,
+    /// The distance from the top of the node to where the path enters.
+    pub entry_height: i64,
+    /// What the node draws, excluding its children.
+    pub primitives: Vec<Primitive>,
+    /// The node's children, placed.
+    pub children: Vec<PlacedNode>,
+}
+
+impl PlacedNode {
+    /// Place `node`, laid out as `geo`, at `(x, y)` without primitives or
+    /// children, taking the kind and label from its description.
+    ///
+    /// This is how [`Node::place_with_geometry`] places nodes by default.
+    #[must_use]
+    pub fn leaf<N: Node + ?Sized>(node: &N, x: i64, y: i64, geo: &NodeGeometry) -> Self {
+        let desc = node.describe();
+        let label = desc.property("label").or_else(|| desc.property("text"));
+        Self {
+            label: label.map(str::to_owned),
+            kind: desc.kind,
+            x,
+            y,
+            width: geo.width,
+            height: geo.height,
+            entry_height: geo.entry_height,
+            primitives: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Move this node and its children by `transform`.
+    pub(crate) fn transform(self, transform: Transform) -> Self {
+        let (x0, y0) = transform.apply(self.x, self.y);
+        let (x1, y1) = transform.apply(self.x + self.width, self.y + self.height);
+        let (entry_x, entry_y) = transform.apply(self.x, self.y + self.entry_height);
+        let (x, y) = (x0.min(x1), y0.min(y1));
+        Self {
+            entry_height: if transform.swaps_axes() {
+                entry_x - x
+            } else {
+                entry_y - y
+            },
+            x,
+            y,
+            width: (x1 - x0).abs(),
+            height: (y1 - y0).abs(),
+            primitives: self
+                .primitives
+                .into_iter()
+                .map(|p| p.transform(transform))
+                .collect(),
+            children: self
+                .children
+                .into_iter()
+                .map(|c| c.transform(transform))
+                .collect(),
+            ..self
+        }
+    }
+}
+
+/// A shape drawn by a [`PlacedNode`], in absolute coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Primitive {
+    /// A straight line.
+    Line {
+        /// Where the line starts.
+        from: (i64, i64),
+        /// Where the line ends.
+        to: (i64, i64),
+    },
+    /// A quarter of a circle.
+    Arc {
+        /// Where the arc starts.
+        from: (i64, i64),
+        /// Where the arc ends.
+        to: (i64, i64),
+        /// The radius of the circle.
+        radius: i64,
+        /// Whether the arc turns clockwise on screen.
+        clockwise: bool,
+    },
+    /// A rectangle, possibly with rounded corners.
+    Rect {
+        /// The left edge.
+        x: i64,
+        /// The top edge.
+        y: i64,
+        /// The width.
+        width: i64,
+        /// The height.
+        height: i64,
+        /// The radius of the corners; zero for square ones.
+        radius: i64,
+    },
+    /// Lines of text, horizontally centered at `x`.
+    ///
+    /// The baseline of the middle line, or where it would be for an even
+    /// number of lines, is at `y`.
+    Text {
+        /// The horizontal center of the text.
+        x: i64,
+        /// The baseline of the middle line.
+        y: i64,
+        /// The lines of text, without styling.
+        lines: Vec<String>,
+        /// The distance between the baselines of two lines.
+        line_height: i64,
+    },
+}
+
+impl Primitive {
+    pub(crate) fn transform(self, transform: Transform) -> Self {
+        match self {
+            Self::Line { from, to } => Self::Line {
+                from: transform.apply(from.0, from.1),
+                to: transform.apply(to.0, to.1),
+            },
+            Self::Arc {
+                from,
+                to,
+                radius,
+                clockwise,
+            } => Self::Arc {
+                from: transform.apply(from.0, from.1),
+                to: transform.apply(to.0, to.1),
+                radius,
+                clockwise: clockwise != transform.flips(),
+            },
+            Self::Rect {
+                x,
+                y,
+                width,
+                height,
+                radius,
+            } => {
+                let (x0, y0) = transform.apply(x, y);
+                let (x1, y1) = transform.apply(x + width, y + height);
+                Self::Rect {
+                    x: x0.min(x1),
+                    y: y0.min(y1),
+                    width: (x1 - x0).abs(),
+                    height: (y1 - y0).abs(),
+                    radius,
+                }
+            }
+            // Text stays upright: its anchor moves, and the baseline stays 5
+            // units below the center of the text, as in the SVG output.
+            Self::Text {
+                x,
+                y,
+                lines,
+                line_height,
+            } => {
+                let (x, y) = transform.apply(x, y - 5);
+                Self::Text {
+                    x,
+                    y: y + 5,
+                    lines,
+                    line_height,
+                }
+            }
+        }
+    }
+}
+
+/// Place `node` at `(x, y)`, recording what `emit` draws through the backend.
+pub(crate) fn place<N: Node + ?Sized>(
+    node: &N,
+    x: i64,
+    y: i64,
+    geo: &NodeGeometry,
+    emit: impl FnOnce(&mut Placer) -> fmt::Result,
+) -> PlacedNode {
+    let mut placer = Placer::default();
+    emit(&mut placer).expect("placement backend is infallible");
+    PlacedNode {
+        primitives: placer.primitives,
+        children: placer.children,
+        ..PlacedNode::leaf(node, x, y, geo)
+    }
+}
+
+/// `RenderBackend` implementation that records primitives and placed children.
+#[derive(Default)]
+pub(crate) struct Placer {
+    primitives: Vec<Primitive>,
+    children: Vec<PlacedNode>,
+}
+
+impl RenderBackend for Placer {
+    fn push_path(&mut self, path: svg::PathData) -> fmt::Result {
+        self.primitives.extend(path_primitives(&path.to_string()));
+        Ok(())
+    }
+
+    fn push_rect(&mut self, x: i64, y: i64, width: i64, height: i64) -> fmt::Result {
+        self.push_rounded_rect(x, y, width, height, 0)
+    }
+
+    fn push_rounded_rect(
+        &mut self,
+        x: i64,
+        y: i64,
+        width: i64,
+        height: i64,
+        radius: i64,
+    ) -> fmt::Result {
+        self.primitives.push(Primitive::Rect {
+            x,
+            y,
+            width,
+            height,
+            radius,
+        });
+        Ok(())
+    }
+
+    fn push_text(&mut self, x: i64, y: i64, text: &str) -> fmt::Result {
+        self.primitives.push(Primitive::Text {
+            x,
+            y,
+            lines: vec![text.to_owned()],
+            line_height: 0,
+        });
+        Ok(())
+    }
+
+    fn push_text_lines(
+        &mut self,
+        x: i64,
+        y: i64,
+        lines: &[rich_text::Line<'_>],
+        line_height: i64,
+    ) -> fmt::Result {
+        self.primitives.push(Primitive::Text {
+            x,
+            y,
+            lines: lines
+                .iter()
+                .map(|line| line.iter().map(|(_, text)| *text).collect())
+                .collect(),
+            line_height,
+        });
+        Ok(())
+    }
+
+    fn push_child(
+        &mut self,
+        child: &dyn Node,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        self.children
+            .push(child.place_with_geometry(x, y, h_dir, geo));
+        Ok(())
+    }
+}
+
+/// The lines and arcs drawn by the path data `d`, as written by [`svg::PathData`].
+pub(crate) fn path_primitives(d: &str) -> Vec<Primitive> {
+    let mut tokens = d.split_whitespace();
+    let mut next = || tokens.next();
+    let mut primitives = Vec::new();
+    let mut at = (0, 0);
+    while let Some(command) = next() {
+        let mut args = [0; 7];
+        let count = match command {
+            "M" | "m" | "l" => 2,
+            "h" | "v" => 1,
+            "a" => 7,
+            _ => unreachable!("unknown path command `{command}`"),
+        };
+        for arg in &mut args[..count] {
+            *arg = next()
+                .and_then(|t| t.parse().ok())
+                .expect("path data is well-formed");
+        }
+        let from = at;
+        at = match command {
+            "M" => (args[0], args[1]),
+            "m" | "l" => (at.0 + args[0], at.1 + args[1]),
+            "h" => (at.0 + args[0], at.1),
+            "v" => (at.0, at.1 + args[0]),
+            _ => (at.0 + args[5], at.1 + args[6]),
+        };
+        match command {
+            "M" | "m" => {}
+            "a" => primitives.push(Primitive::Arc {
+                from,
+                to: at,
+                radius: args[0],
+                clockwise: args[4] == 1,
+            }),
+            _ => primitives.push(Primitive::Line { from, to: at }),
+        }
+    }
+    primitives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Primitive, path_primitives};
+    use crate::svg::{Arc, HDir, PathData};
+
+    #[test]
+    fn path_data_becomes_lines_and_arcs() {
+        let path = PathData::new(HDir::LTR)
+            .move_to(10, 20)
+            .horizontal(5)
+            .arc(10, Arc::WestToSouth)
+            .move_rel(-5, 0)
+            .vertical(-3)
+            .line_rel(2, 2);
+        assert_eq!(
+            path_primitives(&path.to_string()),
+            [
+                Primitive::Line {
+                    from: (10, 20),
+                    to: (15, 20)
+                },
+                Primitive::Arc {
+                    from: (15, 20),
+                    to: (25, 30),
+                    radius: 10,
+                    clockwise: true
+                },
+                Primitive::Line {
+                    from: (20, 30),
+                    to: (20, 27)
+                },
+                Primitive::Line {
+                    from: (20, 27),
+                    to: (22, 29)
+                },
+            ]
+        );
+    }
+}
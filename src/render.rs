@@ -1,6 +1,8 @@
 //! A shorthand for rendering diagrams to images, using `resvg`'s default options.
 //!
-//! This module is only available if the `resvg`-feature is active.
+//! This module is only available if the `resvg`-feature is active. The `pdf`-feature adds
+//! `to_pdf`, which draws the same parsed tree as vectors, so the render-safe stylesheets
+//! give matching results in both formats.
 
 /// Errors encountered while rendering
 #[derive(Debug)]
@@ -71,6 +73,20 @@ static USVG_OPTS: std::sync::LazyLock<resvg::usvg::Options> = std::sync::LazyLoc
     opts
 });
 
+/// Parse the given svg-source the same way for every output format.
+fn parse(svg_src: &str) -> Result<resvg::usvg::Tree, Error> {
+    let xml_tree = resvg::usvg::roxmltree::Document::parse_with_options(
+        svg_src,
+        resvg::usvg::roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        },
+    )
+    .map_err(Error::XMLParse)?;
+
+    resvg::usvg::Tree::from_xmltree(&xml_tree, &USVG_OPTS).map_err(Error::SVGParse)
+}
+
 /// Render the given svg-source to an image in png-format.
 ///
 /// ```rust
@@ -88,17 +104,7 @@ static USVG_OPTS: std::sync::LazyLock<resvg::usvg::Options> = std::sync::LazyLoc
 /// ```
 #[allow(clippy::missing_errors_doc)]
 pub fn to_png(svg_src: &str, fit_to: &FitTo) -> Result<Vec<u8>, Error> {
-    let xml_tree = resvg::usvg::roxmltree::Document::parse_with_options(
-        svg_src,
-        resvg::usvg::roxmltree::ParsingOptions {
-            allow_dtd: true,
-            ..Default::default()
-        },
-    )
-    .map_err(Error::XMLParse)?;
-
-    let svg_tree =
-        resvg::usvg::Tree::from_xmltree(&xml_tree, &USVG_OPTS).map_err(Error::SVGParse)?;
+    let svg_tree = parse(svg_src)?;
 
     let size = fit_to
         .fit_to_size(svg_tree.size().to_int_size())
@@ -117,6 +123,626 @@ pub fn to_png(svg_src: &str, fit_to: &FitTo) -> Result<Vec<u8>, Error> {
     Ok(png_buf)
 }
 
+/// Points per pixel, as SVG assumes 96 pixels and PDF 72 points per inch.
+#[cfg(feature = "pdf")]
+const PT_PER_PX: f32 = 0.75;
+
+/// The page a diagram is placed on when rendering to PDF.
+///
+/// Diagrams are drawn at their natural size and only scaled down if they do not fit between
+/// the margins, which are given in points (1/72 of an inch). Paper sizes are turned to
+/// landscape for diagrams that are wider than they are tall.
+///
+/// This enum is only available if the `pdf`-feature is active.
+#[cfg(feature = "pdf")]
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum PageSize {
+    /// The page is exactly as large as the diagram
+    #[default]
+    Fit,
+    /// ISO A4, 210mm x 297mm
+    A4 { margin: f32 },
+    /// US Letter, 8.5in x 11in
+    Letter { margin: f32 },
+}
+
+#[cfg(feature = "pdf")]
+impl PageSize {
+    /// The width and height of the page in points, and the transform from diagram- to
+    /// page-coordinates.
+    fn layout(&self, size: resvg::usvg::Size) -> Option<((f32, f32), resvg::tiny_skia::Transform)> {
+        let (width, height) = (size.width() * PT_PER_PX, size.height() * PT_PER_PX);
+        let ((paper_width, paper_height), margin) = match *self {
+            Self::Fit => ((width, height), 0.0),
+            Self::A4 { margin } => ((595.28, 841.89), margin),
+            Self::Letter { margin } => ((612.0, 792.0), margin),
+        };
+        let (page_width, page_height) = if *self != Self::Fit && width > height {
+            (paper_height, paper_width)
+        } else {
+            (paper_width, paper_height)
+        };
+        let (available_width, available_height) =
+            (page_width - 2.0 * margin, page_height - 2.0 * margin);
+        if !(available_width > 0.0 && available_height > 0.0) {
+            return None;
+        }
+        let scale = (available_width / width)
+            .min(available_height / height)
+            .min(1.0);
+        let dx = (page_width - width * scale) / 2.0;
+        let dy = (page_height - height * scale) / 2.0;
+        // PDF's y-axis points upwards
+        let ts = resvg::tiny_skia::Transform::from_row(
+            PT_PER_PX * scale,
+            0.0,
+            0.0,
+            -PT_PER_PX * scale,
+            dx,
+            page_height - dy,
+        );
+        Some(((page_width, page_height), ts))
+    }
+}
+
+/// Render the given svg-source to a single-page vector PDF.
+///
+/// Shapes and text stay vectors; the fonts chosen while parsing the svg-source are embedded
+/// as subsets of the glyphs actually used, so text remains selectable and searchable.
+///
+/// This function is only available if the `pdf`-feature is active.
+///
+/// ```rust
+/// use railroad::*;
+///
+/// let mut seq = Sequence::default();
+/// seq.push(Box::new(Start) as Box<dyn Node>)
+///    .push(Box::new(Terminal::new("BEGIN".to_owned())))
+///    .push(Box::new(NonTerminal::new("syntax".to_owned())))
+///    .push(Box::new(End));
+/// let dia = Diagram::new_with_stylesheet(seq, &Stylesheet::LightRendersafe);
+/// let svg_src = dia.to_string();
+///
+/// let pdf_buffer: Vec<u8> =
+///     render::to_pdf(&svg_src, &render::PageSize::A4 { margin: 36.0 }).unwrap();
+/// ```
+#[cfg(feature = "pdf")]
+#[allow(clippy::missing_errors_doc)]
+pub fn to_pdf(svg_src: &str, page_size: &PageSize) -> Result<Vec<u8>, Error> {
+    use pdf_writer::{Filter, Finish, Name, Pdf, Rect, Ref};
+
+    let svg_tree = parse(svg_src)?;
+    let ((page_width, page_height), ts) = page_size
+        .layout(svg_tree.size())
+        .ok_or(Error::InvalidSize)?;
+
+    let mut content = PdfContent::new(svg_tree.fontdb());
+    content.transform(ts);
+    content.group(svg_tree.root(), 1.0);
+    let PdfContent {
+        content,
+        fonts,
+        alphas,
+        ..
+    } = content;
+
+    let mut last_id = 0;
+    let mut next_id = || {
+        last_id += 1;
+        Ref::new(last_id)
+    };
+    let catalog_id = next_id();
+    let pages_id = next_id();
+    let page_id = next_id();
+    let content_id = next_id();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(pages_id);
+    pdf.pages(pages_id).kids([page_id]).count(1);
+
+    let mut font_refs = Vec::with_capacity(fonts.len());
+    for font in &fonts {
+        let font_id = next_id();
+        font.write(&mut pdf, font_id, &mut next_id, svg_tree.fontdb())?;
+        font_refs.push(font_id);
+    }
+    let mut alpha_refs = Vec::with_capacity(alphas.len());
+    for (fill, stroke) in &alphas {
+        let alpha_id = next_id();
+        pdf.ext_graphics(alpha_id)
+            .non_stroking_alpha(*fill)
+            .stroking_alpha(*stroke);
+        alpha_refs.push(alpha_id);
+    }
+
+    let mut page = pdf.page(page_id);
+    page.parent(pages_id)
+        .media_box(Rect::new(0.0, 0.0, page_width, page_height))
+        .contents(content_id);
+    let mut resources = page.resources();
+    if !font_refs.is_empty() {
+        let mut font_dict = resources.fonts();
+        for (i, font_id) in font_refs.iter().enumerate() {
+            font_dict.pair(Name(PdfContent::font_name(i).as_bytes()), *font_id);
+        }
+    }
+    if !alpha_refs.is_empty() {
+        let mut alpha_dict = resources.ext_g_states();
+        for (i, alpha_id) in alpha_refs.iter().enumerate() {
+            alpha_dict.pair(Name(PdfContent::alpha_name(i).as_bytes()), *alpha_id);
+        }
+    }
+    resources.finish();
+    page.finish();
+
+    pdf.stream(content_id, &deflate(&content.finish()))
+        .filter(Filter::FlateDecode);
+
+    Ok(pdf.finish())
+}
+
+#[cfg(feature = "pdf")]
+fn deflate(data: &[u8]) -> Vec<u8> {
+    miniz_oxide::deflate::compress_to_vec_zlib(data, 6)
+}
+
+/// The glyphs used from one font, and the text they stand for.
+#[cfg(feature = "pdf")]
+struct PdfFont {
+    id: resvg::usvg::fontdb::ID,
+    units_per_em: f32,
+    glyphs: std::collections::BTreeMap<u16, String>,
+}
+
+#[cfg(feature = "pdf")]
+impl PdfFont {
+    const SYSTEM_INFO: pdf_writer::types::SystemInfo<'static> = pdf_writer::types::SystemInfo {
+        registry: pdf_writer::Str(b"Adobe"),
+        ordering: pdf_writer::Str(b"Identity"),
+        supplement: 0,
+    };
+
+    /// Write the font as a composite font, embedding a subset of the font's data.
+    fn write(
+        &self,
+        pdf: &mut pdf_writer::Pdf,
+        font_id: pdf_writer::Ref,
+        next_id: &mut impl FnMut() -> pdf_writer::Ref,
+        fontdb: &resvg::usvg::fontdb::Database,
+    ) -> Result<(), Error> {
+        use pdf_writer::types::{CidFontType, FontFlags, UnicodeCmap};
+        use pdf_writer::{Filter, Name, Rect};
+
+        let (cid_id, descriptor_id, cmap_id, file_id) =
+            (next_id(), next_id(), next_id(), next_id());
+        let base_font = format!(
+            "{}+{}",
+            self.subset_tag(),
+            fontdb
+                .face(self.id)
+                .map_or("Unknown", |info| info.post_script_name.as_str())
+                .replace(|c: char| !c.is_ascii_graphic(), "")
+        );
+
+        fontdb
+            .with_face_data(self.id, |data, index| {
+                let face = ttf_parser::Face::parse(data, index)
+                    .map_err(|e| Error::Encoding(e.to_string()))?;
+                let glyphs = self.glyphs.keys().copied().collect::<Vec<_>>();
+                let subset = subsetter::subset(data, index, subsetter::Profile::pdf(&glyphs))
+                    .map_err(|e| Error::Encoding(format!("{e:?}")))?;
+                let is_cff = face.tables().cff.is_some();
+                let to_pdf_units = |v: f32| v * 1000.0 / self.units_per_em;
+
+                pdf.type0_font(font_id)
+                    .base_font(Name(base_font.as_bytes()))
+                    .encoding_predefined(Name(b"Identity-H"))
+                    .descendant_font(cid_id)
+                    .to_unicode(cmap_id);
+
+                let mut cid = pdf.cid_font(cid_id);
+                cid.subtype(if is_cff {
+                    CidFontType::Type0
+                } else {
+                    CidFontType::Type2
+                })
+                .base_font(Name(base_font.as_bytes()))
+                .system_info(Self::SYSTEM_INFO)
+                .font_descriptor(descriptor_id)
+                .default_width(0.0);
+                if !is_cff {
+                    cid.cid_to_gid_map_predefined(Name(b"Identity"));
+                }
+                let mut widths = cid.widths();
+                for &glyph in &glyphs {
+                    let advance = face
+                        .glyph_hor_advance(ttf_parser::GlyphId(glyph))
+                        .unwrap_or(0);
+                    widths.consecutive(glyph, [to_pdf_units(f32::from(advance))]);
+                }
+                drop(widths);
+                drop(cid);
+
+                let mut flags = FontFlags::SYMBOLIC;
+                flags.set(FontFlags::ITALIC, face.is_italic());
+                flags.set(FontFlags::FIXED_PITCH, face.is_monospaced());
+                let bbox = face.global_bounding_box();
+                let ascent = to_pdf_units(f32::from(face.ascender()));
+                let mut descriptor = pdf.font_descriptor(descriptor_id);
+                descriptor
+                    .name(Name(base_font.as_bytes()))
+                    .flags(flags)
+                    .bbox(Rect::new(
+                        to_pdf_units(f32::from(bbox.x_min)),
+                        to_pdf_units(f32::from(bbox.y_min)),
+                        to_pdf_units(f32::from(bbox.x_max)),
+                        to_pdf_units(f32::from(bbox.y_max)),
+                    ))
+                    .italic_angle(face.italic_angle())
+                    .ascent(ascent)
+                    .descent(to_pdf_units(f32::from(face.descender())))
+                    .cap_height(
+                        face.capital_height()
+                            .map_or(ascent, |h| to_pdf_units(f32::from(h))),
+                    )
+                    .stem_v(80.0);
+                if is_cff {
+                    descriptor.font_file3(file_id);
+                } else {
+                    descriptor.font_file2(file_id);
+                }
+                drop(descriptor);
+
+                let mut cmap = UnicodeCmap::new(Name(b"Custom"), Self::SYSTEM_INFO);
+                for (&glyph, text) in &self.glyphs {
+                    if !text.is_empty() {
+                        cmap.pair_with_multiple(glyph, text.chars());
+                    }
+                }
+                pdf.cmap(cmap_id, &deflate(&cmap.finish()))
+                    .filter(Filter::FlateDecode);
+
+                let compressed = deflate(&subset);
+                let mut file = pdf.stream(file_id, &compressed);
+                file.filter(Filter::FlateDecode);
+                if is_cff {
+                    file.pair(Name(b"Subtype"), Name(b"OpenType"));
+                } else {
+                    file.pair(
+                        Name(b"Length1"),
+                        i32::try_from(subset.len()).unwrap_or(i32::MAX),
+                    );
+                }
+                Ok(())
+            })
+            .unwrap_or_else(|| Err(Error::Encoding("font data is unavailable".to_owned())))
+    }
+
+    /// The six-letter tag that marks the font as a subset, derived from the glyphs it contains.
+    fn subset_tag(&self) -> String {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.glyphs.keys().collect::<Vec<_>>().hash(&mut hasher);
+        let mut hash = hasher.finish();
+        (0..6)
+            .map(|_| {
+                let c = char::from(b'A' + (hash % 26) as u8);
+                hash /= 26;
+                c
+            })
+            .collect()
+    }
+}
+
+/// Translates a parsed svg-tree into the content stream of a PDF page.
+#[cfg(feature = "pdf")]
+struct PdfContent<'a> {
+    fontdb: &'a resvg::usvg::fontdb::Database,
+    content: pdf_writer::Content,
+    fonts: Vec<PdfFont>,
+    /// Fill- and stroke-opacity of the graphics states in use
+    alphas: Vec<(f32, f32)>,
+}
+
+#[cfg(feature = "pdf")]
+impl<'a> PdfContent<'a> {
+    fn new(fontdb: &'a resvg::usvg::fontdb::Database) -> Self {
+        Self {
+            fontdb,
+            content: pdf_writer::Content::new(),
+            fonts: Vec::new(),
+            alphas: Vec::new(),
+        }
+    }
+
+    fn font_name(idx: usize) -> String {
+        format!("F{idx}")
+    }
+
+    fn alpha_name(idx: usize) -> String {
+        format!("GS{idx}")
+    }
+
+    fn transform(&mut self, ts: resvg::tiny_skia::Transform) {
+        if !ts.is_identity() {
+            self.content
+                .transform([ts.sx, ts.ky, ts.kx, ts.sy, ts.tx, ts.ty]);
+        }
+    }
+
+    fn set_alpha(&mut self, fill: f32, stroke: f32) {
+        if fill >= 1.0 && stroke >= 1.0 {
+            return;
+        }
+        let idx = self
+            .alphas
+            .iter()
+            .position(|alpha| *alpha == (fill, stroke))
+            .unwrap_or_else(|| {
+                self.alphas.push((fill, stroke));
+                self.alphas.len() - 1
+            });
+        self.content
+            .set_parameters(pdf_writer::Name(Self::alpha_name(idx).as_bytes()));
+    }
+
+    /// The index of the given font, if its data can be embedded.
+    fn font(&mut self, id: resvg::usvg::fontdb::ID) -> Option<usize> {
+        if let Some(idx) = self.fonts.iter().position(|font| font.id == id) {
+            return Some(idx);
+        }
+        let units_per_em = self
+            .fontdb
+            .with_face_data(id, |data, index| {
+                ttf_parser::Face::parse(data, index)
+                    .ok()
+                    .map(|face| f32::from(face.units_per_em()))
+            })
+            .flatten()?;
+        self.fonts.push(PdfFont {
+            id,
+            units_per_em,
+            glyphs: std::collections::BTreeMap::new(),
+        });
+        Some(self.fonts.len() - 1)
+    }
+
+    fn group(&mut self, group: &resvg::usvg::Group, opacity: f32) {
+        let opacity = opacity * group.opacity().get();
+        self.content.save_state();
+        self.transform(group.transform());
+        for child in group.children() {
+            match child {
+                resvg::usvg::Node::Group(group) => self.group(group, opacity),
+                resvg::usvg::Node::Path(path) if path.is_visible() => {
+                    self.path(
+                        path.data(),
+                        path.fill(),
+                        path.stroke(),
+                        path.paint_order(),
+                        opacity,
+                    );
+                }
+                resvg::usvg::Node::Text(text) => self.text(text, opacity),
+                _ => {}
+            }
+        }
+        self.content.restore_state();
+    }
+
+    fn set_stroke(&mut self, stroke: &resvg::usvg::Stroke, width_scale: f32) {
+        use pdf_writer::types::{LineCapStyle, LineJoinStyle};
+        use resvg::usvg::{LineCap, LineJoin};
+
+        self.content
+            .set_line_width(stroke.width().get() * width_scale)
+            .set_line_cap(match stroke.linecap() {
+                LineCap::Butt => LineCapStyle::ButtCap,
+                LineCap::Round => LineCapStyle::RoundCap,
+                LineCap::Square => LineCapStyle::ProjectingSquareCap,
+            })
+            .set_line_join(match stroke.linejoin() {
+                LineJoin::Miter | LineJoin::MiterClip => LineJoinStyle::MiterJoin,
+                LineJoin::Round => LineJoinStyle::RoundJoin,
+                LineJoin::Bevel => LineJoinStyle::BevelJoin,
+            })
+            .set_miter_limit(stroke.miterlimit().get());
+        if let Some(dashes) = stroke.dasharray() {
+            self.content.set_dash_pattern(
+                dashes.iter().map(|dash| dash * width_scale),
+                stroke.dashoffset() * width_scale,
+            );
+        }
+    }
+
+    fn path(
+        &mut self,
+        data: &resvg::tiny_skia::Path,
+        fill: Option<&resvg::usvg::Fill>,
+        stroke: Option<&resvg::usvg::Stroke>,
+        paint_order: resvg::usvg::PaintOrder,
+        opacity: f32,
+    ) {
+        use resvg::tiny_skia::PathSegment;
+
+        let fill = fill.and_then(|fill| Some((fill, paint_color(fill.paint())?)));
+        let stroke = stroke.and_then(|stroke| Some((stroke, paint_color(stroke.paint())?)));
+        if fill.is_none() && stroke.is_none() {
+            return;
+        }
+
+        self.content.save_state();
+        self.set_alpha(
+            fill.map_or(1.0, |(fill, _)| fill.opacity().get() * opacity),
+            stroke.map_or(1.0, |(stroke, _)| stroke.opacity().get() * opacity),
+        );
+        if let Some((_, color)) = fill {
+            let (r, g, b) = rgb(color);
+            self.content.set_fill_rgb(r, g, b);
+        }
+        if let Some((stroke, color)) = stroke {
+            let (r, g, b) = rgb(color);
+            self.content.set_stroke_rgb(r, g, b);
+            self.set_stroke(stroke, 1.0);
+        }
+
+        let mut ops = [fill.map(|(fill, _)| Some(fill)), stroke.map(|_| None)];
+        if paint_order == resvg::usvg::PaintOrder::StrokeAndFill {
+            ops.reverse();
+        }
+        for op in ops.into_iter().flatten() {
+            let mut last = resvg::tiny_skia::Point::zero();
+            for segment in data.segments() {
+                match segment {
+                    PathSegment::MoveTo(p) => {
+                        self.content.move_to(p.x, p.y);
+                        last = p;
+                    }
+                    PathSegment::LineTo(p) => {
+                        self.content.line_to(p.x, p.y);
+                        last = p;
+                    }
+                    PathSegment::QuadTo(p1, p) => {
+                        self.content.cubic_to(
+                            last.x + 2.0 / 3.0 * (p1.x - last.x),
+                            last.y + 2.0 / 3.0 * (p1.y - last.y),
+                            p.x + 2.0 / 3.0 * (p1.x - p.x),
+                            p.y + 2.0 / 3.0 * (p1.y - p.y),
+                            p.x,
+                            p.y,
+                        );
+                        last = p;
+                    }
+                    PathSegment::CubicTo(p1, p2, p) => {
+                        self.content.cubic_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
+                        last = p;
+                    }
+                    PathSegment::Close => {
+                        self.content.close_path();
+                    }
+                }
+            }
+            match op {
+                Some(fill) if fill.rule() == resvg::usvg::FillRule::EvenOdd => {
+                    self.content.fill_even_odd();
+                }
+                Some(_) => {
+                    self.content.fill_nonzero();
+                }
+                None => {
+                    self.content.stroke();
+                }
+            }
+        }
+        self.content.restore_state();
+    }
+
+    fn text(&mut self, text: &resvg::usvg::Text, opacity: f32) {
+        use pdf_writer::types::TextRenderingMode;
+
+        for span in text.layouted().iter().filter(|span| span.visible) {
+            let fill = span
+                .fill
+                .as_ref()
+                .and_then(|fill| Some((fill, paint_color(fill.paint())?)));
+            let stroke = span
+                .stroke
+                .as_ref()
+                .and_then(|stroke| Some((stroke, paint_color(stroke.paint())?)));
+            let mode = match (fill, stroke) {
+                (Some(_), None) => TextRenderingMode::Fill,
+                (None, Some(_)) => TextRenderingMode::Stroke,
+                (Some(_), Some(_)) => TextRenderingMode::FillStroke,
+                (None, None) => continue,
+            };
+
+            for decoration in [&span.underline, &span.overline] {
+                self.decoration(decoration.as_ref(), opacity);
+            }
+
+            self.content.save_state();
+            self.set_alpha(
+                fill.map_or(1.0, |(fill, _)| fill.opacity().get() * opacity),
+                stroke.map_or(1.0, |(stroke, _)| stroke.opacity().get() * opacity),
+            );
+            if let Some((_, color)) = fill {
+                let (r, g, b) = rgb(color);
+                self.content.set_fill_rgb(r, g, b);
+            }
+            if let Some((stroke, color)) = stroke {
+                let (r, g, b) = rgb(color);
+                self.content.set_stroke_rgb(r, g, b);
+                // Strokes of glyphs are measured in text space, which is scaled by the font size
+                self.set_stroke(stroke, 1.0 / span.font_size.get());
+            }
+            self.content.begin_text();
+            self.content.set_text_rendering_mode(mode);
+            let mut current_font = None;
+            for glyph in &span.positioned_glyphs {
+                let Some(idx) = self.font(glyph.font) else {
+                    continue;
+                };
+                let font = &mut self.fonts[idx];
+                font.glyphs
+                    .entry(glyph.id.0)
+                    .or_insert_with(|| glyph.text.clone());
+                let ts = glyph
+                    .transform()
+                    .pre_scale(font.units_per_em, -font.units_per_em);
+                if current_font != Some(idx) {
+                    self.content
+                        .set_font(pdf_writer::Name(Self::font_name(idx).as_bytes()), 1.0);
+                    current_font = Some(idx);
+                }
+                self.content
+                    .set_text_matrix([ts.sx, ts.ky, ts.kx, ts.sy, ts.tx, ts.ty])
+                    .show(pdf_writer::Str(&glyph.id.0.to_be_bytes()));
+            }
+            self.content.end_text();
+            self.content.restore_state();
+
+            self.decoration(span.line_through.as_ref(), opacity);
+        }
+    }
+
+    fn decoration(&mut self, decoration: Option<&resvg::usvg::Path>, opacity: f32) {
+        if let Some(path) = decoration.filter(|path| path.is_visible()) {
+            self.path(
+                path.data(),
+                path.fill(),
+                path.stroke(),
+                path.paint_order(),
+                opacity,
+            );
+        }
+    }
+}
+
+/// The solid color to paint with; gradients are approximated by their first stop.
+#[cfg(feature = "pdf")]
+fn paint_color(paint: &resvg::usvg::Paint) -> Option<resvg::usvg::Color> {
+    match paint {
+        resvg::usvg::Paint::Color(color) => Some(*color),
+        resvg::usvg::Paint::LinearGradient(gradient) => {
+            gradient.stops().first().map(resvg::usvg::Stop::color)
+        }
+        resvg::usvg::Paint::RadialGradient(gradient) => {
+            gradient.stops().first().map(resvg::usvg::Stop::color)
+        }
+        resvg::usvg::Paint::Pattern(_) => None,
+    }
+}
+
+#[cfg(feature = "pdf")]
+fn rgb(color: resvg::usvg::Color) -> (f32, f32, f32) {
+    (
+        f32::from(color.red) / 255.0,
+        f32::from(color.green) / 255.0,
+        f32::from(color.blue) / 255.0,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +786,47 @@ mod tests {
         assert_eq!(FitTo::from_size(None, None), FitTo::default());
     }
 
+    // --- PageSize ---
+
+    #[cfg(feature = "pdf")]
+    fn page(page_size: PageSize, width: f32, height: f32) -> (f32, f32, f32) {
+        let ((page_width, page_height), ts) = page_size
+            .layout(resvg::usvg::Size::from_wh(width, height).unwrap())
+            .unwrap();
+        (page_width, page_height, ts.sx)
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn page_size_fit_matches_the_diagram() {
+        assert_eq!(page(PageSize::Fit, 400.0, 100.0), (300.0, 75.0, 0.75));
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn page_size_paper_turns_to_landscape_for_wide_diagrams() {
+        let (width, height, scale) = page(PageSize::A4 { margin: 36.0 }, 400.0, 100.0);
+        assert!(width > height);
+        assert!((scale - 0.75).abs() < f32::EPSILON);
+        let (width, height, _) = page(PageSize::Letter { margin: 36.0 }, 100.0, 400.0);
+        assert_eq!((width, height), (612.0, 792.0));
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn page_size_paper_scales_down_large_diagrams() {
+        let (width, _, scale) = page(PageSize::Letter { margin: 36.0 }, 4000.0, 100.0);
+        assert_eq!(width, 792.0);
+        assert!((4000.0 * scale - (792.0 - 72.0)).abs() < 0.01);
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn page_size_margins_must_leave_room() {
+        let size = resvg::usvg::Size::from_wh(10.0, 10.0).unwrap();
+        assert!(PageSize::A4 { margin: 300.0 }.layout(size).is_none());
+    }
+
     // --- to_png happy path ---
 
     #[test]
@@ -220,4 +887,42 @@ mod tests {
         let result = to_png("<foo/>", &FitTo::default());
         assert!(matches!(result, Err(Error::SVGParse(_))));
     }
+
+    // --- to_pdf ---
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn to_pdf_produces_pdf() {
+        let pdf = to_pdf(&make_svg(), &PageSize::default()).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"), "output is not a PDF");
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn to_pdf_embeds_subsets_of_used_fonts() {
+        // Text is only laid out with a font that is actually installed
+        let Some(family) = USVG_OPTS
+            .fontdb
+            .faces()
+            .find_map(|face| face.families.first())
+        else {
+            return;
+        };
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="20"><text x="0" y="15" font-family="{}">railroad</text></svg>"#,
+            family.0
+        );
+        let pdf = to_pdf(&svg, &PageSize::Fit).unwrap();
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert!(pdf.contains("/Subtype /Type0"));
+        assert!(pdf.contains("/ToUnicode"));
+        assert!(pdf.contains("/FontFile2") || pdf.contains("/FontFile3"));
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn to_pdf_invalid_xml_returns_xml_parse_error() {
+        let result = to_pdf("not xml at all <<<", &PageSize::Fit);
+        assert!(matches!(result, Err(Error::XMLParse(_))));
+    }
 }
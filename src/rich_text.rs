@@ -0,0 +1,290 @@
+//! Text made of differently styled spans, for labels and comments.
+
+use crate::{description::Description, text_lines, text_width};
+
+/// The vertical offset of subscripts on screen.
+const SUBSCRIPT_SHIFT: i64 = 4;
+
+/// The vertical offset of superscripts on screen.
+const SUPERSCRIPT_SHIFT: i64 = -6;
+
+/// Where a [`Span`] sits relative to the baseline of the surrounding text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Baseline {
+    /// On the baseline.
+    #[default]
+    Normal,
+    /// Lowered and smaller, as a subscript.
+    Subscript,
+    /// Raised and smaller, as a superscript.
+    Superscript,
+}
+
+/// A run of text within a [`RichText`], drawn in a single style.
+///
+/// Styled spans become `<tspan>`-elements carrying the matching presentation
+/// attributes, which take precedence over styles inherited from the stylesheet.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// The text of this span; every `'\n'` in it starts a new line.
+    pub text: String,
+    /// Draw the text in a monospace font.
+    pub monospace: bool,
+    /// Draw the text in italics.
+    pub italic: bool,
+    /// Draw the text in bold.
+    pub bold: bool,
+    /// Where the text sits relative to the baseline.
+    pub baseline: Baseline,
+    /// An additional CSS class for the span's `<tspan>`-element.
+    pub class: Option<String>,
+}
+
+impl Span {
+    /// A span of unstyled text.
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// A span of text in a monospace font.
+    #[must_use]
+    pub fn monospace(text: impl Into<String>) -> Self {
+        Self {
+            monospace: true,
+            ..Self::new(text)
+        }
+    }
+
+    /// A span of text in italics.
+    #[must_use]
+    pub fn italic(text: impl Into<String>) -> Self {
+        Self {
+            italic: true,
+            ..Self::new(text)
+        }
+    }
+
+    /// A span of text in bold.
+    #[must_use]
+    pub fn bold(text: impl Into<String>) -> Self {
+        Self {
+            bold: true,
+            ..Self::new(text)
+        }
+    }
+
+    /// A span of text drawn as a subscript.
+    #[must_use]
+    pub fn subscript(text: impl Into<String>) -> Self {
+        Self {
+            baseline: Baseline::Subscript,
+            ..Self::new(text)
+        }
+    }
+
+    /// A span of text drawn as a superscript.
+    #[must_use]
+    pub fn superscript(text: impl Into<String>) -> Self {
+        Self {
+            baseline: Baseline::Superscript,
+            ..Self::new(text)
+        }
+    }
+
+    /// Returns `true` if this span is drawn just like the surrounding text.
+    pub(crate) fn is_plain(&self) -> bool {
+        !self.monospace
+            && !self.italic
+            && !self.bold
+            && self.baseline == Baseline::Normal
+            && self.class.is_none()
+    }
+
+    /// The vertical offset of this span from the baseline, on screen.
+    pub(crate) fn shift(&self) -> i64 {
+        match self.baseline {
+            Baseline::Normal => 0,
+            Baseline::Subscript => SUBSCRIPT_SHIFT,
+            Baseline::Superscript => SUPERSCRIPT_SHIFT,
+        }
+    }
+
+    /// The attributes of this span's `<tspan>`, apart from its position.
+    pub(crate) fn attributes(&self) -> Vec<(&'static str, &str)> {
+        let mut attributes = Vec::new();
+        if let Some(class) = &self.class {
+            attributes.push(("class", class.as_str()));
+        }
+        if self.monospace {
+            attributes.push(("font-family", "monospace"));
+        }
+        if self.italic {
+            attributes.push(("font-style", "italic"));
+        }
+        if self.bold {
+            attributes.push(("font-weight", "bold"));
+        }
+        if self.baseline != Baseline::Normal {
+            attributes.push(("font-size", "70%"));
+        }
+        attributes
+    }
+
+    /// Describe this span as a child of a text node's description.
+    pub(crate) fn describe(&self) -> Description {
+        let style = [
+            (self.monospace, "monospace"),
+            (self.italic, "italic"),
+            (self.bold, "bold"),
+            (self.baseline == Baseline::Subscript, "subscript"),
+            (self.baseline == Baseline::Superscript, "superscript"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect::<Vec<_>>();
+        let mut desc = Description::new("span").with_property("text", self.text.clone());
+        if !style.is_empty() {
+            desc = desc.with_property("style", style.join(","));
+        }
+        if let Some(class) = &self.class {
+            desc = desc.with_property("class", class.clone());
+        }
+        desc
+    }
+
+    /// The width of `text`, a part of this span, given the width of a
+    /// character in the surrounding text.
+    fn width(&self, text: &str, char_width: i64) -> i64 {
+        let width = i64::try_from(text_width(text)).unwrap() * char_width;
+        match self.baseline {
+            Baseline::Normal => width,
+            Baseline::Subscript | Baseline::Superscript => (width * 7 + 9) / 10,
+        }
+    }
+}
+
+/// A line of text, as the parts of the spans on it.
+pub(crate) type Line<'a> = Vec<(&'a Span, &'a str)>;
+
+/// Text made of differently styled [`Span`]s.
+///
+/// Use it as the label of a [`Terminal`](crate::Terminal) or
+/// [`NonTerminal`](crate::NonTerminal), or as the text of a
+/// [`Comment`](crate::Comment). All text is HTML-escaped when rendered, so
+/// arbitrary strings are safe to pass.
+///
+/// # Example
+/// ```rust
+/// use railroad::*;
+///
+/// let label = RichText::new(vec![
+///     Span::monospace("IDENT"),
+///     Span::new(" "),
+///     Span::italic("(case-insensitive)"),
+/// ]);
+/// let svg = Diagram::new(Terminal::with_rich_label(label)).to_string();
+/// assert!(svg.contains("font-style=\"italic\""));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RichText {
+    spans: Vec<Span>,
+}
+
+impl RichText {
+    /// Construct rich text from the given spans.
+    #[must_use]
+    pub fn new(spans: Vec<Span>) -> Self {
+        Self { spans }
+    }
+
+    /// Add a span to the end of the text.
+    pub fn push(&mut self, span: Span) -> &mut Self {
+        self.spans.push(span);
+        self
+    }
+
+    /// The spans making up the text.
+    #[must_use]
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// The text without any styles.
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Returns `true` if the text is a single unstyled span, as constructed
+    /// from a `String`.
+    pub(crate) fn is_plain(&self) -> bool {
+        matches!(self.spans.as_slice(), [span] if span.is_plain())
+    }
+
+    /// Split the text into the lines it is drawn on, as [`text_lines`] does for
+    /// plain text.
+    pub(crate) fn lines(&self, max_width: Option<usize>) -> Vec<Line<'_>> {
+        if let [span] = self.spans.as_slice() {
+            return text_lines(&span.text, max_width)
+                .into_iter()
+                .map(|line| vec![(span, line)])
+                .collect();
+        }
+        let plain = self.to_plain_text();
+        let mut span_start = 0;
+        let spans = self
+            .spans
+            .iter()
+            .map(|span| {
+                span_start += span.text.len();
+                (span_start - span.text.len(), span)
+            })
+            .collect::<Vec<_>>();
+        text_lines(&plain, max_width)
+            .into_iter()
+            .map(|line| {
+                // `line` is a subslice of `plain`, so this is its byte offset.
+                let line_start = line.as_ptr() as usize - plain.as_ptr() as usize;
+                let line_end = line_start + line.len();
+                spans
+                    .iter()
+                    .filter_map(|&(span_start, span)| {
+                        let start = line_start.max(span_start);
+                        let end = line_end.min(span_start + span.text.len());
+                        (start < end)
+                            .then(|| (span, &span.text[start - span_start..end - span_start]))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The width of `line`, given the width of a character in unstyled text.
+    pub(crate) fn line_width(line: &Line<'_>, char_width: i64) -> i64 {
+        line.iter()
+            .map(|(span, text)| span.width(text, char_width))
+            .sum()
+    }
+}
+
+impl From<String> for RichText {
+    fn from(text: String) -> Self {
+        Self::new(vec![Span::new(text)])
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        Self::new(vec![Span::new(text)])
+    }
+}
+
+impl FromIterator<Span> for RichText {
+    fn from_iter<T: IntoIterator<Item = Span>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
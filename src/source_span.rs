@@ -0,0 +1,135 @@
+//! Where in the text of a grammar a node was defined.
+
+use std::ops::Range;
+
+use crate::import;
+
+/// The location of a node in the text it was imported from.
+///
+/// Terminals, non-terminals and comments carry a span via `set_source_span`;
+/// it is written to the SVG as `data-source-file`, `data-source-line`,
+/// `data-source-column`, `data-source-start` and `data-source-end` attributes,
+/// so editors can map between diagram elements and text. Custom `data-*`
+/// attributes come from SVG 2; a diagram with source spans does not validate
+/// against the SVG 1.1 DTD.
+///
+/// # Example
+/// ```rust
+/// use railroad::*;
+///
+/// let src = "list: item ',' list;";
+/// let mut nt = NonTerminal::new("item".to_owned());
+/// nt.set_source_span(Some(SourceSpan::locate(src, 6..10)));
+/// let svg = Diagram::new(nt).to_string();
+/// assert!(svg.contains(r#"data-source-column="7""#));
+/// assert!(svg.contains(r#"data-source-start="6""#));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SourceSpan {
+    /// The file the text was read from, if known.
+    pub file: Option<String>,
+    /// The 1-based line of the start of the span.
+    pub line: usize,
+    /// The 1-based column, in characters, of the start of the span.
+    pub column: usize,
+    /// The byte-offsets of the span within the text.
+    pub bytes: Range<usize>,
+}
+
+impl SourceSpan {
+    /// The span covering `bytes` in `src`, located by line and column.
+    #[must_use]
+    pub fn locate(src: &str, bytes: Range<usize>) -> Self {
+        let (line, column) = import::position(src, bytes.start);
+        Self {
+            file: None,
+            line,
+            column,
+            bytes,
+        }
+    }
+
+    /// The properties describing this span, their keys prefixed with `prefix`.
+    pub(crate) fn properties(&self, prefix: &str) -> Vec<(String, String)> {
+        let file = self.file.iter().map(|file| ("file", file.clone()));
+        let numbers = [
+            ("line", self.line),
+            ("column", self.column),
+            ("start", self.bytes.start),
+            ("end", self.bytes.end),
+        ]
+        .map(|(key, n)| (key, n.to_string()));
+        file.chain(numbers)
+            .map(|(key, value)| (format!("{prefix}{key}"), value))
+            .collect()
+    }
+
+    /// Read a span back from the properties written by
+    /// [`SourceSpan::properties`], looking them up via `get`.
+    ///
+    /// Returns `None` if there are none at all, or the key of the first
+    /// property that is missing or malformed.
+    pub(crate) fn from_properties<'a>(
+        prefix: &str,
+        get: impl Fn(&str) -> Option<&'a str>,
+    ) -> Result<Option<Self>, String> {
+        let key = |key: &str| format!("{prefix}{key}");
+        if ["file", "line", "column", "start", "end"]
+            .iter()
+            .all(|k| get(&key(k)).is_none())
+        {
+            return Ok(None);
+        }
+        let number = |k: &str| {
+            let key = key(k);
+            get(&key).and_then(|v| v.parse::<usize>().ok()).ok_or(key)
+        };
+        let (start, end) = (number("start")?, number("end")?);
+        if end < start {
+            return Err(key("end"));
+        }
+        Ok(Some(Self {
+            file: get(&key("file")).map(str::to_owned),
+            line: number("line")?,
+            column: number("column")?,
+            bytes: start..end,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SourceSpan;
+
+    #[test]
+    fn properties_round_trip() {
+        let mut span = SourceSpan::locate("a\nbc d", 5..6);
+        assert_eq!((span.line, span.column), (2, 4));
+        for file in [None, Some("x.y".to_owned())] {
+            span.file = file;
+            let props = span.properties("data-source-");
+            let get = |key: &str| {
+                props
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.as_str())
+            };
+            assert_eq!(
+                SourceSpan::from_properties("data-source-", get),
+                Ok(Some(span.clone()))
+            );
+        }
+    }
+
+    #[test]
+    fn partial_properties_are_rejected() {
+        let get = |key: &str| (key == "line").then_some("1");
+        assert_eq!(
+            SourceSpan::from_properties("", get),
+            Err("start".to_owned())
+        );
+        assert_eq!(SourceSpan::from_properties("", |_| None), Ok(None));
+        let get = |key: &str| Some(if key == "end" { "1" } else { "2" });
+        assert_eq!(SourceSpan::from_properties("", get), Err("end".to_owned()));
+    }
+}
@@ -0,0 +1,387 @@
+//! Drawing node trees as TikZ pictures, for LaTeX documents.
+//!
+//! [`Diagram::to_tikz`](crate::Diagram::to_tikz) and [`picture`] write a
+//! `tikzpicture` environment, in which paths and arcs become TikZ paths, boxes
+//! become nodes and labels become escaped LaTeX text. Nothing in the picture is
+//! styled directly; everything is drawn in the styles defined by [`STYLES`],
+//! which have to be part of the document's preamble.
+//!
+//! Each path, box and label is drawn in the style `railroad/path`,
+//! `railroad/box` or `railroad/text`, followed by the style for the kind of
+//! node it belongs to, e.g. `railroad/terminal box` or
+//! `railroad/nonterminal text`, if such a style is defined. The picture as a
+//! whole uses `railroad/picture`. All of them can be changed after the
+//! defaults have been defined:
+//!
+//! ```latex
+//! \usepackage{tikz}
+//! % The contents of `railroad::tikz::STYLES`
+//! \tikzset{
+//!   railroad/terminal box/.append style={fill=yellow!20},
+//!   railroad/picture/.style={scale=.8, transform shape},
+//! }
+//! ```
+//!
+//! Nodes which only know how to draw themselves as SVG are left out.
+//!
+//! # Example
+//! ```rust
+//! use railroad::*;
+//!
+//! let dia = Diagram::new(Sequence::new(vec![
+//!     Box::new(SimpleStart) as Box<dyn Node>,
+//!     Box::new(Terminal::new("$x_1".to_owned())),
+//!     Box::new(SimpleEnd),
+//! ]));
+//! let picture = dia.to_tikz();
+//! assert!(picture.starts_with(r"\begin{tikzpicture}"));
+//! assert!(picture.contains(r"{\$x\_1}"));
+//!
+//! let document = tikz::document(&picture);
+//! assert!(document.contains(tikz::STYLES));
+//! ```
+
+use std::fmt::{self, Write};
+
+use crate::{
+    HDir, Node, NodeGeometry, RenderBackend,
+    layout::Transform,
+    placement::{self, Primitive},
+    rich_text::{self, Baseline, Span},
+    svg,
+};
+
+/// The size of one unit of the diagram's layout, in points.
+///
+/// This is the size of a pixel in the SVG output, at 96 pixels per inch.
+const UNIT: f64 = 0.75;
+
+/// The default styles, colors and fonts, resembling [`crate::Stylesheet::Light`].
+///
+/// This has to be part of the preamble of every document that includes a
+/// picture; the styles can be changed after it using `\tikzset`.
+pub const STYLES: &str = r"\definecolor{railroadcanvas}{RGB}{245,242,240}
+\definecolor{railroadbox}{RGB}{241,247,212}
+\definecolor{railroadlabel}{RGB}{90,90,150}
+\tikzset{
+  railroad/picture/.style={},
+  railroad/canvas/.style={fill=railroadcanvas},
+  railroad/path/.style={draw, line width=2.25pt},
+  railroad/box/.style={draw, line width=2.25pt, fill=railroadbox, inner sep=0pt, outer sep=0pt},
+  railroad/text/.style={anchor=mid, inner sep=0pt, outer sep=0pt, font=\ttfamily\small},
+  railroad/nonterminal text/.style={font=\ttfamily\small\bfseries},
+  railroad/comment text/.style={font=\ttfamily\footnotesize\itshape},
+  railroad/repeat text/.style={font=\ttfamily\footnotesize\itshape},
+  railroad/separatedlist text/.style={font=\ttfamily\footnotesize\itshape},
+  railroad/unordered text/.style={font=\ttfamily\footnotesize\itshape},
+  railroad/labeledbox box/.style={draw=gray, line width=0.75pt, dash pattern=on 3.75pt off 3.75pt, fill=railroadlabel, fill opacity=0.1},
+  railroad/unordered box/.style={draw=gray, line width=0.75pt, dash pattern=on 0.75pt off 2.25pt, fill=none},
+  railroad/debug box/.style={draw=red, line width=0.75pt, fill=none},
+}
+";
+
+/// Draw `node` at the origin as a TikZ picture.
+///
+/// Use [`Diagram::to_tikz`](crate::Diagram::to_tikz) for diagrams, which
+/// also draws the canvas and lays the diagram out in its orientation.
+#[must_use]
+pub fn picture(node: &dyn Node) -> String {
+    let geo = node.compute_geometry();
+    let mut backend = TikzBackend::default();
+    backend
+        .push_child(node, 0, 0, HDir::LTR, &geo)
+        .expect("tikz backend is infallible");
+    backend.into_picture()
+}
+
+/// A complete LaTeX document which shows only the given picture.
+///
+/// The document uses the `standalone` class, which crops the page to the
+/// picture, and defines the [`STYLES`].
+#[must_use]
+pub fn document(picture: &str) -> String {
+    format!(
+        "\\documentclass[tikz]{{standalone}}\n{STYLES}\\begin{{document}}\n{picture}\\end{{document}}\n"
+    )
+}
+
+/// `RenderBackend` implementation that writes TikZ drawing commands.
+///
+/// Every node drawn through [`RenderBackend::push_child`] lends its kind to
+/// the styles of what it draws itself; see the [module documentation](self).
+///
+/// ```rust
+/// use railroad::*;
+///
+/// let node = Terminal::new("x".to_owned());
+/// let mut backend = tikz::TikzBackend::default();
+/// backend.push_child(&node, 0, 0, svg::HDir::LTR, &node.compute_geometry()).unwrap();
+/// assert!(backend.into_picture().contains("railroad/terminal box/.try"));
+/// ```
+#[derive(Debug, Default)]
+pub struct TikzBackend {
+    out: String,
+    /// The kinds of the nodes currently being drawn, innermost last
+    kinds: Vec<String>,
+    /// Maps the layout onto the picture, if it is not drawn as laid out
+    transform: Option<Transform>,
+}
+
+impl TikzBackend {
+    /// Wrap everything drawn so far in a `tikzpicture` environment.
+    #[must_use]
+    pub fn into_picture(self) -> String {
+        format!(
+            "\\begin{{tikzpicture}}[x={UNIT}pt, y=-{UNIT}pt, railroad/picture]\n{}\\end{{tikzpicture}}\n",
+            self.out
+        )
+    }
+
+    /// Fill the background of a diagram of the given size.
+    pub(crate) fn push_canvas(&mut self, width: i64, height: i64) -> fmt::Result {
+        writeln!(
+            self.out,
+            "\\path[railroad/canvas] (0,0) rectangle ({width},{height});"
+        )
+    }
+
+    /// Draw everything from now on through `transform`.
+    pub(crate) fn set_transform(&mut self, transform: Transform) {
+        self.transform = Some(transform);
+    }
+
+    fn transformed(&self, primitive: Primitive) -> Primitive {
+        match self.transform {
+            Some(transform) => primitive.transform(transform),
+            None => primitive,
+        }
+    }
+
+    /// The generic style, followed by the style for the current node's kind.
+    fn style(&self, generic: &str) -> String {
+        match self.kinds.last() {
+            Some(kind) if !kind.is_empty() => {
+                format!("railroad/{generic}, railroad/{kind} {generic}/.try")
+            }
+            _ => format!("railroad/{generic}"),
+        }
+    }
+
+    /// Write `lines` of LaTeX, vertically centered around the baseline at `(x, y)`.
+    fn push_latex_lines(
+        &mut self,
+        x: i64,
+        y: i64,
+        lines: &[String],
+        line_height: i64,
+    ) -> fmt::Result {
+        // Text stays upright, centered where the SVG output centers it.
+        let (x, y) = match self.transform {
+            Some(transform) => transform.apply(x, y - 5),
+            None => (x, y - 5),
+        };
+        let style = self.style("text");
+        let middle = (lines.len() as f64 - 1.0) / 2.0;
+        for (i, line) in lines.iter().enumerate() {
+            let y = y as f64 + (i as f64 - middle) * line_height as f64;
+            writeln!(self.out, "\\node[{style}] at ({x},{y}) {{{line}}};")?;
+        }
+        Ok(())
+    }
+}
+
+impl RenderBackend for TikzBackend {
+    fn push_path(&mut self, path: svg::PathData) -> fmt::Result {
+        let mut commands = String::new();
+        let mut at = None;
+        for primitive in placement::path_primitives(&path.to_string()) {
+            let primitive = self.transformed(primitive);
+            let (Primitive::Line { from, to } | Primitive::Arc { from, to, .. }) = primitive else {
+                unreachable!("paths only consist of lines and arcs");
+            };
+            if at != Some(from) {
+                write!(commands, " ({},{})", from.0, from.1)?;
+            }
+            match primitive {
+                Primitive::Arc {
+                    radius, clockwise, ..
+                } => commands.push_str(&arc(from, to, radius, clockwise)),
+                _ => write!(commands, " -- ({},{})", to.0, to.1)?,
+            }
+            at = Some(to);
+        }
+        if commands.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.out, "\\path[{}]{commands};", self.style("path"))
+    }
+
+    fn push_rect(&mut self, x: i64, y: i64, width: i64, height: i64) -> fmt::Result {
+        self.push_rounded_rect(x, y, width, height, 0)
+    }
+
+    fn push_rounded_rect(
+        &mut self,
+        x: i64,
+        y: i64,
+        width: i64,
+        height: i64,
+        radius: i64,
+    ) -> fmt::Result {
+        let Primitive::Rect {
+            x,
+            y,
+            width,
+            height,
+            radius,
+        } = self.transformed(Primitive::Rect {
+            x,
+            y,
+            width,
+            height,
+            radius,
+        })
+        else {
+            unreachable!("rectangles stay rectangles");
+        };
+        write!(
+            self.out,
+            "\\node[{}, minimum width={}pt, minimum height={}pt",
+            self.style("box"),
+            width as f64 * UNIT,
+            height as f64 * UNIT,
+        )?;
+        if radius > 0 {
+            write!(self.out, ", rounded corners={}pt", radius as f64 * UNIT)?;
+        }
+        writeln!(
+            self.out,
+            "] at ({},{}) {{}};",
+            x as f64 + width as f64 / 2.0,
+            y as f64 + height as f64 / 2.0
+        )
+    }
+
+    fn push_text(&mut self, x: i64, y: i64, text: &str) -> fmt::Result {
+        self.push_latex_lines(x, y, &[escape(text)], 0)
+    }
+
+    fn push_text_lines(
+        &mut self,
+        x: i64,
+        y: i64,
+        lines: &[rich_text::Line<'_>],
+        line_height: i64,
+    ) -> fmt::Result {
+        let lines = lines
+            .iter()
+            .map(|line| line.iter().map(|(span, text)| latex(span, text)).collect())
+            .collect::<Vec<String>>();
+        self.push_latex_lines(x, y, &lines, line_height)
+    }
+
+    fn push_child(
+        &mut self,
+        child: &dyn Node,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        let kind = child.describe().kind;
+        self.kinds
+            .push(kind.chars().filter(char::is_ascii_alphanumeric).collect());
+        let result = child.emit_with_geometry(self, x, y, h_dir, geo);
+        self.kinds.pop();
+        result
+    }
+}
+
+/// A quarter circle from `from` to `to`, which are in the same coordinates as
+/// the SVG output; TikZ measures angles towards the y-axis, i.e. clockwise on
+/// screen.
+fn arc(from: (i64, i64), to: (i64, i64), radius: i64, clockwise: bool) -> String {
+    let angle = |center: (i64, i64), (x, y): (i64, i64)| match (
+        (x - center.0).signum(),
+        (y - center.1).signum(),
+    ) {
+        (1, _) => 0,
+        (_, 1) => 90,
+        (-1, _) => 180,
+        _ => 270,
+    };
+    let delta: i64 = if clockwise { 90 } else { -90 };
+    let center = [(to.0, from.1), (from.0, to.1)]
+        .into_iter()
+        .find(|&center| (angle(center, to) - angle(center, from) - delta).rem_euclid(360) == 0)
+        .unwrap_or((to.0, from.1));
+    format!(
+        " arc[start angle={}, delta angle={delta}, radius={radius}]",
+        angle(center, from)
+    )
+}
+
+/// The text of a span as LaTeX, in the span's style.
+fn latex(span: &Span, text: &str) -> String {
+    let mut latex = escape(text);
+    for (styled, command) in [
+        (span.monospace, "texttt"),
+        (span.italic, "textit"),
+        (span.bold, "textbf"),
+        (span.baseline == Baseline::Subscript, "textsubscript"),
+        (span.baseline == Baseline::Superscript, "textsuperscript"),
+    ] {
+        if styled {
+            latex = format!("\\{command}{{{latex}}}");
+        }
+    }
+    latex
+}
+
+/// Escape the characters LaTeX treats specially in text.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '{' | '}' | '$' | '&' | '#' | '%' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '<' => escaped.push_str(r"\textless{}"),
+            '>' => escaped.push_str(r"\textgreater{}"),
+            '|' => escaped.push_str(r"\textbar{}"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{arc, escape};
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(
+            escape(r"a_b {c} $5 & 10% #1 ~^ <|> \x"),
+            r"a\_b \{c\} \$5 \& 10\% \#1 \textasciitilde{}\textasciicircum{} \textless{}\textbar{}\textgreater{} \textbackslash{}x"
+        );
+    }
+
+    #[test]
+    fn arcs_turn_around_the_right_center() {
+        // Heading east, turning south
+        assert_eq!(
+            arc((15, 20), (25, 30), 10, true),
+            " arc[start angle=270, delta angle=90, radius=10]"
+        );
+        // Heading east, turning north
+        assert_eq!(
+            arc((0, 0), (10, -10), 10, false),
+            " arc[start angle=90, delta angle=-90, radius=10]"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+mod common;
+
+use std::fmt;
+
+use railroad::{
+    Choice, Diagram, ElementBackend, Empty, Node, NodeGeometry, NonTerminal, Optional, Orientation,
+    RenderBackend, Repeat, Span, Terminal,
+    placement::Primitive,
+    svg::{self, HDir},
+};
+
+use crate::common::{basic_sequence, boxed};
+
+/// Records every call as a line of text, drawing children through itself.
+#[derive(Default)]
+struct Recorder {
+    calls: Vec<String>,
+}
+
+impl RenderBackend for Recorder {
+    fn push_path(&mut self, path: svg::PathData) -> fmt::Result {
+        self.calls.push(format!("path{path}"));
+        Ok(())
+    }
+
+    fn push_rect(&mut self, x: i64, y: i64, width: i64, height: i64) -> fmt::Result {
+        self.calls.push(format!("rect {x} {y} {width} {height}"));
+        Ok(())
+    }
+
+    fn push_rounded_rect(
+        &mut self,
+        x: i64,
+        y: i64,
+        width: i64,
+        height: i64,
+        radius: i64,
+    ) -> fmt::Result {
+        self.calls
+            .push(format!("rounded {x} {y} {width} {height} {radius}"));
+        Ok(())
+    }
+
+    fn push_text(&mut self, x: i64, y: i64, text: &str) -> fmt::Result {
+        self.calls.push(format!("text {x} {y} {text}"));
+        Ok(())
+    }
+
+    fn push_text_lines(
+        &mut self,
+        x: i64,
+        y: i64,
+        lines: &[Vec<(&Span, &str)>],
+        line_height: i64,
+    ) -> fmt::Result {
+        let lines = lines
+            .iter()
+            .map(|line| line.iter().map(|(_, text)| *text).collect::<String>())
+            .collect::<Vec<_>>();
+        self.calls
+            .push(format!("text {x} {y} {} {line_height}", lines.join("|")));
+        Ok(())
+    }
+
+    fn push_child(
+        &mut self,
+        child: &dyn Node,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        self.calls
+            .push(format!("child {} {x} {y}", child.describe().kind));
+        child.emit_with_geometry(self, x, y, h_dir, geo)
+    }
+
+    fn push_element(&mut self, element: svg::Element) -> fmt::Result {
+        self.calls.push(format!("element {element}"));
+        Ok(())
+    }
+}
+
+fn record<N: Node>(node: &N) -> Vec<String> {
+    let mut recorder = Recorder::default();
+    node.emit(&mut recorder, 0, 0, HDir::LTR).unwrap();
+    recorder.calls
+}
+
+/// A custom node which only knows how to draw itself as SVG.
+struct SvgOnly;
+
+impl Node for SvgOnly {
+    fn entry_height(&self) -> i64 {
+        5
+    }
+    fn height(&self) -> i64 {
+        10
+    }
+    fn width(&self) -> i64 {
+        10
+    }
+    fn draw(&self, x: i64, y: i64, _: HDir) -> svg::Element {
+        svg::Element::new("circle").set("cx", &x).set("cy", &y)
+    }
+}
+
+/// A custom node which draws itself through any backend.
+struct Cross;
+
+impl Node for Cross {
+    fn entry_height(&self) -> i64 {
+        5
+    }
+    fn height(&self) -> i64 {
+        10
+    }
+    fn width(&self) -> i64 {
+        10
+    }
+    fn draw(&self, x: i64, y: i64, h_dir: HDir) -> svg::Element {
+        let mut backend = ElementBackend::default();
+        self.emit(&mut backend, x, y, h_dir).unwrap();
+        backend.into_group().set("class", "cross")
+    }
+    fn emit_with_geometry(
+        &self,
+        backend: &mut dyn RenderBackend,
+        x: i64,
+        y: i64,
+        h_dir: HDir,
+        geo: &NodeGeometry,
+    ) -> fmt::Result {
+        backend.push_path(
+            svg::PathData::new(h_dir)
+                .move_to(x, y)
+                .line_rel(geo.width, geo.height)
+                .move_rel(0, -geo.height)
+                .line_rel(-geo.width, geo.height),
+        )
+    }
+}
+
+#[test]
+fn built_in_nodes_draw_through_the_backend() {
+    assert_eq!(
+        record(&Diagram::new(basic_sequence())),
+        [
+            "child sequence 10 10",
+            "child start 10 11",
+            "path M 10 11 v 20 m 10 -20 v 20 m -10 -10 h 20",
+            "child terminal 40 10",
+            "rounded 40 10 60 22 10",
+            "text 70 26 BEGIN 16",
+            "child end 110 11",
+            "path M 110 21 h 20 m -10 -10 v 20 m 10 -20 v 20",
+            "path M 30 21 h 10",
+            "path M 100 21 h 10",
+        ]
+    );
+}
+
+#[test]
+fn every_box_and_label_reaches_the_backend() {
+    let node = Choice::new(vec![
+        boxed(Terminal::new("a".to_owned())),
+        boxed(Repeat::new(NonTerminal::new("b".to_owned()), Empty)),
+    ]);
+    let calls = record(&node);
+    let kinds = calls
+        .iter()
+        .filter_map(|call| call.strip_prefix("child "))
+        .map(|child| child.split(' ').next().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, ["terminal", "repeat", "empty", "nonterminal"]);
+    assert_eq!(calls.iter().filter(|c| c.starts_with("rounded")).count(), 1);
+    assert_eq!(calls.iter().filter(|c| c.starts_with("rect")).count(), 1);
+    assert!(
+        calls
+            .iter()
+            .any(|c| c.starts_with("text") && c.contains(" a "))
+    );
+    assert!(
+        calls
+            .iter()
+            .any(|c| c.starts_with("text") && c.contains(" b "))
+    );
+    assert!(!calls.iter().any(|c| c.starts_with("element")));
+}
+
+#[test]
+fn svg_only_nodes_are_handed_over_as_elements() {
+    let calls = record(&Repeat::new(SvgOnly, Empty));
+    let element = calls
+        .iter()
+        .position(|c| c.starts_with("element <circle"))
+        .unwrap();
+    assert_eq!(calls[element - 1], "child unknown 12 0");
+}
+
+#[test]
+fn custom_nodes_draw_through_any_backend() {
+    let calls = record(&Repeat::new(Cross, Empty));
+    assert!(
+        calls
+            .iter()
+            .any(|c| c.starts_with("path M 12 ") && c.contains(" l 10 10"))
+    );
+
+    // The same drawing code produces the SVG output.
+    let svg = Diagram::new(Cross).to_string();
+    assert!(svg.contains(r#"<g class="cross">"#));
+    assert!(svg.contains(r#"d=" M 10 10 l 10 10 m 0 -10 l -10 10""#));
+
+    // And the placement records it.
+    let layout = Diagram::new(Cross).layout();
+    assert_eq!(layout.children[0].primitives.len(), 2);
+}
+
+#[test]
+fn diagrams_apply_their_orientation_for_any_backend() {
+    let mut dia = Diagram::new(basic_sequence());
+    dia.set_orientation(Orientation::Vertical);
+    assert_eq!(
+        record(&dia),
+        [
+            "child sequence 10 10",
+            "child start 30 10",
+            "path M 30 10 l 20 0 M 30 20 l 20 0 M 40 10 l 0 20",
+            "child terminal 10 40",
+            "rounded 10 40 60 22 10",
+            "text 40 56 BEGIN 16",
+            "child end 30 72",
+            "path M 40 72 l 0 20 M 30 82 l 20 0 M 30 92 l 20 0",
+            "path M 40 30 l 0 10",
+            "path M 40 62 l 0 10",
+        ]
+    );
+
+    // Transposing the layout mirrors it, so arcs turn the other way; the
+    // placement agrees.
+    let mut dia = Diagram::new(Optional::new(Terminal::new("x".to_owned())));
+    dia.set_orientation(Orientation::Vertical);
+    let calls = record(&dia);
+    assert!(calls[1].starts_with("path M 36 10 l 0 24 M 36 10 a 12 12 0 0 1 -12 12 l -2 0"));
+    assert_eq!(
+        &calls[2..],
+        [
+            "child terminal 22 34",
+            "rounded 22 34 28 22 10",
+            "text 36 50 x 16"
+        ]
+    );
+    let optional = &dia.layout().children[0];
+    assert_eq!(
+        optional.primitives[1],
+        Primitive::Arc {
+            from: (36, 10),
+            to: (24, 22),
+            radius: 12,
+            clockwise: true
+        }
+    );
+    assert_eq!((optional.children[0].x, optional.children[0].y), (22, 34));
+}
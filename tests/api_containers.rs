@@ -1,6 +1,9 @@
 mod common;
 
-use railroad::{Choice, Debug, Diagram, Empty, MultiChoice, Node, Stack, Terminal};
+use railroad::{
+    Alignment, AlternatingSequence, Choice, Debug, Diagram, Empty, HorizontalChoice, MultiChoice,
+    Node, OptionalSequence, Sequence, Stack, Terminal, Unordered,
+};
 
 use crate::common::boxed;
 
@@ -136,3 +139,376 @@ fn multichoice_empty_and_single_column_match_choice_geometry() {
     assert_eq!(multichoice.height(), choice.height());
     assert_eq!(multichoice.width(), choice.width());
 }
+
+fn narrow_and_wide() -> Vec<Box<dyn Node>> {
+    vec![
+        boxed(Terminal::new("a".to_owned())),
+        boxed(Terminal::new("bbbbbbbb".to_owned())),
+        boxed(Terminal::new("cc".to_owned())),
+    ]
+}
+
+#[test]
+fn choice_alignment_places_narrow_alternatives() {
+    let render = |alignment| {
+        let mut choice = Choice::new(narrow_and_wide());
+        choice.set_alignment(alignment);
+        let dia = Diagram::new(choice);
+        (dia.width(), dia.to_string())
+    };
+    let (default_width, default_svg) = (
+        Diagram::new(Choice::new(narrow_and_wide())).width(),
+        Diagram::new(Choice::new(narrow_and_wide())).to_string(),
+    );
+    assert_eq!(render(Alignment::Left), (default_width, default_svg));
+
+    // The widest alternative is 84 wide, "cc" is 36 wide.
+    for (alignment, x) in [
+        (Alignment::Left, 34),
+        (Alignment::Center, 58),
+        (Alignment::Right, 82),
+        (Alignment::Justified, 58),
+    ] {
+        let (width, svg) = render(alignment);
+        assert_eq!(width, default_width);
+        assert!(
+            svg.contains(&format!(
+                "<rect x=\"{x}\" y=\"76\" height=\"22\" width=\"36\""
+            )),
+            "{alignment:?}"
+        );
+    }
+}
+
+#[test]
+fn stack_alignment_places_narrow_rows() {
+    let render = |alignment| {
+        let mut stack = Stack::new(narrow_and_wide());
+        stack.set_alignment(alignment);
+        Diagram::new(stack).to_string()
+    };
+    assert_eq!(
+        render(Alignment::Left),
+        Diagram::new(Stack::new(narrow_and_wide())).to_string()
+    );
+
+    let right = render(Alignment::Right);
+    assert!(right.contains("<rect x=\"90\" y=\"10\" height=\"22\" width=\"28\""));
+    assert!(right.contains("<rect x=\"82\" y=\"106\" height=\"22\" width=\"36\""));
+
+    // Justified rows are centered, and turn down at the common right edge.
+    let justified = render(Alignment::Justified);
+    assert!(justified.contains("<rect x=\"62\" y=\"10\" height=\"22\" width=\"28\""));
+    assert!(justified.contains("<path d=\" M 90 21 h 28 a 12 12 0 0 1 12 12"));
+    assert!(!render(Alignment::Center).contains("<path d=\" M 90 21 h 28"));
+}
+
+#[test]
+fn multichoice_alignment_is_set_per_column() {
+    let columns = || {
+        vec![
+            vec![
+                boxed(Terminal::new("x".to_owned())),
+                boxed(Terminal::new("yyyyyy".to_owned())),
+            ],
+            vec![
+                boxed(Terminal::new("zzzzzz".to_owned())),
+                boxed(Terminal::new("w".to_owned())),
+            ],
+        ]
+    };
+    let mut aligned = MultiChoice::new(columns());
+    aligned.set_column_alignment(1, Alignment::Right);
+    let aligned = Diagram::new(aligned);
+    let plain = Diagram::new(MultiChoice::new(columns()));
+    assert_eq!(aligned.width(), plain.width());
+
+    let rects = |svg: &str| {
+        svg.lines()
+            .filter(|l| l.starts_with("<rect x="))
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    };
+    let (aligned, plain) = (rects(&aligned.to_string()), rects(&plain.to_string()));
+    // The first column is untouched, "w" moves right by the difference in width.
+    assert_eq!(aligned[..3], plain[..3]);
+    assert_ne!(aligned[3], plain[3]);
+    assert!(aligned[3].contains("width=\"28\""));
+    let x = |rect: &str| -> i64 { rect[9..].split('"').next().unwrap().parse().unwrap() };
+    assert_eq!(x(&aligned[3]) - x(&plain[3]), 68 - 28);
+}
+
+fn three_letters() -> Vec<Box<dyn Node>> {
+    ["a", "b", "c"]
+        .into_iter()
+        .map(|l| boxed(Terminal::new(l.to_owned())))
+        .collect()
+}
+
+#[test]
+fn choice_default_branch_sits_on_the_main_line() {
+    let mut choice = Choice::new(three_letters());
+    choice.set_default_branch(1);
+    let geo = choice.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (choice.entry_height(), choice.height(), choice.width())
+    );
+    // One alternative above, one below; the overall size does not change.
+    let plain = Choice::new(three_letters());
+    assert_eq!(choice.entry_height(), 44);
+    assert_eq!(choice.height(), plain.height());
+    assert_eq!(choice.width(), plain.width());
+
+    let svg = Diagram::new(choice).to_string();
+    let rects: Vec<_> = svg.lines().filter(|l| l.starts_with("<rect x=")).collect();
+    assert!(rects[0].starts_with("<rect x=\"34\" y=\"43\""), "{rects:?}");
+    assert!(svg.contains("<path d=\" M 10 54 h 24 m 28 0 h 24"));
+    assert!(svg.contains("b</text>"));
+}
+
+#[test]
+fn choice_default_branch_keeps_sequences_aligned() {
+    let mut choice = Choice::new(three_letters());
+    choice.set_default_branch(2);
+    let seq = railroad::Sequence::new(vec![
+        boxed(Terminal::new("before".to_owned())),
+        boxed(choice),
+    ]);
+    let geo = seq.compute_geometry();
+    assert_eq!(geo.entry_height, geo.children[1].entry_height);
+    assert_eq!(geo.children[1].entry_height, seq.entry_height());
+    assert_eq!(geo.height, geo.children[1].height);
+}
+
+#[test]
+fn choice_default_branch_out_of_bounds_uses_the_last_alternative() {
+    let mut last = Choice::new(three_letters());
+    last.set_default_branch(2);
+    let mut beyond = Choice::new(three_letters());
+    beyond.set_default_branch(7);
+    assert_eq!(
+        Diagram::new(beyond).to_string(),
+        Diagram::new(last).to_string()
+    );
+
+    let mut empty: Choice<Box<dyn Node>> = Choice::new(vec![]);
+    empty.set_default_branch(3);
+    assert_eq!(
+        (empty.entry_height(), empty.height(), empty.width()),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn horizontal_choice_lays_alternatives_side_by_side() {
+    let choice = HorizontalChoice::new(three_letters());
+    let geo = choice.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (choice.entry_height(), choice.height(), choice.width())
+    );
+    // Three boxes of 28, two gaps and both ends take 24 each.
+    assert_eq!(choice.width(), 3 * 28 + 4 * 24);
+    // Small boxes leave room for the rails' arcs.
+    assert_eq!(choice.entry_height(), 24);
+    assert_eq!(choice.height(), 2 * 24);
+    assert!(choice.height() < Choice::new(three_letters()).height());
+
+    let svg = Diagram::new(choice).to_string();
+    assert!(svg.contains("class=\"horizontalchoice\""));
+    let rects: Vec<_> = svg.lines().filter(|l| l.starts_with("<rect x=")).collect();
+    assert_eq!(rects.len(), 3);
+    for (rect, x) in rects.iter().zip([34, 86, 138]) {
+        assert!(
+            rect.starts_with(&format!("<rect x=\"{x}\" y=\"23\"")),
+            "{rect}"
+        );
+    }
+}
+
+#[test]
+fn horizontal_choice_handles_empty_and_uneven_alternatives() {
+    let choice = HorizontalChoice::new(vec![
+        boxed(Empty),
+        boxed(Debug::new(40, 50, 10)),
+        boxed(Debug::new(5, 60, 10)),
+    ]);
+    // The tall last alternative is not covered by the upper rail, but still
+    // counts towards the height above and below the main path.
+    assert_eq!(choice.entry_height(), 50);
+    assert_eq!(choice.height(), 50 + 55 + 10);
+
+    let single = HorizontalChoice::new(vec![Terminal::new("a".to_owned())]);
+    let terminal = Terminal::new("a".to_owned());
+    assert_eq!(
+        (single.entry_height(), single.height(), single.width()),
+        (terminal.entry_height(), terminal.height(), terminal.width())
+    );
+    let empty: HorizontalChoice<Empty> = HorizontalChoice::new(vec![]);
+    assert_eq!(
+        (empty.entry_height(), empty.height(), empty.width()),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn horizontal_choice_nests_in_stack_and_repeat() {
+    let stack = Stack::new(vec![
+        boxed(HorizontalChoice::new(three_letters())),
+        boxed(railroad::Repeat::new(
+            HorizontalChoice::new(three_letters()),
+            Terminal::new(",".to_owned()),
+        )),
+    ]);
+    let geo = stack.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (stack.entry_height(), stack.height(), stack.width())
+    );
+    let svg = Diagram::new(stack).to_string();
+    assert_eq!(svg.matches("class=\"horizontalchoice\"").count(), 2);
+}
+
+#[test]
+fn optional_sequence_keeps_elements_on_the_main_path() {
+    let seq = OptionalSequence::new(three_letters());
+    let geo = seq.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (seq.entry_height(), seq.height(), seq.width())
+    );
+    // Both ends take 24, the crossing connectors between elements 36 each.
+    assert_eq!(seq.width(), 3 * 28 + 2 * 24 + 2 * 36);
+    assert_eq!((seq.entry_height(), seq.height()), (24, 48));
+
+    let svg = Diagram::new(seq).to_string();
+    assert!(svg.contains("class=\"optionalsequence\""));
+    let rects: Vec<_> = svg.lines().filter(|l| l.starts_with("<rect x=")).collect();
+    assert_eq!(rects.len(), 3);
+    for (rect, x) in rects.iter().zip([34, 98, 162]) {
+        assert!(
+            rect.starts_with(&format!("<rect x=\"{x}\" y=\"23\"")),
+            "{rect}"
+        );
+    }
+}
+
+#[test]
+fn optional_sequence_rails_clear_the_elements_they_pass() {
+    let seq = OptionalSequence::new(vec![
+        boxed(Debug::new(5, 45, 5)),
+        boxed(Debug::new(30, 35, 5)),
+        boxed(Debug::new(35, 40, 5)),
+    ]);
+    // The upper rail passes all but the first element, the lower rail all
+    // but the last.
+    assert_eq!(seq.entry_height(), 35 + 10);
+    assert_eq!(seq.height(), 45 + 40 + 10);
+
+    let single = OptionalSequence::new(vec![Terminal::new("a".to_owned())]);
+    assert_eq!(
+        (single.entry_height(), single.height(), single.width()),
+        (11, 22, 28)
+    );
+    let empty: OptionalSequence<Empty> = OptionalSequence::new(vec![]);
+    assert_eq!(
+        (empty.entry_height(), empty.height(), empty.width()),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn alternating_sequence_places_elements_above_and_below() {
+    let seq = AlternatingSequence::new(
+        Terminal::new("a".to_owned()),
+        Terminal::new("bcd".to_owned()),
+    );
+    let geo = seq.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (seq.entry_height(), seq.height(), seq.width())
+    );
+    assert_eq!(seq.width(), 48 + 44 + 48);
+    // First entry, both return rails 24 apart around the main path, second entry.
+    assert_eq!(seq.entry_height(), 11 + 24 + 12);
+    assert_eq!(seq.height(), 11 + 24 + 24 + 24 + 11);
+
+    let svg = Diagram::new(seq).to_string();
+    assert!(svg.contains("class=\"alternatingsequence\""));
+    let rects: Vec<_> = svg.lines().filter(|l| l.starts_with("<rect x=")).collect();
+    assert_eq!(rects.len(), 2);
+    assert!(rects[0].starts_with("<rect x=\"58\" y=\"10\" height=\"22\" width=\"28\""));
+    assert!(rects[1].starts_with("<rect x=\"58\" y=\"82\" height=\"22\" width=\"44\""));
+}
+
+#[test]
+fn alternating_sequence_nests_in_stack() {
+    let stack = Stack::new(vec![
+        boxed(AlternatingSequence::new(Debug::new(10, 40, 20), Empty)),
+        boxed(OptionalSequence::new(three_letters())),
+    ]);
+    let geo = stack.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (stack.entry_height(), stack.height(), stack.width())
+    );
+}
+
+#[test]
+fn unordered_frames_its_elements_with_a_marker() {
+    let unordered = Unordered::new(three_letters());
+    let geo = unordered.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (
+            unordered.entry_height(),
+            unordered.height(),
+            unordered.width()
+        )
+    );
+    // The elements are side by side like in a `Sequence`, below the marker.
+    let sequence = Sequence::new(three_letters());
+    assert_eq!(
+        unordered.entry_height(),
+        8 + 20 + 8 + sequence.entry_height()
+    );
+    assert_eq!(
+        unordered.height() - unordered.entry_height(),
+        sequence.height() - sequence.entry_height() + 8
+    );
+    assert_eq!(unordered.width(), 8 + 12 + sequence.width() + 12 + 8);
+
+    let svg = Diagram::new(unordered).to_string();
+    assert!(svg.contains("class=\"unordered\""));
+    assert!(svg.contains(">\nany order</text>"));
+    assert_eq!(svg.matches("<rect").count(), 1 + 3 + 1, "{svg}");
+}
+
+#[test]
+fn unordered_optional_elements_get_a_bypass() {
+    // Wide enough for the marker to fit above it.
+    let mut unordered = Unordered::new(vec![Terminal::new("--verbose".to_owned())]);
+    let required = (unordered.entry_height(), unordered.width());
+    let b = Terminal::new("b".to_owned());
+    let b_width = b.width();
+    unordered.push_optional(b);
+    assert!(unordered.is_required(0));
+    assert!(!unordered.is_required(1));
+    // The optional element is wider by the bypass, which rises above it.
+    assert_eq!(unordered.width(), required.1 + 10 + b_width + 48);
+    assert_eq!(unordered.entry_height(), required.0 + 13);
+    let svg = Diagram::new(&unordered).to_string();
+    assert!(!svg.contains("optional"));
+
+    unordered.set_marker("in any order".to_owned());
+    assert_eq!(unordered.marker(), "in any order");
+    let empty: Unordered<Empty> = Unordered::new(vec![]);
+    let geo = empty.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (empty.entry_height(), empty.height(), empty.width())
+    );
+    // Only the frame and the marker remain.
+    assert_eq!(empty.height(), empty.entry_height() + 8);
+}
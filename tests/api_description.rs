@@ -0,0 +1,168 @@
+mod common;
+
+use railroad::{
+    Alignment, AlternatingSequence, Choice, Comment, Diagram, Empty, End, HorizontalChoice,
+    HorizontalGrid, LabeledBox, Link, LinkTarget, MultiChoice, Node, NonTerminal, Optional,
+    OptionalSequence, Repeat, RichText, SeparatedList, Sequence, SimpleEnd, SimpleStart, Span,
+    Stack, Start, Terminal, Tooltip, Unordered, VerticalGrid, description::Description, import,
+    svg,
+};
+
+use crate::common::boxed;
+
+fn every_primitive() -> Sequence<Box<dyn Node>> {
+    let mut link = Link::new(
+        NonTerminal::new("target & <co>".to_owned()),
+        "https://example.com/?a=1&b=\"2\"".to_owned(),
+    );
+    link.set_target(Some(LinkTarget::Parent));
+    let mut choice = Choice::new(vec![
+        boxed(Empty),
+        boxed(Terminal::new("</metadata>".to_owned())),
+        boxed(Comment::new("a \"comment\"".to_owned())),
+    ]);
+    choice.set_alignment(Alignment::Center);
+    choice.set_default_branch(1);
+    let mut stack = Stack::new(vec![boxed(Terminal::new("a".to_owned())), boxed(Empty)]);
+    stack.set_alignment(Alignment::Justified);
+    let mut multichoice = MultiChoice::new(vec![
+        vec![boxed(Terminal::new("x".to_owned()))],
+        vec![boxed(Terminal::new("y".to_owned())), boxed(Empty)],
+    ]);
+    multichoice.set_column_alignment(1, Alignment::Right);
+    let mut repeat = Repeat::zero_or_more(Terminal::new("r".to_owned()), Empty);
+    repeat.set_bounds(0, Some(3));
+    let mut unordered = Unordered::new(vec![boxed(Terminal::new("u".to_owned()))]);
+    unordered.push_optional(boxed(Empty));
+    unordered.set_marker("in any <order>".to_owned());
+    let mut wrapped = Comment::new("a wrapped\ncomment".to_owned());
+    wrapped.set_max_line_width(Some(4));
+    let mut keyword = Span::bold("<kw>");
+    keyword.class = Some("a \"class\"".to_owned());
+    let rich = Terminal::with_rich_label(RichText::new(vec![
+        keyword,
+        Span::subscript("1"),
+        Span::new("\nand "),
+        Span {
+            italic: true,
+            monospace: true,
+            ..Span::superscript("more")
+        },
+    ]));
+    let mut tooltip = Tooltip::new(Terminal::new("t".to_owned()), "a <title>".to_owned());
+    tooltip.set_description(Some("a \"description\"".to_owned()));
+    let mut list = SeparatedList::new(NonTerminal::new("l".to_owned()), Empty);
+    list.set_bounds(0, Some(2));
+    list.set_trailing_separator(true);
+    Sequence::new(vec![
+        boxed(Start),
+        boxed(SimpleStart),
+        boxed(choice),
+        boxed(Optional::new(Repeat::new(
+            link,
+            Terminal::new(",".to_owned()),
+        ))),
+        boxed(repeat),
+        boxed(HorizontalChoice::new(vec![
+            boxed(Empty),
+            boxed(Terminal::new("h".to_owned())),
+        ])),
+        boxed(OptionalSequence::new(vec![
+            boxed(Terminal::new("o".to_owned())),
+            boxed(AlternatingSequence::new(
+                Terminal::new("p".to_owned()),
+                Empty,
+            )),
+        ])),
+        boxed(unordered),
+        boxed(list),
+        boxed(wrapped),
+        boxed(rich),
+        boxed(tooltip),
+        boxed(Tooltip::new(Empty, String::new())),
+        boxed(NonTerminal::new("two\nlines".to_owned())),
+        boxed(LabeledBox::new(stack, Comment::new("label".to_owned()))),
+        boxed(multichoice),
+        boxed(VerticalGrid::new(vec![boxed(HorizontalGrid::new(vec![
+            boxed(Terminal::new("g".to_owned())),
+        ]))])),
+        boxed(SimpleEnd),
+        boxed(End),
+    ])
+}
+
+#[test]
+fn embedded_description_roundtrips_through_svg() {
+    let mut original = Diagram::new(every_primitive());
+    original.set_embed_description(true);
+    let svg = original.to_string();
+    assert!(svg.contains("<metadata>"));
+
+    let description = import::svg::import(&svg).unwrap();
+    assert_eq!(description, every_primitive().describe());
+
+    let mut restored = Diagram::new(description.into_node().unwrap());
+    restored.set_embed_description(true);
+    assert_eq!(restored.to_string(), svg);
+}
+
+#[test]
+fn description_is_only_embedded_on_request() {
+    let svg = Diagram::new(every_primitive()).to_string();
+    assert!(!svg.contains("<metadata"));
+    assert!(import::svg::import(&svg).is_err());
+}
+
+#[test]
+fn element_tree_embeds_the_same_description() {
+    let mut dia = Diagram::new(every_primitive());
+    dia.set_embed_description(true);
+    let element = dia.draw(0, 0, svg::HDir::LTR);
+    assert_eq!(
+        import::svg::import(&element.to_string()).unwrap(),
+        every_primitive().describe()
+    );
+}
+
+#[test]
+fn custom_nodes_describe_as_unknown() {
+    struct Custom;
+
+    impl Node for Custom {
+        fn entry_height(&self) -> i64 {
+            0
+        }
+        fn height(&self) -> i64 {
+            0
+        }
+        fn width(&self) -> i64 {
+            0
+        }
+        fn draw(&self, _: i64, _: i64, _: svg::HDir) -> svg::Element {
+            svg::Element::new("g")
+        }
+    }
+
+    let desc = Optional::new(Custom).describe();
+    assert_eq!(
+        desc,
+        Description::new("optional").with_child(Description::new("unknown"))
+    );
+    let err = desc.into_node().unwrap_err();
+    assert_eq!(err.kind, "unknown");
+}
+
+#[test]
+fn alignment_is_described_unless_default() {
+    let mut choice = Choice::new(vec![Terminal::new("a".to_owned())]);
+    assert_eq!(choice.describe().property("alignment"), None);
+    choice.set_alignment(Alignment::Right);
+    assert_eq!(choice.describe().property("alignment"), Some("right"));
+
+    let mut desc = choice.describe();
+    desc.properties[0].1 = "diagonal".to_owned();
+    assert_eq!(
+        desc.into_node().unwrap_err().message,
+        "unknown alignment `diagonal`"
+    );
+}
@@ -1,6 +1,13 @@
 mod common;
 
-use railroad::{DEFAULT_CSS, Diagram, Terminal, svg};
+use std::fmt;
+
+use railroad::{
+    Choice, Comment, DEFAULT_CSS, Diagram, ElementBackend, Empty, End, LabeledBox, MultiChoice,
+    Node, NodeGeometry, NonTerminal, Optional, OptionalSequence, RenderBackend, Repeat,
+    SeparatedList, Sequence, SimpleEnd, SimpleStart, Start, Terminal, description::Description,
+    svg,
+};
 
 use crate::common::{basic_sequence, render_svg};
 
@@ -47,3 +54,289 @@ fn diagram_into_inner_returns_root_node() {
 
     assert_eq!(svg, expected);
 }
+
+#[test]
+fn diagram_is_unlabelled_by_default() {
+    let svg = Diagram::new(Terminal::new("plain".to_owned())).to_string();
+
+    assert!(!svg.contains("role="));
+    assert!(!svg.contains("aria-labelledby"));
+    assert!(!svg.contains("<title"));
+    assert!(!svg.contains("<desc"));
+}
+
+#[test]
+fn diagram_title_and_description_label_the_svg_in_both_backends() {
+    let mut diagram = Diagram::with_default_css(Terminal::new("SELECT".to_owned()));
+    diagram
+        .set_title(Some("The <SELECT> keyword".to_owned()))
+        .set_description(Some("Starts a \"query\".".to_owned()));
+    assert_eq!(diagram.title(), Some("The <SELECT> keyword"));
+    assert_eq!(diagram.description(), Some("Starts a \"query\"."));
+
+    let streamed = diagram.to_string();
+    let element = diagram.draw(0, 0, svg::HDir::LTR).to_string();
+    for svg in [&streamed, &element] {
+        assert!(svg.contains(" role=\"img\""), "{svg}");
+        assert!(
+            svg.contains(" aria-labelledby=\"railroad-title railroad-desc\""),
+            "{svg}"
+        );
+        // The title is the first child of the `<svg>`-tag, before the stylesheet.
+        assert!(
+            svg.contains(
+                ">\n<title id=\"railroad-title\">\nThe &lt;SELECT&gt; keyword</title>\n\
+                 <desc id=\"railroad-desc\">\nStarts a &quot;query&quot;.</desc>\n<style"
+            ),
+            "{svg}"
+        );
+    }
+}
+
+#[test]
+fn diagram_accessible_ids_follow_the_svg_id() {
+    let mut diagram = Diagram::new(Terminal::new("x".to_owned()));
+    diagram.attr("id".to_owned()).or_insert("select".to_owned());
+    diagram.set_description(Some("x".to_owned()));
+
+    let svg = diagram.to_string();
+
+    assert!(svg.contains("aria-labelledby=\"select-desc\""));
+    assert!(svg.contains("<desc id=\"select-desc\">"));
+    assert!(!svg.contains("<title"));
+}
+
+#[test]
+fn diagram_text_alternative_respects_precedence() {
+    let diagram = Diagram::new(Sequence::new(vec![
+        Box::new(Start) as Box<dyn Node>,
+        Box::new(Choice::new(vec![
+            Box::new(Terminal::new("ALL".to_owned())) as Box<dyn Node>,
+            Box::new(Sequence::new(vec![
+                Box::new(Terminal::new("DISTINCT".to_owned())) as Box<dyn Node>,
+                Box::new(Terminal::new("ON".to_owned())),
+            ])),
+            Box::new(Empty),
+        ])),
+        Box::new(Repeat::new(
+            Choice::new(vec![
+                Box::new(NonTerminal::new("column".to_owned())) as Box<dyn Node>,
+                Box::new(Terminal::new("*".to_owned())),
+            ]),
+            Terminal::new(",".to_owned()),
+        )),
+        Box::new(Optional::new(Repeat::new(
+            NonTerminal::new("join".to_owned()),
+            Empty,
+        ))),
+        Box::new(End),
+    ]));
+
+    assert_eq!(
+        diagram.text_alternative(),
+        "(\"ALL\" | \"DISTINCT\" \"ON\")? (column | \"*\") (\",\" (column | \"*\"))* (join+)?"
+    );
+}
+
+#[test]
+fn diagram_text_alternative_expresses_every_primitive() {
+    let mut bounded = Repeat::new(NonTerminal::new("digit".to_owned()), Empty);
+    bounded.set_bounds(2, Some(4));
+    let diagram = Diagram::new(Sequence::new(vec![
+        Box::new(OptionalSequence::new(vec![
+            Box::new(Terminal::new("a".to_owned())) as Box<dyn Node>,
+            Box::new(Terminal::new("b".to_owned())),
+        ])) as Box<dyn Node>,
+        Box::new(MultiChoice::new(vec![
+            vec![Box::new(Terminal::new("c".to_owned())) as Box<dyn Node>],
+            vec![Box::new(Terminal::new("d\"".to_owned())) as Box<dyn Node>],
+        ])),
+        Box::new(LabeledBox::new(
+            NonTerminal::new("e".to_owned()),
+            Comment::new("a */ label".to_owned()),
+        )),
+        Box::new(bounded),
+    ]));
+
+    assert_eq!(
+        diagram.text_alternative(),
+        "(\"a\" \"b\"? | \"b\") (\"c\" | 'd\"') e /* a * / label */ digit+ /* {2,4} */"
+    );
+}
+
+fn select_statement() -> Sequence<Box<dyn Node>> {
+    let mut columns = SeparatedList::new(
+        NonTerminal::new("column".to_owned()),
+        Terminal::new(",".to_owned()),
+    );
+    columns.set_trailing_separator(true);
+    Sequence::new(vec![
+        Box::new(SimpleStart) as Box<dyn Node>,
+        Box::new(Terminal::new("SELECT".to_owned())),
+        Box::new(Choice::new(vec![
+            Box::new(Terminal::new("*".to_owned())) as Box<dyn Node>,
+            Box::new(columns),
+        ])),
+        Box::new(MultiChoice::new(vec![
+            vec![Box::new(Empty) as Box<dyn Node>],
+            vec![Box::new(Terminal::new("LIMIT".to_owned())) as Box<dyn Node>],
+        ])),
+        Box::new(SimpleEnd),
+    ])
+}
+
+#[test]
+fn diagram_has_no_element_ids_by_default() {
+    let diagram = Diagram::new(select_statement());
+
+    assert!(!diagram.to_string().contains(" id="));
+    assert_eq!(diagram.element_ids(), Ok(Vec::new()));
+}
+
+#[test]
+fn diagram_element_ids_follow_the_path_in_both_backends() {
+    let mut diagram = Diagram::new(select_statement());
+    diagram.set_element_ids(Some("select".to_owned())).unwrap();
+    let expected = [
+        "select",
+        "select.sequence.0",
+        "select.sequence.1",
+        "select.sequence.2",
+        "select.sequence.2.choice.0",
+        "select.sequence.2.choice.1",
+        "select.sequence.2.choice.1.separatedlist.0",
+        "select.sequence.2.choice.1.separatedlist.1",
+        "select.sequence.3",
+        "select.sequence.3.multichoice.0.0",
+        "select.sequence.3.multichoice.1.0",
+        "select.sequence.4",
+    ];
+
+    let mut ids = diagram.element_ids().unwrap();
+    ids.sort();
+    assert_eq!(ids, expected);
+    let streamed = diagram.to_string();
+    for id in expected {
+        // Every id is written exactly once, even for the separator, which is
+        // drawn twice.
+        assert_eq!(
+            streamed.matches(&format!(" id=\"{id}\"")).count(),
+            1,
+            "{id}: {streamed}"
+        );
+    }
+    let keyword = streamed.find(" id=\"select.sequence.1\"").unwrap();
+    let tag = &streamed[streamed[..keyword].rfind('<').unwrap()..keyword];
+    assert!(tag.starts_with("<g class=\"terminal\""), "{tag}");
+}
+
+#[test]
+fn diagram_element_ids_keep_explicit_ids_and_detect_collisions() {
+    let mut keyword = Terminal::new("SELECT".to_owned());
+    keyword
+        .attr("id".to_owned())
+        .or_insert("keyword".to_owned());
+    let mut diagram = Diagram::new(Sequence::new(vec![
+        Box::new(keyword) as Box<dyn Node>,
+        Box::new(NonTerminal::new("columns".to_owned())),
+    ]));
+    diagram.set_element_ids(Some("rule".to_owned())).unwrap();
+
+    let svg = diagram.to_string();
+    assert!(svg.contains("id=\"keyword\""));
+    assert!(!svg.contains("rule.sequence.0"));
+    assert_eq!(
+        diagram.element_ids(),
+        Ok(vec![
+            "rule".to_owned(),
+            "keyword".to_owned(),
+            "rule.sequence.1".to_owned()
+        ])
+    );
+
+    diagram.set_title(Some("SELECT".to_owned()));
+    diagram.attr("id".to_owned()).or_insert("rule".to_owned());
+    let error = diagram.element_ids().unwrap_err();
+    assert_eq!(error.id, "rule");
+    assert_eq!(error.to_string(), "the id `rule` is used more than once");
+
+    diagram.set_element_ids(None).unwrap();
+    assert_eq!(
+        diagram
+            .set_element_ids(Some("rule".to_owned()))
+            .unwrap_err()
+            .id,
+        "rule"
+    );
+    assert!(!diagram.to_string().contains("rule.sequence.1"));
+}
+
+#[test]
+fn diagram_with_duplicate_ids_is_written_and_reported() {
+    let mut diagram = Diagram::new(NonTerminal::new("columns".to_owned()));
+    diagram.set_element_ids(Some("rule".to_owned())).unwrap();
+    diagram.attr("id".to_owned()).or_insert("rule".to_owned());
+    assert_eq!(diagram.to_string().matches("id=\"rule\"").count(), 2);
+    assert_eq!(diagram.element_ids().unwrap_err().id, "rule");
+}
+
+#[test]
+fn diagram_element_ids_survive_copied_geometry() {
+    /// A custom node which draws its child from a copy of its geometry.
+    struct Copying(Terminal);
+
+    impl Node for Copying {
+        fn entry_height(&self) -> i64 {
+            self.0.entry_height()
+        }
+        fn height(&self) -> i64 {
+            self.0.height()
+        }
+        fn width(&self) -> i64 {
+            self.0.width()
+        }
+        fn draw(&self, x: i64, y: i64, h_dir: svg::HDir) -> svg::Element {
+            self.draw_with_geometry(x, y, h_dir, &self.compute_geometry())
+        }
+        fn compute_geometry(&self) -> NodeGeometry {
+            let child = self.0.compute_geometry();
+            NodeGeometry {
+                children: vec![child.clone()],
+                ..child
+            }
+        }
+        fn draw_with_geometry(
+            &self,
+            x: i64,
+            y: i64,
+            h_dir: svg::HDir,
+            geo: &NodeGeometry,
+        ) -> svg::Element {
+            let mut backend = ElementBackend::default();
+            self.emit_with_geometry(&mut backend, x, y, h_dir, geo)
+                .unwrap();
+            backend.into_group()
+        }
+        fn emit_with_geometry(
+            &self,
+            backend: &mut dyn RenderBackend,
+            x: i64,
+            y: i64,
+            h_dir: svg::HDir,
+            geo: &NodeGeometry,
+        ) -> fmt::Result {
+            let copy = geo.children[0].clone();
+            backend.push_child(&self.0, x, y, h_dir, &copy)
+        }
+        fn describe(&self) -> Description {
+            Description::new("copying").with_child(self.0.describe())
+        }
+    }
+
+    let mut diagram = Diagram::new(Copying(Terminal::new("x".to_owned())));
+    diagram.set_element_ids(Some("rule".to_owned())).unwrap();
+    assert_eq!(
+        diagram.element_ids(),
+        Ok(vec!["rule".to_owned(), "rule.copying.0".to_owned()])
+    );
+}
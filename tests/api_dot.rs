@@ -0,0 +1,122 @@
+use railroad::grammar::{Expr, Grammar, Rule};
+use railroad::import::yacc;
+use railroad::{Diagram, Node, NonTerminal, Optional, Sequence, Terminal, dot};
+
+const CALCULATOR: &str = r#"
+%token NUM
+%%
+input: %empty | input line ;
+line: '\n' | exp '\n' ;
+exp: NUM | exp '+' exp | '-' exp | '(' exp ')' | factor ;
+factor: "a\"b" | exp ;
+%%
+"#;
+
+/// The statements of `dot`, without indentation.
+fn statements(dot: &str) -> Vec<&str> {
+    dot.lines().map(str::trim).collect()
+}
+
+#[test]
+fn rules_are_connected_by_their_references() {
+    let grammar = yacc::import(CALCULATOR).unwrap();
+    let dot = grammar.to_dot(dot::Detail::Rules);
+    assert!(dot.starts_with("digraph grammar {\n"));
+    assert!(dot.ends_with("}\n"));
+    let statements = statements(&dot);
+    for stmt in [
+        r#"r0 [label="input"];"#,
+        r#"r1 [label="line"];"#,
+        r#"r2 [label="exp", color=red];"#,
+        r#"r3 [label="factor", color=red];"#,
+        "r0 -> r1 [weight=1];",
+        "r1 -> r2 [weight=1];",
+        r#"r2 -> r2 [weight=3, label="3", color=red, penwidth=2];"#,
+        "r2 -> r3 [weight=1, color=red, penwidth=2];",
+        "r3 -> r2 [weight=1, color=red, penwidth=2];",
+    ] {
+        assert!(statements.contains(&stmt), "missing `{stmt}` in\n{dot}");
+    }
+    assert!(!dot.contains("subgraph"));
+}
+
+#[test]
+fn undefined_names_are_dashed() {
+    let grammar = Grammar {
+        rules: vec![Rule::new(
+            "stmt".to_owned(),
+            Expr::sequence(vec![
+                Expr::NonTerminal("expr".to_owned()),
+                Expr::Terminal(";".to_owned()),
+                Expr::NonTerminal("expr".to_owned()),
+            ]),
+        )],
+    };
+    let dot = grammar.to_dot(dot::Detail::Rules);
+    let statements = statements(&dot);
+    assert!(statements.contains(&r#"r0 [label="stmt"];"#));
+    assert!(statements.contains(&r#"r1 [label="expr", style=dashed];"#));
+    assert!(statements.contains(&r#"r0 -> r1 [weight=2, label="2"];"#));
+}
+
+#[test]
+fn structure_is_expanded_into_clusters() {
+    let grammar = yacc::import(CALCULATOR).unwrap();
+    let dot = grammar.to_dot(dot::Detail::Structure);
+    assert_eq!(
+        dot.matches("subgraph cluster_").count(),
+        grammar.rules.len()
+    );
+    let statements = statements(&dot);
+    // Every rule is declared once, within its own cluster.
+    for (idx, rule) in grammar.rules.iter().enumerate() {
+        let cluster = statements
+            .iter()
+            .position(|stmt| *stmt == format!("subgraph cluster_{idx} {{"))
+            .unwrap();
+        assert!(statements[cluster + 2].starts_with(&format!("r{idx} [label=\"{}\"", rule.name)));
+        assert_eq!(
+            statements
+                .iter()
+                .filter(|stmt| stmt.starts_with(&format!("r{idx} [")))
+                .count(),
+            1
+        );
+    }
+    assert!(statements.contains(&r#"s3_1 [label="a\"b", style=rounded];"#));
+    assert!(statements.contains(&r#"s3_2 [label="exp", color=red];"#));
+    assert!(statements.contains(&"r3 -> s3_0 [arrowhead=none];"));
+    assert!(statements.contains(&"s3_0 -> s3_2 [arrowhead=none];"));
+    // Rule-level edges are kept.
+    assert!(statements.contains(&r#"r2 -> r2 [weight=3, label="3", color=red, penwidth=2];"#));
+    let depth = dot.chars().try_fold(0i64, |depth, c| {
+        let depth = match c {
+            '{' => depth + 1,
+            '}' => depth - 1,
+            _ => depth,
+        };
+        (depth >= 0).then_some(depth)
+    });
+    assert_eq!(depth, Some(0));
+}
+
+#[test]
+fn any_node_tree_can_be_a_rule() {
+    let call = Diagram::new(Sequence::new(vec![
+        Box::new(NonTerminal::new("name".to_owned())) as Box<dyn Node>,
+        Box::new(Terminal::new("(".to_owned())),
+        Box::new(Optional::new(NonTerminal::new("call".to_owned()))),
+        Box::new(Terminal::new(")".to_owned())),
+    ]));
+    let name = Terminal::new("x".to_owned());
+    let dot = dot::graph(
+        [("call", &call as &dyn Node), ("name", &name)],
+        dot::Detail::Structure,
+    );
+    let statements = statements(&dot);
+    assert!(statements.contains(&r#"r0 [label="call", color=red];"#));
+    assert!(statements.contains(&r#"r1 [label="name"];"#));
+    assert!(statements.contains(&"r0 -> r1 [weight=1];"));
+    assert!(statements.contains(&"r0 -> r0 [weight=1, color=red, penwidth=2];"));
+    assert!(statements.contains(&r#"s1_0 [label="x", style=rounded];"#));
+}
@@ -0,0 +1,222 @@
+use railroad::description::Description;
+use railroad::grammar::{Dialect, Expr, Grammar, Rule};
+use railroad::{
+    Choice, Comment, Empty, LabeledBox, MultiChoice, Node, NonTerminal, Optional, Repeat,
+    SeparatedList, Sequence, Stack, Terminal,
+};
+
+fn t(label: &str) -> Box<dyn Node> {
+    Box::new(Terminal::new(label.to_owned()))
+}
+
+fn nt(label: &str) -> Box<dyn Node> {
+    Box::new(NonTerminal::new(label.to_owned()))
+}
+
+fn all_dialects(expr: &Expr) -> [String; 3] {
+    [Dialect::W3c, Dialect::Iso, Dialect::Abnf].map(|dialect| expr.to_ebnf(dialect))
+}
+
+#[test]
+fn ebnf_parenthesizes_by_precedence() {
+    let expr = Expr::from_node(&Stack::new(vec![
+        Box::new(Choice::new(vec![
+            t("a"),
+            Box::new(Sequence::new(vec![t("b"), nt("c")])),
+        ])) as Box<dyn Node>,
+        Box::new(Optional::new(Sequence::new(vec![t("d"), nt("e")]))),
+        Box::new(Optional::new(Choice::new(vec![t("f"), nt("g")]))),
+        Box::new(Repeat::new(Sequence::new(vec![t("h"), nt("i")]), Empty)),
+        Box::new(Optional::new(Repeat::new(nt("j"), Empty))),
+    ]));
+
+    assert_eq!(
+        all_dialects(&expr),
+        [
+            r#"("a" | "b" c) ("d" e)? ("f" | g)? ("h" i)+ (j+)?"#,
+            r#"("a" | "b", c), ["d", e], ["f" | g], "h", i, {"h", i}, [j, {j}]"#,
+            r#"("a" / "b" c) ["d" e] ["f" / g] 1*("h" i) [1*j]"#,
+        ]
+    );
+}
+
+#[test]
+fn ebnf_choices_with_empty_alternatives_become_optional() {
+    let expr = Expr::from_node(&Choice::new(vec![
+        Box::new(Empty) as Box<dyn Node>,
+        t("ALL"),
+        t("DISTINCT"),
+    ]));
+
+    assert_eq!(
+        all_dialects(&expr),
+        [
+            r#"("ALL" | "DISTINCT")?"#,
+            r#"["ALL" | "DISTINCT"]"#,
+            r#"["ALL" / "DISTINCT"]"#,
+        ]
+    );
+}
+
+#[test]
+fn ebnf_writes_comments_and_labeled_boxes_as_comments() {
+    let expr = Expr::from_node(&Sequence::new(vec![
+        Box::new(LabeledBox::new(
+            nt("body"),
+            Comment::new("block *) */".to_owned()),
+        )) as Box<dyn Node>,
+        Box::new(Comment::new("then".to_owned())),
+        t("end"),
+    ]));
+
+    assert_eq!(
+        all_dialects(&expr),
+        [
+            r#"body /* block *) * / */ /* then */ "end""#,
+            r#"body, (* block * ) */ *), (* then *), "end""#,
+            "body ; block *) */\n    ; then\n    \"end\"",
+        ]
+    );
+}
+
+#[test]
+fn ebnf_expresses_bounds_where_the_dialect_can() {
+    let mut exact = Repeat::new(nt("digit"), Empty);
+    exact.set_bounds(4, Some(4));
+    let mut list = SeparatedList::new(nt("item"), t(","));
+    list.set_bounds(2, Some(5));
+    let expr = Expr::from_node(&Sequence::new(vec![
+        Box::new(exact) as Box<dyn Node>,
+        Box::new(list),
+    ]));
+
+    assert_eq!(
+        all_dialects(&expr),
+        [
+            r#"digit+ /* {4} */ item ("," item)* /* {2,5} */"#,
+            r#"4 * digit, item, {",", item}, (* {2,5} *)"#,
+            r#"4digit item 1*4("," item)"#,
+        ]
+    );
+}
+
+#[test]
+fn ebnf_flattens_multichoice_columns() {
+    let expr = Expr::from_node(&MultiChoice::new(vec![vec![t("a"), t("b")], vec![nt("c")]]));
+
+    assert_eq!(expr.to_ebnf(Dialect::W3c), r#""a" | "b" | c"#);
+}
+
+#[test]
+fn ebnf_quotes_terminals_per_dialect() {
+    let expr = Expr::from_node(&Sequence::new(vec![t("say \"hi\""), t("ü")]));
+
+    assert_eq!(
+        all_dialects(&expr),
+        [
+            r#"'say "hi"' "ü""#,
+            r#"'say "hi"', "ü""#,
+            "%x73.61.79.20.22.68.69.22 %xFC",
+        ]
+    );
+}
+
+#[test]
+fn ebnf_splits_terminals_with_both_quotes() {
+    let expr = Expr::from_node(&Optional::new(t(r#"a"b'c"#)));
+
+    assert_eq!(
+        all_dialects(&expr),
+        [r#"('a"b' "'c")?"#, r#"['a"b', "'c"]"#, "[%x61.22.62.27.63]",]
+    );
+}
+
+#[test]
+fn ebnf_writes_exactly_one_repetition_plainly() {
+    let mut once = Repeat::new(nt("digit"), Empty);
+    once.set_bounds(1, Some(1));
+    let mut list = SeparatedList::new(nt("item"), t(","));
+    list.set_bounds(1, Some(1));
+    let expr = Expr::from_node(&Sequence::new(vec![
+        Box::new(once) as Box<dyn Node>,
+        Box::new(list),
+    ]));
+
+    assert_eq!(
+        all_dialects(&expr),
+        ["digit item", "digit, item", "digit item"]
+    );
+}
+
+#[test]
+fn grammar_to_ebnf_writes_one_rule_per_line() {
+    let grammar = Grammar {
+        rules: vec![
+            Rule::new(
+                "arg_list".to_owned(),
+                Expr::repeat(
+                    Expr::NonTerminal("arg".to_owned()),
+                    Expr::Terminal(",".to_owned()),
+                ),
+            ),
+            Rule::new("nothing".to_owned(), Expr::Empty),
+        ],
+    };
+
+    assert_eq!(
+        grammar.to_ebnf(Dialect::W3c),
+        "arg_list ::= arg (\",\" arg)*\nnothing ::=\n"
+    );
+    assert_eq!(
+        grammar.to_ebnf(Dialect::Iso),
+        "arg_list = arg, {\",\", arg} ;\nnothing = ;\n"
+    );
+    assert_eq!(
+        grammar.to_ebnf(Dialect::Abnf),
+        "arg-list = arg *(\",\" arg)\nnothing = \"\"\n"
+    );
+}
+
+#[test]
+fn degenerate_bounds_are_normalized() {
+    let repeat = |min, max| Expr::Repeat {
+        inner: Box::new(Expr::Terminal("a".to_owned())),
+        repeat: Box::new(Expr::Terminal(",".to_owned())),
+        min,
+        max,
+    };
+
+    // Repeating at most zero times is written as nothing at all.
+    let never = Expr::sequence(vec![Expr::Terminal("x".to_owned()), repeat(0, Some(0))]);
+    assert_eq!(all_dialects(&never), [r#""x""#, r#""x""#, r#""x""#]);
+    let mut node = Repeat::new(t("a"), t(","));
+    node.set_bounds(0, Some(0));
+    assert_eq!(repeat(0, Some(0)).into_node().describe(), node.describe());
+
+    // A maximum below the minimum is raised to the minimum, as by the nodes.
+    assert_eq!(
+        all_dialects(&repeat(3, Some(2))),
+        all_dialects(&repeat(3, Some(3)))
+    );
+    assert_eq!(
+        repeat(3, Some(2)).into_node().describe(),
+        repeat(3, Some(3)).into_node().describe()
+    );
+
+    // The same holds for bounds read from a description.
+    for (min, max, normalized) in [
+        ("0", "0", repeat(0, Some(0))),
+        ("3", "2", repeat(3, Some(3))),
+    ] {
+        let desc = Description::new("repeat")
+            .with_property("min", min)
+            .with_property("max", max)
+            .with_child(Terminal::new("a".to_owned()).describe())
+            .with_child(Terminal::new(",".to_owned()).describe());
+        assert_eq!(Expr::from_description(&desc), normalized);
+        assert_eq!(
+            desc.into_node().unwrap().describe(),
+            normalized.into_node().describe()
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use railroad::grammar::Expr;
+use railroad::import::{json_schema, macro_rules, yacc};
+use railroad::{Diagram, Node, SourceSpan};
+
+const CALCULATOR: &str = r#"
+%{
+#include <math.h>
+%}
+%token NUM
+%token POW "**"
+%left '-' '+'
+%%
+input:
+    %empty
+  | input line
+  ;
+
+line: '\n' | exp '\n' { printf ("%.10g\n", $1); } ;
+
+args: args ',' exp | exp ;
+
+exp:
+    NUM
+  | exp '+' exp        { $$ = $1 + $3; }
+  | '-' exp  %prec NEG { $$ = -$2; }
+  | exp POW exp
+  | '(' exp ')'
+  | error
+  ;
+%%
+"#;
+
+#[test]
+fn yacc_grammar_becomes_one_rule_per_nonterminal() {
+    let grammar = yacc::import(CALCULATOR).unwrap();
+
+    let names: Vec<_> = grammar.rules.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["input", "line", "args", "exp"]);
+
+    assert_eq!(
+        grammar.rule("line").unwrap().expr,
+        Expr::Choice(vec![
+            Expr::Terminal("\\n".to_owned()),
+            Expr::Sequence(vec![
+                Expr::NonTerminal("exp".to_owned()),
+                Expr::Terminal("\\n".to_owned()),
+            ]),
+        ])
+    );
+    assert_eq!(
+        grammar.rule("args").unwrap().expr,
+        Expr::repeat(
+            Expr::NonTerminal("exp".to_owned()),
+            Expr::Terminal(",".to_owned())
+        )
+    );
+    // `%empty` as the base case makes the repetition optional.
+    assert_eq!(
+        grammar.rule("input").unwrap().expr,
+        Expr::Optional(Box::new(Expr::repeat(
+            Expr::NonTerminal("line".to_owned()),
+            Expr::Empty
+        )))
+    );
+}
+
+#[test]
+fn yacc_rules_render_as_diagrams() {
+    let grammar = yacc::import(CALCULATOR).unwrap();
+    let svg = grammar
+        .rule("exp")
+        .unwrap()
+        .clone()
+        .into_diagram()
+        .to_string();
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("class=\"choice\""));
+    assert!(svg.contains("class=\"repeat\""));
+    assert!(svg.contains("**"));
+    assert!(svg.contains("error</text>"));
+    assert!(!svg.contains("NEG"));
+    assert!(!svg.contains("$$"));
+}
+
+/// The spans of all terminals and non-terminals in `expr`, in order.
+fn spans(expr: &Expr) -> Vec<(String, SourceSpan)> {
+    let desc = expr.clone().into_node().describe();
+    let mut spans = Vec::new();
+    let mut stack = vec![&desc];
+    while let Some(desc) = stack.pop() {
+        if let Some(span) = desc.source_span() {
+            spans.push((desc.property("label").unwrap().to_owned(), span));
+        }
+        stack.extend(desc.children.iter().rev());
+    }
+    spans
+}
+
+#[test]
+fn yacc_symbols_carry_source_spans() {
+    let mut grammar = yacc::import(CALCULATOR).unwrap();
+    grammar.set_source_file(Some("calc.y".to_owned()));
+
+    let args = spans(&grammar.rule("args").unwrap().expr);
+    let labels = args
+        .iter()
+        .map(|(label, _)| label.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(labels, ["exp", ","]);
+    let offset = CALCULATOR.find("args: args ',' exp | exp").unwrap();
+    let (_, exp) = &args[0];
+    assert_eq!(exp.file.as_deref(), Some("calc.y"));
+    assert_eq!((exp.line, exp.column), (16, 22));
+    assert_eq!(exp.bytes, offset + 21..offset + 24);
+    let (_, comma) = &args[1];
+    assert_eq!(&CALCULATOR[comma.bytes.clone()], "','");
+
+    let pow = spans(&grammar.rule("exp").unwrap().expr)
+        .into_iter()
+        .find(|(label, _)| label == "**")
+        .unwrap();
+    assert_eq!(&CALCULATOR[pow.1.bytes], "POW");
+
+    let diagram = grammar.rules[0].clone().into_diagram();
+    assert!(
+        diagram
+            .source_spans()
+            .iter()
+            .all(|(_, span)| span.file.as_deref() == Some("calc.y"))
+    );
+}
+
+#[test]
+fn yacc_errors_report_line_and_column() {
+    let err = yacc::import("%%\nexp: '(' exp\n  | ')\n").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 5));
+    assert_eq!(err.to_string(), "3:5: unterminated literal");
+}
+
+#[test]
+fn macro_rules_arms_become_a_choice() {
+    let grammar = macro_rules::import(
+        r#"
+#[macro_export]
+macro_rules! hashmap {
+    () => { HashMap::new() };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut map = HashMap::new();
+        $(map.insert($key, $value);)+
+        map
+    }};
+}
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(grammar.rules.len(), 1);
+    assert_eq!(grammar.rules[0].name, "hashmap");
+    assert_eq!(
+        grammar.rules[0].expr,
+        Expr::Choice(vec![
+            Expr::Empty,
+            Expr::Sequence(vec![
+                Expr::repeat(
+                    Expr::Sequence(vec![
+                        Expr::NonTerminal("expr".to_owned()),
+                        Expr::Terminal("=>".to_owned()),
+                        Expr::NonTerminal("expr".to_owned()),
+                    ]),
+                    Expr::Terminal(",".to_owned()),
+                ),
+                Expr::Optional(Box::new(Expr::Terminal(",".to_owned()))),
+            ]),
+        ])
+    );
+
+    let svg = grammar.rules[0].clone().into_diagram().to_string();
+    assert!(svg.contains("class=\"repeat\""));
+    assert!(!svg.contains("insert"));
+}
+
+#[test]
+fn macro_rules_tokens_carry_source_spans() {
+    let src = "macro_rules! m {\n    ($($x:expr),* ; [$crate]) => {};\n}";
+    let grammar = macro_rules::import(src).unwrap();
+    let spans = spans(&grammar.rules[0].expr)
+        .into_iter()
+        .map(|(label, span)| (label, &src[span.bytes], span.line, span.column))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans,
+        [
+            ("expr".to_owned(), "$x:expr", 2, 8),
+            (",".to_owned(), ",", 2, 16),
+            (";".to_owned(), ";", 2, 19),
+            ("[".to_owned(), "[", 2, 21),
+            ("$crate".to_owned(), "$crate", 2, 22),
+            ("]".to_owned(), "]", 2, 28),
+        ]
+    );
+
+    let mut diagram = Diagram::new(grammar.rules[0].expr.clone().into_node());
+    diagram.set_element_ids(Some("m".to_owned())).unwrap();
+    let (id, span) = &diagram.source_spans()[2];
+    assert_eq!(id.as_deref(), Some("m.sequence.1"));
+    assert_eq!(span.bytes.start, src.find(';').unwrap());
+}
+
+#[test]
+fn macro_rules_errors_report_line_and_column() {
+    let err = macro_rules::import("macro_rules! m {\n    ($x) => {};\n}").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 7));
+}
+
+#[test]
+fn json_schema_objects_arrays_and_references() {
+    let grammar = json_schema::import(
+        r##"{
+            "title": "config",
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "level": { "enum": [1, 2, "max"] },
+                "plugins": { "type": "array", "items": { "$ref": "#/$defs/plugin" } }
+            },
+            "required": ["name"],
+            "$defs": {
+                "plugin": { "oneOf": [{ "type": "string" }, { "const": null }] }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let names: Vec<_> = grammar.rules.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["config", "plugin"]);
+
+    let t = |s: &str| Expr::Terminal(s.to_owned());
+    let nt = |s: &str| Expr::NonTerminal(s.to_owned());
+    assert_eq!(
+        grammar.rules[0].expr,
+        Expr::Sequence(vec![
+            t("{"),
+            Expr::Sequence(vec![t("\"name\""), t(":"), nt("string")]),
+            Expr::Optional(Box::new(Expr::Sequence(vec![
+                t(","),
+                t("\"level\""),
+                t(":"),
+                Expr::Choice(vec![t("1"), t("2"), t("\"max\"")]),
+            ]))),
+            Expr::Optional(Box::new(Expr::Sequence(vec![
+                t(","),
+                t("\"plugins\""),
+                t(":"),
+                Expr::Sequence(vec![
+                    t("["),
+                    Expr::Optional(Box::new(Expr::repeat(
+                        Expr::Link {
+                            inner: Box::new(nt("plugin")),
+                            uri: "#/$defs/plugin".to_owned(),
+                        },
+                        t(","),
+                    ))),
+                    t("]"),
+                ]),
+            ]))),
+            t("}"),
+        ])
+    );
+    assert_eq!(
+        grammar.rules[1].expr,
+        Expr::Choice(vec![nt("string"), t("null")])
+    );
+
+    let svg = grammar.rules[0].clone().into_diagram().to_string();
+    assert!(svg.contains("class=\"link\""));
+    assert!(svg.contains("xlink:href=\"#/$defs/plugin\""));
+}
+
+#[test]
+fn json_schema_literals_carry_source_spans() {
+    let src = r##"{
+  "properties": {
+    "mode": { "enum": ["fast", 2] },
+    "next": { "$ref": "#/$defs/node" }
+  }
+}"##;
+    let grammar = json_schema::import(src).unwrap();
+    let spans = spans(&grammar.rules[0].expr)
+        .into_iter()
+        .map(|(label, span)| (label, &src[span.bytes], span.line, span.column))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans,
+        [
+            ("\"mode\"".to_owned(), "\"mode\"", 3, 5),
+            ("\"fast\"".to_owned(), "\"fast\"", 3, 24),
+            ("2".to_owned(), "2", 3, 32),
+            ("\"next\"".to_owned(), "\"next\"", 4, 5),
+            ("node".to_owned(), "\"#/$defs/node\"", 4, 23),
+            // Again, as the first property present.
+            ("\"next\"".to_owned(), "\"next\"", 4, 5),
+            ("node".to_owned(), "\"#/$defs/node\"", 4, 23),
+        ]
+    );
+}
+
+#[test]
+fn json_schema_rejects_invalid_json() {
+    let err = json_schema::import("{\n  \"type\": \"object\",\n}").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 1));
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use std::cell::Cell;
+
+use railroad::{
+    Choice, Comment, Diagram, Node, Orientation, Repeat, Sequence, Terminal,
+    svg::{self, HDir},
+};
+
+use crate::common::{basic_sequence, boxed};
+
+fn text_tags(svg: &str) -> Vec<&str> {
+    svg.lines().filter(|l| l.starts_with("<text")).collect()
+}
+
+#[test]
+fn vertical_diagram_flows_top_to_bottom() {
+    let horizontal = Diagram::new(basic_sequence());
+    assert!(horizontal.width() > horizontal.height());
+
+    let mut vertical = Diagram::new(basic_sequence());
+    vertical.set_orientation(Orientation::Vertical);
+    assert!(vertical.height() > vertical.width());
+
+    let svg = vertical.to_string();
+    assert!(svg.contains(&format!(
+        "viewBox=\"0 0 {} {}\"",
+        vertical.width(),
+        vertical.height()
+    )));
+    assert!(svg.contains("<g transform=\"matrix(0 1 1 0 10 10)\">"));
+}
+
+#[test]
+fn vertical_text_stays_upright_and_centered() {
+    let horizontal = Diagram::new(Terminal::new("upright".to_owned()));
+    let mut vertical = Diagram::new(Terminal::new("upright".to_owned()));
+    vertical.set_orientation(Orientation::Vertical);
+
+    // A lone box looks the same either way.
+    assert_eq!(vertical.width(), horizontal.width());
+    assert_eq!(vertical.height(), horizontal.height());
+
+    let horizontal = horizontal.to_string();
+    let vertical = vertical.to_string();
+    let expected =
+        text_tags(&horizontal)[0].replace('>', " transform=\"matrix(0 1 1 0 -10 -10)\">");
+    assert_eq!(text_tags(&vertical), [expected.as_str()]);
+}
+
+#[test]
+fn vertical_multi_line_text_stays_upright_and_centered() {
+    let horizontal = Diagram::new(Terminal::new("two\nlines".to_owned()));
+    let mut vertical = Diagram::new(Terminal::new("two\nlines".to_owned()));
+    vertical.set_orientation(Orientation::Vertical);
+    assert_eq!(vertical.width(), horizontal.width());
+    assert_eq!(vertical.height(), horizontal.height());
+
+    let horizontal = horizontal.to_string();
+    let vertical = vertical.to_string();
+    let tspans = |svg: &str| -> Vec<String> {
+        svg.split("<tspan")
+            .skip(1)
+            .map(|tspan| tspan.split_once("</tspan>").unwrap().0.to_owned())
+            .collect()
+    };
+    assert_eq!(tspans(&vertical), tspans(&horizontal));
+    assert_eq!(tspans(&vertical).len(), 2);
+
+    let mut dia = Diagram::new(Terminal::new("two\nlines".to_owned()));
+    dia.set_orientation(Orientation::Vertical);
+    assert_eq!(
+        tspans(&dia.draw(0, 0, HDir::LTR).to_string()),
+        tspans(&dia.to_string())
+    );
+}
+
+#[test]
+fn vertical_element_tree_matches_streaming_output() {
+    let root = Choice::new(vec![
+        boxed(Terminal::new("a".to_owned())),
+        boxed(Repeat::new(
+            Comment::new("note".to_owned()),
+            Terminal::new(",".to_owned()),
+        )),
+    ]);
+    let mut dia = Diagram::new(root);
+    dia.set_orientation(Orientation::Vertical);
+
+    let streamed = dia.to_string();
+    let element = dia.draw(0, 0, HDir::LTR).to_string();
+    let streamed_texts = text_tags(&streamed);
+    assert_eq!(streamed_texts.len(), 3);
+    assert!(
+        streamed_texts
+            .iter()
+            .all(|t| t.contains("transform=\"matrix(0 1 1 0 -10 -10)\""))
+    );
+    assert_eq!(
+        element
+            .matches("transform=\"matrix(0 1 1 0 -10 -10)\"")
+            .count(),
+        3
+    );
+    assert!(element.contains("transform=\"matrix(0 1 1 0 10 10)\""));
+}
+
+#[test]
+fn orientation_does_not_leak_out_of_the_diagram() {
+    let terminal = Terminal::new("leak".to_owned());
+    let (width, height) = (terminal.width(), terminal.height());
+
+    let mut dia = Diagram::new(&terminal);
+    dia.set_orientation(Orientation::Vertical);
+    let _ = dia.to_string();
+
+    assert_eq!((terminal.width(), terminal.height()), (width, height));
+}
+
+#[test]
+fn text_is_laid_out_transposed_only_when_asked() {
+    let terminal = Terminal::new("upright".to_owned());
+    let (width, height) = (terminal.width(), terminal.height());
+
+    let geo = terminal.compute_geometry_in(Orientation::Vertical);
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (width / 2, width, height)
+    );
+    assert_eq!(geo.orientation, Orientation::Vertical);
+    let geo = Sequence::new(vec![&terminal]).compute_geometry_in(Orientation::Vertical);
+    assert_eq!(
+        (geo.children[0].height, geo.children[0].width),
+        (width, height)
+    );
+    assert_eq!(terminal.compute_geometry().width, width);
+
+    /// Records the width of its child while being drawn.
+    struct Probe<'a>(&'a Terminal, &'a Cell<i64>);
+
+    impl Node for Probe<'_> {
+        fn entry_height(&self) -> i64 {
+            0
+        }
+        fn height(&self) -> i64 {
+            0
+        }
+        fn width(&self) -> i64 {
+            0
+        }
+        fn draw(&self, _: i64, _: i64, _: HDir) -> svg::Element {
+            self.1.set(self.0.width());
+            svg::Element::new("g")
+        }
+    }
+
+    let measured = Cell::new(0);
+    let mut dia = Diagram::new(Probe(&terminal, &measured));
+    dia.set_orientation(Orientation::Vertical);
+    let _ = dia.to_string();
+    assert_eq!(measured.get(), width);
+}
+
+#[test]
+fn mirrored_diagram_keeps_its_size_and_text() {
+    let horizontal = Diagram::new(basic_sequence());
+    let mut mirrored = Diagram::new(basic_sequence());
+    mirrored.set_mirrored(true);
+    assert_eq!(mirrored.width(), horizontal.width());
+    assert_eq!(mirrored.height(), horizontal.height());
+
+    let svg = mirrored.to_string();
+    let transform = format!("matrix(-1 0 0 1 {} 10)", horizontal.width() - 10);
+    assert!(svg.contains(&format!("<g transform=\"{transform}\">")));
+
+    // The label is symmetric within the sequence, so it ends up where it was;
+    // the inverse transform keeps the glyphs from being mirrored.
+    let horizontal = horizontal.to_string();
+    let inverse = format!("matrix(-1 0 0 1 {} -10)", mirrored.width() - 10);
+    let expected = text_tags(&horizontal)[0].replace('>', &format!(" transform=\"{inverse}\">"));
+    assert_eq!(text_tags(&svg), [expected.as_str()]);
+}
+
+#[test]
+fn mirroring_composes_with_orientation() {
+    let mut dia = Diagram::new(basic_sequence());
+    dia.set_orientation(Orientation::Vertical)
+        .set_mirrored(true);
+    let transform = format!("matrix(0 1 -1 0 {} 10)", dia.width() - 10);
+
+    let streamed = dia.to_string();
+    let element = dia.draw(0, 0, HDir::LTR).to_string();
+    assert!(streamed.contains(&format!("<g transform=\"{transform}\">")));
+    assert!(element.contains(&format!("transform=\"{transform}\"")));
+    let inverse = format!("matrix(0 -1 1 0 -10 {})", dia.width() - 10);
+    assert!(text_tags(&streamed)[0].contains(&inverse));
+    assert!(element.contains(&inverse));
+}
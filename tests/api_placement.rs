@@ -0,0 +1,257 @@
+mod common;
+
+use railroad::{
+    Choice, Comment, Diagram, Empty, Link, Node, NodeGeometry, NonTerminal, Optional, Orientation,
+    Repeat, SimpleEnd, SimpleStart, Terminal, Tooltip,
+    placement::{PlacedNode, Primitive},
+    svg::{self, HDir},
+};
+
+use crate::common::{basic_sequence, boxed};
+
+fn all_nodes(node: &PlacedNode) -> Vec<&PlacedNode> {
+    let mut nodes = vec![node];
+    for child in &node.children {
+        nodes.extend(all_nodes(child));
+    }
+    nodes
+}
+
+fn all_primitives(node: &PlacedNode) -> Vec<&Primitive> {
+    all_nodes(node)
+        .into_iter()
+        .flat_map(|n| &n.primitives)
+        .collect()
+}
+
+fn inside(inner: &PlacedNode, outer: &PlacedNode) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+fn grammar() -> Diagram<Box<dyn Node>> {
+    let mut diagram = Diagram::new(boxed(railroad::Sequence::new(vec![
+        boxed(SimpleStart),
+        boxed(Choice::new(vec![
+            boxed(Terminal::new("a".to_owned())),
+            boxed(Optional::new(NonTerminal::new("b".to_owned()))),
+        ])),
+        boxed(Repeat::new(
+            Link::new(
+                Terminal::new("c".to_owned()),
+                "https://example.com".to_owned(),
+            ),
+            Comment::new("again".to_owned()),
+        )),
+        boxed(SimpleEnd),
+    ])));
+    diagram.set_title(Some("grammar".to_owned()));
+    diagram
+}
+
+#[test]
+fn every_node_is_placed_within_its_parent() {
+    let diagram = grammar();
+    let layout = diagram.layout();
+    assert_eq!(layout.kind, "diagram");
+    assert_eq!(layout.label.as_deref(), Some("grammar"));
+    assert_eq!(
+        (layout.width, layout.height),
+        (diagram.width(), diagram.height())
+    );
+
+    fn check(node: &PlacedNode) {
+        for child in &node.children {
+            assert!(inside(child, node), "{child:?} is outside of {node:?}");
+            check(child);
+        }
+    }
+    check(&layout);
+
+    let kinds = all_nodes(&layout)
+        .into_iter()
+        .map(|n| n.kind.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            "diagram",
+            "sequence",
+            "simplestart",
+            "choice",
+            "terminal",
+            "optional",
+            "nonterminal",
+            "repeat",
+            "comment",
+            "link",
+            "terminal",
+            "simpleend"
+        ]
+    );
+}
+
+#[test]
+fn primitives_match_the_svg() {
+    let diagram = grammar();
+    let svg = diagram.to_string();
+    let primitives = all_primitives(&diagram.layout())
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // The canvas is drawn by the SVG only.
+    let rects = primitives
+        .iter()
+        .filter(|p| matches!(p, Primitive::Rect { .. }))
+        .count();
+    assert_eq!(rects, svg.matches("<rect").count() - 1);
+    let arcs = primitives
+        .iter()
+        .filter(|p| matches!(p, Primitive::Arc { .. }))
+        .count();
+    assert_eq!(arcs, svg.matches(" a ").count());
+
+    let texts = primitives
+        .iter()
+        .filter_map(|p| match p {
+            Primitive::Text { lines, .. } => Some(lines.join("\n")),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(texts, ["a", "b", "again", "c"]);
+}
+
+#[test]
+fn text_boxes_match_their_nodes() {
+    let layout = Diagram::new(basic_sequence()).layout();
+    let terminal = &layout.children[0].children[1];
+    assert_eq!(terminal.label.as_deref(), Some("BEGIN"));
+    assert_eq!(
+        terminal.primitives,
+        [
+            Primitive::Rect {
+                x: terminal.x,
+                y: terminal.y,
+                width: terminal.width,
+                height: terminal.height,
+                radius: 10,
+            },
+            Primitive::Text {
+                x: terminal.x + terminal.width / 2,
+                y: terminal.y + terminal.entry_height + 5,
+                lines: vec!["BEGIN".to_owned()],
+                line_height: 16,
+            },
+        ]
+    );
+}
+
+#[test]
+fn vertical_diagrams_are_placed_on_screen() {
+    let mut vertical = Diagram::new(basic_sequence());
+    vertical.set_orientation(Orientation::Vertical);
+    let layout = vertical.layout();
+    let sequence = &layout.children[0];
+    assert!(sequence.height > sequence.width);
+
+    let [start, terminal, end] = sequence.children.as_slice() else {
+        panic!("expected three children");
+    };
+    assert!(start.y < terminal.y && terminal.y < end.y);
+    assert_eq!(
+        terminal.x + terminal.entry_height,
+        sequence.x + sequence.entry_height
+    );
+
+    // The box is drawn around the text, which stays upright in its middle.
+    let horizontal = Diagram::new(basic_sequence()).layout();
+    let upright = &horizontal.children[0].children[1];
+    assert_eq!(
+        (terminal.width, terminal.height),
+        (upright.width, upright.height)
+    );
+    match &terminal.primitives[..] {
+        [
+            Primitive::Rect {
+                x,
+                y,
+                width,
+                height,
+                ..
+            },
+            Primitive::Text {
+                x: text_x,
+                y: text_y,
+                ..
+            },
+        ] => {
+            assert_eq!(
+                (*x, *y, *width, *height),
+                (terminal.x, terminal.y, terminal.width, terminal.height)
+            );
+            assert_eq!(*text_x, x + width / 2);
+            assert_eq!(*text_y, y + height / 2 + 5);
+        }
+        other => panic!("unexpected primitives {other:?}"),
+    }
+}
+
+#[test]
+fn mirrored_arcs_turn_the_other_way() {
+    let clockwise = |layout: &PlacedNode| {
+        all_primitives(layout)
+            .into_iter()
+            .filter_map(|p| match p {
+                Primitive::Arc { clockwise, .. } => Some(*clockwise),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let node = || Optional::new(Terminal::new("x".to_owned()));
+    let mut mirrored = Diagram::new(node());
+    mirrored.set_mirrored(true);
+    let arcs = clockwise(&Diagram::new(node()).layout());
+    let mirrored_arcs = clockwise(&mirrored.layout());
+    assert!(!arcs.is_empty());
+    assert_eq!(mirrored_arcs, arcs.iter().map(|c| !c).collect::<Vec<_>>());
+}
+
+#[test]
+fn tooltips_and_foreign_nodes() {
+    struct Foreign;
+
+    impl Node for Foreign {
+        fn entry_height(&self) -> i64 {
+            5
+        }
+        fn height(&self) -> i64 {
+            10
+        }
+        fn width(&self) -> i64 {
+            20
+        }
+        fn draw(&self, x: i64, y: i64, _: HDir) -> svg::Element {
+            svg::Element::new("rect").set("x", &x).set("y", &y)
+        }
+    }
+
+    let tooltip = Tooltip::new(Empty, "nothing".to_owned());
+    let geo = tooltip.compute_geometry();
+    let placed = tooltip.place_with_geometry(3, 4, HDir::LTR, &geo);
+    assert_eq!(placed.kind, "tooltip");
+    assert_eq!(placed.children[0].kind, "empty");
+
+    let geo = NodeGeometry {
+        entry_height: 5,
+        height: 10,
+        width: 20,
+        ..Default::default()
+    };
+    let placed = Foreign.place_with_geometry(1, 2, HDir::LTR, &geo);
+    assert_eq!(placed.kind, railroad::description::UNKNOWN_KIND);
+    assert_eq!((placed.x, placed.y, placed.width), (1, 2, 20));
+    assert!(placed.primitives.is_empty());
+}
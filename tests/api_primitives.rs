@@ -86,7 +86,7 @@ fn node_defaults_and_collection_helpers_follow_the_public_contract() {
         entry_height: 4,
         height: 9,
         width: 1,
-        children: vec![],
+        ..Default::default()
     };
     assert_eq!(geo.height_below_entry(), 5);
 }
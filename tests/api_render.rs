@@ -35,3 +35,28 @@ fn render_api_reports_invalid_xml() {
         Err(render::Error::XMLParse(_))
     ));
 }
+
+#[cfg(feature = "pdf")]
+#[test]
+fn render_to_pdf_places_the_diagram_on_the_page() {
+    use railroad::{End, Node, NonTerminal, Sequence, Start};
+
+    let mut seq = Sequence::default();
+    seq.push(Box::new(Start) as Box<dyn Node>)
+        .push(Box::new(Terminal::new("render".to_owned())))
+        .push(Box::new(NonTerminal::new("pdf".to_owned())))
+        .push(Box::new(End));
+    let svg = Diagram::new_with_stylesheet(seq, &Stylesheet::LightRendersafe).to_string();
+
+    let pdf = render::to_pdf(&svg, &render::PageSize::A4 { margin: 36.0 }).unwrap();
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(pdf.ends_with(b"%%EOF"));
+    // Wide diagrams are placed on a landscape page
+    let pdf = String::from_utf8_lossy(&pdf);
+    assert!(pdf.contains("/MediaBox [0 0 841.89 595.28]"));
+
+    assert!(matches!(
+        render::to_pdf(&svg, &render::PageSize::Letter { margin: 400.0 }),
+        Err(render::Error::InvalidSize)
+    ));
+}
@@ -1,6 +1,9 @@
 mod common;
 
-use railroad::{Comment, Diagram, HorizontalGrid, NonTerminal, Terminal};
+use railroad::{
+    Comment, Diagram, HorizontalGrid, Node, NonTerminal, RichText, SourceSpan, Span, Terminal,
+    svg::HDir,
+};
 
 use crate::common::boxed;
 
@@ -38,3 +41,199 @@ fn text_nodes_escape_content_and_forward_representative_attributes() {
     assert!(svg.contains("data-role=\"rule\""));
     assert!(svg.contains("data-note=\"commentary\""));
 }
+
+#[test]
+fn line_breaks_stack_lines_around_the_entry() {
+    let single = NonTerminal::new("expr".to_owned());
+    let double = NonTerminal::new("expr\ntail".to_owned());
+    let triple = NonTerminal::new("expr\ntail\n<end>".to_owned());
+    assert_eq!((double.height(), double.width()), (22 + 16, single.width()));
+    assert_eq!(double.entry_height(), double.height() / 2);
+    assert_eq!((triple.entry_height(), triple.height()), (11 + 16, 22 + 32));
+
+    let svg = Diagram::new(&triple).to_string();
+    assert!(
+        svg.contains("<tspan x=\"40\" y=\"26\">expr</tspan>"),
+        "{svg}"
+    );
+    assert!(
+        svg.contains("<tspan x=\"40\" y=\"42\">tail</tspan>"),
+        "{svg}"
+    );
+    assert!(
+        svg.contains("<tspan x=\"40\" y=\"58\">&lt;end&gt;</tspan>"),
+        "{svg}"
+    );
+    // Line breaks between the lines would be drawn as spaces.
+    assert!(svg.contains("</tspan><tspan"), "{svg}");
+    // The middle line sits on the path, just like a single line does.
+    assert_eq!(10 + triple.entry_height() + 5, 42);
+    assert!(
+        Diagram::new(&single)
+            .to_string()
+            .contains("y=\"26\">\nexpr</text>")
+    );
+
+    let comment = Comment::new("a\nb".to_owned());
+    assert_eq!((comment.entry_height(), comment.height()), (17, 20 + 14));
+}
+
+#[test]
+fn labels_wrap_between_words_at_the_maximum_line_width() {
+    let mut terminal = Terminal::new("SELECT DISTINCT  ON\nALL".to_owned());
+    assert_eq!(terminal.max_line_width(), None);
+    terminal.set_max_line_width(Some(10));
+    assert_eq!(terminal.max_line_width(), Some(10));
+    assert_eq!(terminal.height(), 22 + 3 * 16);
+    assert_eq!(
+        terminal.width(),
+        Terminal::new("DISTINCT".to_owned()).width()
+    );
+    let svg = Diagram::new(&terminal).to_string();
+    assert!(svg.contains(">SELECT</tspan>"), "{svg}");
+    assert!(svg.contains(">DISTINCT</tspan>"), "{svg}");
+    assert!(svg.contains(">ON</tspan>"), "{svg}");
+    assert!(svg.contains(">ALL</tspan>"), "{svg}");
+
+    // A word wider than the maximum gets a line of its own.
+    let mut comment = Comment::new("a overlong b".to_owned());
+    comment.set_max_line_width(Some(3));
+    assert_eq!(comment.height(), 20 + 2 * 14);
+    assert!(
+        Diagram::new(&comment)
+            .to_string()
+            .contains(">overlong</tspan>")
+    );
+}
+
+#[test]
+fn rich_labels_render_styled_spans() {
+    let mut ident = Span::monospace("IDENT");
+    ident.class = Some("keyword".to_owned());
+    let label = RichText::new(vec![
+        ident,
+        Span::new(" "),
+        Span::italic("(ci)"),
+        Span::bold("!"),
+    ]);
+    assert_eq!(label.to_plain_text(), "IDENT (ci)!");
+    let terminal = Terminal::with_rich_label(label.clone());
+    // Regular spans are as wide as the plain text.
+    assert_eq!(
+        terminal.width(),
+        Terminal::new("IDENT (ci)!".to_owned()).width()
+    );
+
+    let svg = Diagram::new(&terminal).to_string();
+    assert!(
+        svg.contains(
+            "<tspan class=\"keyword\" font-family=\"monospace\">IDENT</tspan><tspan> </tspan>\
+             <tspan font-style=\"italic\">(ci)</tspan><tspan font-weight=\"bold\">!</tspan></text>"
+        ),
+        "{svg}"
+    );
+    let element = Diagram::new(&terminal).draw(0, 0, HDir::LTR).to_string();
+    assert!(
+        element.contains("<tspan font-style=\"italic\">(ci)</tspan>"),
+        "{element}"
+    );
+
+    // A rich text of a single unstyled span is drawn like a plain label.
+    let plain = Terminal::with_rich_label(RichText::from("IDENT"));
+    assert_eq!(
+        Diagram::new(plain).to_string(),
+        Diagram::new(Terminal::new("IDENT".to_owned())).to_string()
+    );
+}
+
+#[test]
+fn sub_and_superscripts_are_smaller_and_shifted() {
+    let mut text = RichText::default();
+    text.push(Span::new("x"))
+        .push(Span::subscript("10"))
+        .push(Span::superscript("2"))
+        .push(Span::new("y"));
+    let comment = Comment::with_rich_text(text);
+    assert!(comment.width() < Comment::new("x102y".to_owned()).width());
+    let svg = Diagram::new(&comment).to_string();
+    assert!(
+        svg.contains(
+            "<tspan>x</tspan><tspan font-size=\"70%\" dy=\"4\">10</tspan>\
+             <tspan font-size=\"70%\" dy=\"-10\">2</tspan><tspan dy=\"6\">y</tspan>"
+        ),
+        "{svg}"
+    );
+}
+
+#[test]
+fn rich_text_wraps_across_spans() {
+    let mut comment = Comment::with_rich_text(
+        [
+            Span::new("see "),
+            Span::bold("RFC 1234"),
+            Span::new(" for details"),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    comment.set_max_line_width(Some(12));
+    assert_eq!(comment.height(), 20 + 14);
+    let svg = Diagram::new(&comment).to_string();
+    assert!(
+        svg.contains(
+            "<tspan>see </tspan><tspan font-weight=\"bold\">RFC 1234</tspan></tspan>\
+             <tspan x=\"57\" y=\"39\">for details</tspan>"
+        ),
+        "{svg}"
+    );
+}
+
+#[test]
+fn source_spans_become_data_attributes_and_survive_descriptions() {
+    let src = "expr: term\n    | expr '+' term;";
+    let mut span = SourceSpan::locate(src, 20..23);
+    span.file = Some("calc.y".to_owned());
+    assert_eq!((span.line, span.column), (2, 10));
+
+    let mut terminal = Terminal::new("+".to_owned());
+    terminal.set_source_span(Some(span.clone()));
+    assert_eq!(terminal.source_span(), Some(&span));
+
+    let svg = Diagram::new(terminal.clone()).to_string();
+    for attr in [
+        r#"data-source-file="calc.y""#,
+        r#"data-source-line="2""#,
+        r#"data-source-column="10""#,
+        r#"data-source-start="20""#,
+        r#"data-source-end="23""#,
+    ] {
+        assert!(svg.contains(attr), "{attr} missing");
+    }
+
+    let desc = terminal.describe();
+    assert_eq!(desc.source_span(), Some(span.clone()));
+    let restored = desc.clone().into_node().unwrap();
+    assert_eq!(restored.describe(), desc);
+    assert_eq!(Diagram::new(restored).to_string(), svg);
+
+    // Replacing or removing the span replaces the attributes.
+    let mut comment = Comment::new("note".to_owned());
+    comment.set_source_span(Some(span));
+    comment.set_source_span(Some(SourceSpan::locate(src, 0..4)));
+    let svg = Diagram::new(comment.clone()).to_string();
+    assert!(!svg.contains("data-source-file"));
+    assert!(svg.contains(r#"data-source-end="4""#));
+    comment.set_source_span(None);
+    assert!(!Diagram::new(comment).to_string().contains("data-source-"));
+}
+
+#[test]
+fn malformed_source_spans_are_rejected() {
+    let mut nt = NonTerminal::new("x".to_owned());
+    nt.set_source_span(Some(SourceSpan::locate("x", 0..1)));
+    let mut desc = nt.describe();
+    desc.properties.retain(|(key, _)| key != "source_line");
+    let err = desc.into_node().unwrap_err();
+    assert_eq!(err.kind, "nonterminal");
+    assert!(err.message.contains("source_line"));
+}
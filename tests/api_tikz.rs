@@ -0,0 +1,158 @@
+mod common;
+
+use railroad::{
+    Choice, Comment, Diagram, Node, NonTerminal, Optional, Orientation, Repeat, SimpleEnd,
+    SimpleStart, Terminal, svg::HDir, tikz,
+};
+
+use crate::common::{basic_sequence, boxed};
+
+fn grammar() -> Diagram<Box<dyn Node>> {
+    Diagram::new(boxed(railroad::Sequence::new(vec![
+        boxed(SimpleStart),
+        boxed(Choice::new(vec![
+            boxed(Terminal::new("a".to_owned())),
+            boxed(Optional::new(NonTerminal::new("b".to_owned()))),
+        ])),
+        boxed(Repeat::new(
+            Terminal::new("c".to_owned()),
+            Comment::new("again".to_owned()),
+        )),
+        boxed(SimpleEnd),
+    ])))
+}
+
+/// The lines of `picture` drawing with the given generic style.
+fn drawn_in<'a>(picture: &'a str, style: &str) -> Vec<&'a str> {
+    picture
+        .lines()
+        .filter(|line| line.contains(&format!("[railroad/{style}")))
+        .collect()
+}
+
+#[test]
+fn every_box_and_label_becomes_a_node() {
+    let diagram = grammar();
+    let picture = diagram.to_tikz();
+    assert!(picture.starts_with("\\begin{tikzpicture}[x=0.75pt, y=-0.75pt, railroad/picture]\n"));
+    assert!(picture.ends_with("\\end{tikzpicture}\n"));
+    assert!(picture.contains(&format!(
+        "\\path[railroad/canvas] (0,0) rectangle ({},{});",
+        diagram.width(),
+        diagram.height()
+    )));
+
+    let boxes = drawn_in(&picture, "box");
+    assert_eq!(boxes.len(), 3);
+    assert!(boxes[0].contains("railroad/terminal box/.try"));
+    assert!(boxes[0].contains("rounded corners=7.5pt"));
+    assert!(boxes[1].contains("railroad/nonterminal box/.try"));
+    assert!(!boxes[1].contains("rounded corners"));
+
+    let texts = drawn_in(&picture, "text")
+        .into_iter()
+        .map(|line| &line[line.rfind('{').unwrap() + 1..line.len() - 2])
+        .collect::<Vec<_>>();
+    assert_eq!(texts, ["a", "b", "again", "c"]);
+
+    // Every arc of the SVG output is drawn, and every style referenced is defined.
+    let arcs = picture.matches(" arc[").count();
+    assert_eq!(arcs, diagram.to_string().matches(" a ").count());
+    for style in ["canvas", "path", "box", "text", "picture"] {
+        assert!(tikz::STYLES.contains(&format!("railroad/{style}/.style")));
+    }
+}
+
+#[test]
+fn boxes_and_labels_are_centered_on_the_layout() {
+    let diagram = Diagram::new(basic_sequence());
+    let terminal = &diagram.layout().children[0].children[1];
+    let center = (
+        terminal.x + terminal.width / 2,
+        terminal.y + terminal.height / 2,
+    );
+    let picture = diagram.to_tikz();
+    assert!(picture.contains(&format!(
+        "\\node[railroad/box, railroad/terminal box/.try, minimum width={}pt, minimum height={}pt, rounded corners=7.5pt] at ({},{}) {{}};",
+        terminal.width as f64 * 0.75,
+        terminal.height as f64 * 0.75,
+        center.0,
+        center.1
+    )));
+    assert!(picture.contains(&format!(
+        "\\node[railroad/text, railroad/terminal text/.try] at ({},{}) {{BEGIN}};",
+        center.0, center.1
+    )));
+}
+
+#[test]
+fn labels_are_escaped() {
+    let picture = tikz::picture(&Terminal::new(r"{$x_1 & 50%}\".to_owned()));
+    assert!(picture.contains(r"{\{\$x\_1 \& 50\%\}\textbackslash{}};"));
+}
+
+#[test]
+fn vertical_diagrams_keep_labels_upright() {
+    let mut vertical = Diagram::new(basic_sequence());
+    vertical.set_orientation(Orientation::Vertical);
+    let layout = vertical.layout();
+    let picture = vertical.to_tikz();
+    let terminal = &layout.children[0].children[1];
+    assert!(terminal.y > terminal.x);
+    assert!(picture.contains(&format!(
+        "] at ({},{}) {{BEGIN}};",
+        terminal.x + terminal.width / 2,
+        terminal.y + terminal.height / 2
+    )));
+    assert!(picture.contains(&format!(
+        "minimum width={}pt, minimum height={}pt",
+        terminal.width as f64 * 0.75,
+        terminal.height as f64 * 0.75
+    )));
+}
+
+#[test]
+fn any_node_tree_can_be_drawn() {
+    let picture = tikz::picture(&Optional::new(NonTerminal::new("x".to_owned())));
+    assert!(!picture.contains("railroad/canvas"));
+    let paths = drawn_in(&picture, "path");
+    assert!(!paths.is_empty());
+    assert!(paths.iter().all(|path| path.ends_with(';')));
+    assert!(picture.contains("railroad/optional path/.try"));
+
+    // The backend can be driven by hand, too.
+    let node = Comment::new("one\ntwo".to_owned());
+    let mut backend = tikz::TikzBackend::default();
+    railroad::RenderBackend::push_child(
+        &mut backend,
+        &node,
+        0,
+        0,
+        HDir::LTR,
+        &node.compute_geometry(),
+    )
+    .unwrap();
+    let picture = backend.into_picture();
+    let texts = drawn_in(&picture, "text");
+    assert_eq!(texts.len(), 2);
+    assert!(texts[0].contains("railroad/comment text/.try"));
+}
+
+#[test]
+fn documents_define_the_styles() {
+    let picture = grammar().to_tikz();
+    let document = tikz::document(&picture);
+    assert!(document.starts_with("\\documentclass[tikz]{standalone}\n"));
+    assert!(document.contains(tikz::STYLES));
+    assert!(document.contains(&picture));
+    assert!(document.ends_with("\\end{document}\n"));
+    let depth = document.chars().try_fold(0i64, |depth, c| {
+        let depth = match c {
+            '{' => depth + 1,
+            '}' => depth - 1,
+            _ => depth,
+        };
+        (depth >= 0).then_some(depth)
+    });
+    assert_eq!(depth, Some(0));
+}
@@ -1,6 +1,9 @@
 mod common;
 
-use railroad::{Comment, Diagram, LabeledBox, Link, LinkTarget, Node, Optional, Repeat, Terminal};
+use railroad::{
+    Comment, Diagram, Empty, LabeledBox, Link, LinkTarget, Node, NonTerminal, Optional, Repeat,
+    SeparatedList, Terminal, Tooltip, svg::HDir,
+};
 
 #[test]
 fn link_wraps_inner_node_and_emits_target_attributes() {
@@ -58,3 +61,159 @@ fn labeled_box_without_label_avoids_reserved_label_space() {
 
     assert!(unlabeled.height() < labeled.height());
 }
+
+#[test]
+fn zero_or_more_repeat_looks_like_an_optional_repeat() {
+    let zero = Repeat::zero_or_more(Terminal::new("item".to_owned()), Empty);
+    let optional = Optional::new(Repeat::one_or_more(Terminal::new("item".to_owned()), Empty));
+    assert_eq!(zero.bounds(), (0, None));
+    assert_eq!(
+        (zero.entry_height(), zero.height(), zero.width()),
+        (optional.entry_height(), optional.height(), optional.width())
+    );
+    let geo = zero.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (zero.entry_height(), zero.height(), zero.width())
+    );
+    assert_eq!(Diagram::new(zero).to_string().matches("<text").count(), 1);
+}
+
+#[test]
+fn bounded_repeat_is_annotated_below_the_loop() {
+    let plain = Repeat::new(Terminal::new("digit".to_owned()), Empty);
+    let mut bounded = Repeat::new(Terminal::new("digit".to_owned()), Empty);
+    for (min, max, annotation) in [
+        (2, Some(5), "{2,5}"),
+        (3, Some(3), "{3}"),
+        (2, None, "{2,}"),
+        (1, Some(4), "{1,4}"),
+    ] {
+        bounded.set_bounds(min, max);
+        assert_eq!(bounded.bounds(), (min, max));
+        assert_eq!(bounded.entry_height(), plain.entry_height());
+        assert_eq!(bounded.height(), plain.height() + 20);
+        let svg = Diagram::new(&bounded).to_string();
+        assert!(svg.contains(&format!(">\n{annotation}</text>")), "{svg}");
+    }
+
+    // A wide annotation widens the loop.
+    bounded.set_bounds(1000, Some(100_000));
+    assert_eq!(
+        bounded.width(),
+        12 + "{1000,100000}".len() as i64 * 7 + 10 + 12
+    );
+}
+
+#[test]
+fn degenerate_bounds_are_normalized() {
+    let mut repeat = Repeat::new(Terminal::new("digit".to_owned()), Empty);
+    repeat.set_bounds(3, Some(2));
+    assert_eq!(repeat.bounds(), (3, Some(3)));
+    let mut list = SeparatedList::new(
+        NonTerminal::new("expr".to_owned()),
+        Terminal::new(",".to_owned()),
+    );
+    list.set_bounds(3, Some(2));
+    assert_eq!(list.bounds(), (3, Some(3)));
+
+    // A loop which is never traversed is bypassed and annotated as such.
+    repeat.set_bounds(0, Some(0));
+    assert_eq!(repeat.bounds(), (0, Some(0)));
+    assert!(Diagram::new(&repeat).to_string().contains(">\n{0}</text>"));
+    list.set_bounds(0, Some(0));
+    assert!(list.allows_empty());
+}
+
+#[test]
+fn separated_list_is_a_repeat_with_the_separator_on_the_return_path() {
+    let list = SeparatedList::new(
+        NonTerminal::new("expr".to_owned()),
+        Terminal::new(",".to_owned()),
+    );
+    let repeat = Repeat::new(
+        NonTerminal::new("expr".to_owned()),
+        Terminal::new(",".to_owned()),
+    );
+    assert_eq!(list.bounds(), (1, None));
+    assert!(!list.allows_empty());
+    assert!(!list.trailing_separator());
+    assert_eq!(
+        (list.entry_height(), list.height(), list.width()),
+        (repeat.entry_height(), repeat.height(), repeat.width())
+    );
+    assert!(
+        Diagram::new(list)
+            .to_string()
+            .contains("class=\"separatedlist\"")
+    );
+}
+
+#[test]
+fn separated_list_options_widen_the_loop() {
+    let mut list = SeparatedList::new(
+        NonTerminal::new("expr".to_owned()),
+        Terminal::new(",".to_owned()),
+    );
+    let (height, width) = (list.height(), list.width());
+    let separator_width = Terminal::new(",".to_owned()).width();
+
+    list.set_trailing_separator(true);
+    assert_eq!(list.width(), width + separator_width + 4 * 12);
+    assert_eq!(list.entry_height(), 12 + 12);
+    assert_eq!(list.height(), height + 12 + 12 - 11);
+    let svg = Diagram::new(&list).to_string();
+    assert_eq!(svg.matches(">\n,</text>").count(), 2);
+
+    list.set_allow_empty(true);
+    assert!(list.allows_empty());
+    assert_eq!(list.bounds(), (0, None));
+    assert_eq!(list.width(), width + separator_width + 8 * 12);
+    assert_eq!(list.entry_height(), 12 + 12 + 12);
+
+    list.set_bounds(0, Some(3));
+    assert!(Diagram::new(&list).to_string().contains(">\n{0,3}</text>"));
+    list.set_allow_empty(false);
+    assert_eq!(list.bounds(), (1, Some(3)));
+    let geo = list.compute_geometry();
+    assert_eq!(
+        (geo.entry_height, geo.height, geo.width),
+        (list.entry_height(), list.height(), list.width())
+    );
+}
+
+#[test]
+fn tooltip_adds_title_and_description_in_both_backends() {
+    let mut tooltip = Tooltip::new(
+        NonTerminal::new("expr".to_owned()),
+        "An <expression>".to_owned(),
+    );
+    assert_eq!(tooltip.title(), "An <expression>");
+    assert_eq!(tooltip.description(), None);
+    let inner = NonTerminal::new("expr".to_owned());
+    assert_eq!(
+        (tooltip.entry_height(), tooltip.height(), tooltip.width()),
+        (inner.entry_height(), inner.height(), inner.width())
+    );
+
+    let svg = Diagram::new(&tooltip).to_string();
+    assert!(
+        svg.contains("<g class=\"tooltip\">\n<title>\nAn &lt;expression&gt;</title>\n<g class=\"nonterminal\""),
+        "{svg}"
+    );
+    assert!(!svg.contains("<desc"));
+
+    tooltip.set_description(Some("Evaluates to a \"number\" & more.".to_owned()));
+    assert_eq!(
+        tooltip.description(),
+        Some("Evaluates to a \"number\" & more.")
+    );
+    let expected = "<title>\nAn &lt;expression&gt;</title>\n\
+                    <desc>\nEvaluates to a &quot;number&quot; &amp; more.</desc>\n";
+    let streamed = Diagram::new(&tooltip).to_string();
+    let element = Diagram::new(&tooltip).draw(0, 0, HDir::LTR).to_string();
+    assert!(streamed.contains(expected), "{streamed}");
+    assert!(element.contains(expected), "{element}");
+    // Only the tooltip's own title is attached to its group.
+    assert_eq!(streamed.matches("<title>").count(), 1);
+}
@@ -24,6 +24,36 @@ macro_rules! verify {
     };
 }
 
+/// Some output is valid SVG 2 but not SVG 1.1; verify `$src` with the
+/// attributes named (or prefixed) by `$attrs` removed, and pin that those are
+/// what keeps `$src` itself from validating.
+macro_rules! verify_without_attrs {
+    ($testname:ident, [$($attr:expr),+], $src:expr) => {
+        #[test]
+        #[ignore]
+        fn $testname() {
+            let src: String = $src;
+            assert!(init_verifier().verify(src.clone()).is_err());
+            init_verifier()
+                .verify(strip_attrs(&src, &[$($attr),+]))
+                .unwrap();
+        }
+    };
+}
+
+/// Remove every attribute whose name starts with one of `attrs`.
+fn strip_attrs(src: &str, attrs: &[&str]) -> String {
+    let mut src = src.to_owned();
+    for attr in attrs {
+        while let Some(start) = src.find(&format!(" {attr}")) {
+            let value = start + src[start..].find("=\"").unwrap() + 2;
+            let end = value + src[value..].find('"').unwrap() + 1;
+            src.replace_range(start..end, "");
+        }
+    }
+    src
+}
+
 macro_rules! raw_dia {
     ($r:expr) => {
         railroad::Diagram::with_default_css($r).to_string()
@@ -96,14 +126,142 @@ verify!(
         vec![Box::new(term!("Baz")) as Box<dyn Node>],
     ]))
 );
+verify!(
+    simple_horizontal_choice,
+    dia!(railroad::HorizontalChoice::new(vec![
+        Box::new(term!("Foo")) as Box<dyn Node>,
+        Box::new(railroad::Empty),
+        Box::new(term!("Bar")),
+    ]))
+);
+verify!(
+    simple_optional_sequence,
+    dia!(railroad::OptionalSequence::new(vec![
+        term!("Foo"),
+        term!("Bar"),
+        term!("Baz"),
+    ]))
+);
+verify!(
+    simple_alternating_sequence,
+    dia!(railroad::AlternatingSequence::new(
+        term!("Foo"),
+        term!("Bar")
+    ))
+);
+verify!(
+    simple_unordered,
+    dia!(railroad::Unordered::new(vec![term!("Foo"), term!("Bar")]))
+);
 verify!(simple_stack, dia!(stck!(term!("Foo"), term!("Bar"))));
 verify!(simple_comment, dia!(cmt!("Foobar")));
+verify!(multi_line_terminal, dia!(term!("Foo\nBar\nBaz")));
+verify!(
+    rich_terminal,
+    dia!(railroad::Terminal::with_rich_label(
+        railroad::RichText::new(vec![
+            railroad::Span::monospace("Foo"),
+            railroad::Span::subscript("1"),
+            railroad::Span::italic(" bar"),
+        ])
+    ))
+);
+verify!(
+    wrapped_comment,
+    dia!({
+        let mut c = cmt!("Foo bar baz");
+        c.set_max_line_width(Some(7));
+        c
+    })
+);
 verify!(escape_comment, dia!(cmt!("Foo<bar>")));
 verify!(simple_vertical, dia!(vert!(term!("Foo"), term!("Bar"))));
 verify!(simple_horizontal, dia!(horiz!(term!("Foo"), term!("Bar"))));
 verify!(simple_repeat, dia!(rpt!(term!("Foo"))));
+verify!(
+    zero_or_more_repeat,
+    dia!(railroad::Repeat::zero_or_more(
+        term!("Foo"),
+        railroad::Empty
+    ))
+);
+verify!(
+    bounded_repeat,
+    dia!({
+        let mut r = rpt!(term!("Foo"));
+        r.set_bounds(2, Some(5));
+        r
+    })
+);
+verify!(
+    simple_separated_list,
+    dia!(railroad::SeparatedList::new(term!("Foo"), term!(",")))
+);
+verify!(
+    optional_separated_list,
+    dia!({
+        let mut l = railroad::SeparatedList::new(term!("Foo"), term!(","));
+        l.set_allow_empty(true);
+        l.set_trailing_separator(true);
+        l
+    })
+);
 verify!(simple_opt, dia!(opt!(term!("Foo"))));
 verify!(simple_lbox, dia!(lbox!(term!("Foo"))));
+verify!(
+    simple_tooltip,
+    dia!({
+        let mut t = railroad::Tooltip::new(term!("Foo"), "Foo".to_owned());
+        t.set_description(Some("Foo or Bar".to_owned()));
+        t
+    })
+);
+verify!(element_ids, {
+    let mut dia = railroad::Diagram::with_default_css(seq!(
+        railroad::SimpleStart,
+        choice!(term!("Foo"), nonterm!("Bar")),
+        railroad::SimpleEnd
+    ));
+    dia.set_element_ids(Some("rule".to_owned())).unwrap();
+    dia.to_string()
+});
+verify!(embedded_description, {
+    let mut dia = railroad::Diagram::with_default_css(seq!(
+        railroad::SimpleStart,
+        choice!(term!("Foo"), nonterm!("Bar<&>")),
+        railroad::SimpleEnd
+    ));
+    dia.set_embed_description(true);
+    dia.to_string()
+});
+// `role` and `aria-labelledby` are defined by SVG 2 and WAI-ARIA.
+verify_without_attrs!(accessible_diagram, ["role", "aria-labelledby"], {
+    let mut dia = railroad::Diagram::with_default_css(seq!(
+        railroad::SimpleStart,
+        term!("Foo"),
+        railroad::SimpleEnd
+    ));
+    dia.set_title(Some("Foo".to_owned()));
+    dia.set_description(Some(dia.text_alternative()));
+    dia.to_string()
+});
+// Custom `data-*` attributes are defined by SVG 2.
+verify_without_attrs!(source_spans, ["data-source-"], {
+    let src = "list: item ',' list;";
+    let mut item = railroad::NonTerminal::new("item".to_owned());
+    item.set_source_span(Some(railroad::SourceSpan::locate(src, 6..10)));
+    let mut comma = railroad::Terminal::new(",".to_owned());
+    let mut span = railroad::SourceSpan::locate(src, 11..14);
+    span.file = Some("list.y".to_owned());
+    comma.set_source_span(Some(span));
+    railroad::Diagram::with_default_css(seq!(
+        railroad::SimpleStart,
+        item,
+        comma,
+        railroad::SimpleEnd
+    ))
+    .to_string()
+});
 verify!(simple_link, dia!(lnk!(term!("Foo"))));
 verify!(
     blank_link,